@@ -43,6 +43,18 @@ impl From<libloading::Error> for Error {
     }
 }
 
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::new(ErrorKind::IoError(err))
+    }
+}
+
+impl From<image::ImageError> for Error {
+    fn from(err: image::ImageError) -> Self {
+        Error::new(ErrorKind::ImageError(err))
+    }
+}
+
 impl From<std::ffi::NulError> for Error {
     fn from(err: std::ffi::NulError) -> Self {
         Error::new(ErrorKind::NulError(err))
@@ -55,6 +67,7 @@ pub enum MutexType {
     FrameData,
     FrameCallback,
     Device,
+    Event,
 }
 
 pub trait MutexExt<T> {
@@ -82,6 +95,9 @@ pub enum ErrorKind {
     GxStatusError(i32),
     DeviceHandleError(String),
     FrameDataError(String),
+    InvalidParameter(String),
+    IoError(std::io::Error),
+    ImageError(image::ImageError),
     MutexPoisonError {
         mutex_type: MutexType,
         message: String,
@@ -98,6 +114,9 @@ impl std::fmt::Display for ErrorKind {
             ErrorKind::GxStatusError(e) => write!(f, "GxStatusError: {:?}", e),
             ErrorKind::GxiError(e) => write!(f, "GxiError: {:?}", e),
             ErrorKind::FrameDataError(e) => write!(f, "FrameDataError: {:?}", e),
+            ErrorKind::InvalidParameter(e) => write!(f, "InvalidParameter: {:?}", e),
+            ErrorKind::IoError(e) => write!(f, "IoError: {:?}", e),
+            ErrorKind::ImageError(e) => write!(f, "ImageError: {:?}", e),
             ErrorKind::MutexPoisonError {
                 mutex_type,
                 message,
@@ -118,6 +137,9 @@ impl std::fmt::Debug for ErrorKind {
             ErrorKind::GxStatusError(e) => write!(f, "GxStatusError: {:?}", e),
             ErrorKind::GxiError(e) => write!(f, "GxiError: {:?}", e),
             ErrorKind::FrameDataError(e) => write!(f, "FrameDataError: {:?}", e),
+            ErrorKind::InvalidParameter(e) => write!(f, "InvalidParameter: {:?}", e),
+            ErrorKind::IoError(e) => write!(f, "IoError: {:?}", e),
+            ErrorKind::ImageError(e) => write!(f, "ImageError: {:?}", e),
             ErrorKind::MutexPoisonError {
                 mutex_type,
                 message,
@@ -4,17 +4,20 @@ use crate::error::{Error, ErrorKind, MutexExt, MutexType};
 use crate::raw::gx_interface::*;
 use std::sync::{Arc, LazyLock, Mutex};
 
-pub static GXI: LazyLock<Arc<Mutex<Option<GXInstance>>>> =
+/// The backend every HAL call goes through, the GxIAPI library or e.g. a playback device.
+pub type GxiBackend = Box<dyn GXInterface + Send>;
+
+pub static GXI: LazyLock<Arc<Mutex<Option<GxiBackend>>>> =
     LazyLock::new(|| Arc::new(Mutex::new(None)));
 
 // 添加通用的 Mutex 检查函数
 pub fn gxi_check<T, F>(func: F) -> Result<T>
 where
-    F: FnOnce(&GXInstance) -> Result<T>,
+    F: FnOnce(&dyn GXInterface) -> Result<T>,
 {
     let gxi = GXI.lock_safe(MutexType::Gxi)?;
     if let Some(gxi) = gxi.as_ref() {
-        func(gxi)
+        func(gxi.as_ref())
     } else {
         Err(Error::new(ErrorKind::GxiError(format!(
             "GXI is None while gxi_check(). Please check your gxci_init situation."
@@ -30,7 +33,7 @@ pub fn gxci_init(dll_path: &str) -> Result<()> {
         println!("Warning: GXI is already initialized. Reinitializing.");
     }
 
-    *gxi = Some(GXInstance::new(dll_path)?);
+    *gxi = Some(Box::new(GXInstance::new(dll_path)?));
     gxi.as_ref().unwrap().gx_init_lib()?;
     Ok(())
 }
@@ -44,11 +47,23 @@ pub fn gxci_init_default() -> Result<()> {
         println!("Warning: GXI is already initialized. Reinitializing.");
     }
 
-    *gxi = Some(GXInstance::new(dll_path_default)?);
+    *gxi = Some(Box::new(GXInstance::new(dll_path_default)?));
     gxi.as_ref().unwrap().gx_init_lib()?;
     Ok(())
 }
 
+/// Initialize with another backend than the GxIAPI library, see `hal::playback`.
+pub fn gxci_init_with(backend: GxiBackend) -> Result<()> {
+    let mut gxi = GXI.lock_safe(MutexType::Gxi)?;
+    if gxi.is_some() {
+        println!("Warning: GXI is already initialized. Reinitializing.");
+    }
+
+    backend.gx_init_lib()?;
+    *gxi = Some(backend);
+    Ok(())
+}
+
 pub fn gxci_close() -> Result<()> {
     gxi_check(|gxi| gxi.gx_close_lib())?;
 
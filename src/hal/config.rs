@@ -5,7 +5,6 @@ use crate::hal::base::gxi_check;
 use crate::hal::check::check_gx_status;
 use crate::hal::device::gxi_get_device_handle;
 use crate::raw::gx_enum::{GX_FEATURE_ID, GX_FEATURE_TYPE};
-use crate::raw::gx_interface::GXInterface;
 use crate::raw::gx_struct::{GX_ENUM_DESCRIPTION, GX_FLOAT_RANGE, GX_INT_RANGE};
 use crate::utils::extract::{extract_n_value, extract_sz_symbolic};
 use crate::utils::matching::match_feature_type;
@@ -125,8 +124,19 @@ pub struct GxiEnumDescription {
 #[cfg(feature = "solo")]
 pub fn gxi_get_feature_name(feature_id: GX_FEATURE_ID) -> Result<String> {
     let gxi_device = gxi_get_device_handle()?;
-    let mut buffer_size: usize = 8;
-    let mut feature_name = vec![0u8; buffer_size];
+    // 先传空指针拿到名字需要的长度，再按长度分配，名字长短不一，固定长度会不够
+    let mut buffer_size: usize = 0;
+    let status = gxi_check(|gxi| {
+        gxi.gx_get_feature_name(
+            gxi_device,
+            feature_id,
+            std::ptr::null_mut(),
+            &mut buffer_size,
+        )
+    })?;
+    check_gx_status(status)?;
+
+    let mut feature_name = vec![0u8; buffer_size.max(1)];
     let status = gxi_check(|gxi| {
         gxi.gx_get_feature_name(
             gxi_device,
@@ -138,8 +148,47 @@ pub fn gxi_get_feature_name(feature_id: GX_FEATURE_ID) -> Result<String> {
     // 这里的feature_name.as_mut_ptr() as *mut i8是将feature_name的地址转换成i8类型的指钋
 
     check_gx_status(status)?;
+    let name_length = feature_name
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(feature_name.len());
+    feature_name.truncate(name_length);
     println!("Successfully get feature name.");
-    Ok(String::from_utf8(feature_name).unwrap())
+    Ok(String::from_utf8_lossy(&feature_name).into_owned())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_is_implemented(feature_id: GX_FEATURE_ID) -> Result<bool> {
+    let gxi_device = gxi_get_device_handle()?;
+    let mut is_implemented = false;
+    let status =
+        gxi_check(|gxi| gxi.gx_is_implemented(gxi_device, feature_id, &mut is_implemented))?;
+
+    check_gx_status(status)?;
+    println!("Successfully get is implemented.");
+    Ok(is_implemented)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_is_readable(feature_id: GX_FEATURE_ID) -> Result<bool> {
+    let gxi_device = gxi_get_device_handle()?;
+    let mut is_readable = false;
+    let status = gxi_check(|gxi| gxi.gx_is_readable(gxi_device, feature_id, &mut is_readable))?;
+
+    check_gx_status(status)?;
+    println!("Successfully get is readable.");
+    Ok(is_readable)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_is_writable(feature_id: GX_FEATURE_ID) -> Result<bool> {
+    let gxi_device = gxi_get_device_handle()?;
+    let mut is_writable = false;
+    let status = gxi_check(|gxi| gxi.gx_is_writable(gxi_device, feature_id, &mut is_writable))?;
+
+    check_gx_status(status)?;
+    println!("Successfully get is writable.");
+    Ok(is_writable)
 }
 
 #[cfg(feature = "solo")]
@@ -0,0 +1,302 @@
+//! Export and import of the SDK camera configuration file, and a reader for it to compare two configurations.
+
+use crate::error::{Error, ErrorKind, Result};
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+#[cfg(feature = "solo")]
+use crate::hal::base::gxi_check;
+#[cfg(feature = "solo")]
+use crate::hal::check::check_gx_status;
+#[cfg(feature = "solo")]
+use crate::hal::device::gxi_get_device_handle;
+#[cfg(feature = "solo")]
+use std::ffi::CString;
+
+#[cfg(feature = "solo")]
+fn path_to_c_string(path: &Path) -> Result<CString> {
+    let path = path.to_str().ok_or_else(|| {
+        Error::new(ErrorKind::InvalidParameter(format!(
+            "config file path {:?} is not valid UTF-8",
+            path
+        )))
+    })?;
+    Ok(CString::new(path)?)
+}
+
+/// Write the current device configuration to `path` in the SDK's config file format, the
+/// same file the Galaxy viewer saves.
+#[cfg(feature = "solo")]
+pub fn gxi_export_config<P: AsRef<Path>>(path: P) -> Result<()> {
+    let gxi_device = gxi_get_device_handle()?;
+    let c_path = path_to_c_string(path.as_ref())?;
+    let status = gxi_check(|gxi| gxi.gx_export_config_file(gxi_device, c_path.as_ptr()))?;
+
+    check_gx_status(status)?;
+    println!("Successfully exported config to {:?}", path.as_ref());
+    Ok(())
+}
+
+/// Write a config file from `gxi_export_config` or the Galaxy viewer to the device. With
+/// `verify` the SDK reads every value back and fails when one did not take. Stop acquisition
+/// first, the device locks some features while it runs.
+#[cfg(feature = "solo")]
+pub fn gxi_import_config<P: AsRef<Path>>(path: P, verify: bool) -> Result<()> {
+    let gxi_device = gxi_get_device_handle()?;
+    let c_path = path_to_c_string(path.as_ref())?;
+    let status = gxi_check(|gxi| gxi.gx_import_config_file(gxi_device, c_path.as_ptr(), verify))?;
+
+    check_gx_status(status)?;
+    println!("Successfully imported config from {:?}", path.as_ref());
+    Ok(())
+}
+
+//----------------------------------------------------------
+//---------------Config File Parsing------------------------
+//----------------------------------------------------------
+
+/// One `[section]` of a config file with its `key=value` entries in file order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigSection {
+    pub name: String,
+    pub entries: Vec<(String, String)>,
+}
+
+impl ConfigSection {
+    /// The value of `key`, the last one when the key repeats.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(entry_key, _)| entry_key == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The keys in file order, each once.
+    pub fn keys(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = Vec::new();
+        for (key, _) in &self.entries {
+            if !keys.contains(&key.as_str()) {
+                keys.push(key);
+            }
+        }
+        keys
+    }
+}
+
+/// A config file as written by `gxi_export_config`, in `.ini` style: `[section]` headers,
+/// `key=value` lines and `;` or `#` comments. Entries before the first header go into a
+/// section named `""`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigFile {
+    pub sections: Vec<ConfigSection>,
+}
+
+/// A key whose value differs between two config files, `None` where a file lacks it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigDifference {
+    pub section: String,
+    pub key: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl fmt::Display for ConfigDifference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let show = |value: &Option<String>| value.clone().unwrap_or("(missing)".to_string());
+        write!(
+            f,
+            "[{}] {}: {} -> {}",
+            self.section,
+            self.key,
+            show(&self.left),
+            show(&self.right)
+        )
+    }
+}
+
+// 导出的浮点数带6位小数，手写的文件不一定，数值相等就不算差异
+fn same_value(left: &str, right: &str) -> bool {
+    left == right
+        || matches!(
+            (left.parse::<f64>(), right.parse::<f64>()),
+            (Ok(left), Ok(right)) if left == right
+        )
+}
+
+impl ConfigFile {
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut config = ConfigFile::default();
+        let mut current = String::new();
+        for (number, line) in text.trim_start_matches('\u{feff}').lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+            {
+                current = name.trim().to_string();
+                config.section_mut(&current);
+            } else if let Some((key, value)) = line.split_once('=') {
+                config
+                    .section_mut(&current)
+                    .entries
+                    .push((key.trim().to_string(), value.trim().to_string()));
+            } else {
+                return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                    "config line {} is neither a [section] nor key=value: {}",
+                    number + 1,
+                    line
+                ))));
+            }
+        }
+        Ok(config)
+    }
+
+    /// Read and parse `path`. Bytes that are not UTF-8, e.g. from a Chinese Windows code
+    /// page, are replaced rather than failing the whole file.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let bytes = fs::read(path)?;
+        let text = String::from_utf8_lossy(&bytes);
+        Self::parse(&text)
+    }
+
+    fn section_mut(&mut self, name: &str) -> &mut ConfigSection {
+        let index = match self
+            .sections
+            .iter()
+            .position(|section| section.name == name)
+        {
+            Some(index) => index,
+            None => {
+                self.sections.push(ConfigSection {
+                    name: name.to_string(),
+                    entries: Vec::new(),
+                });
+                self.sections.len() - 1
+            }
+        };
+        &mut self.sections[index]
+    }
+
+    pub fn section(&self, name: &str) -> Option<&ConfigSection> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.section(section)?.get(key)
+    }
+
+    /// The keys whose values differ from `other`, in the order of this file with the keys
+    /// only `other` has at the end of their section. Numbers are compared by value, so
+    /// `1000` and `1000.000000` are the same.
+    pub fn diff(&self, other: &ConfigFile) -> Vec<ConfigDifference> {
+        let mut section_names: Vec<&str> = self.sections.iter().map(|s| s.name.as_str()).collect();
+        for section in &other.sections {
+            if !section_names.contains(&section.name.as_str()) {
+                section_names.push(&section.name);
+            }
+        }
+
+        let mut differences = Vec::new();
+        for name in section_names {
+            let left = self.section(name);
+            let right = other.section(name);
+            let mut keys = left.map(|section| section.keys()).unwrap_or_default();
+            for key in right.map(|section| section.keys()).unwrap_or_default() {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+
+            for key in keys {
+                let left_value = left.and_then(|section| section.get(key));
+                let right_value = right.and_then(|section| section.get(key));
+                let same = match (left_value, right_value) {
+                    (Some(left), Some(right)) => same_value(left, right),
+                    _ => false,
+                };
+                if !same {
+                    differences.push(ConfigDifference {
+                        section: name.to_string(),
+                        key: key.to_string(),
+                        left: left_value.map(str::to_string),
+                        right: right_value.map(str::to_string),
+                    });
+                }
+            }
+        }
+        differences
+    }
+}
+
+/// Read two config files and list where they differ, see `ConfigFile::diff`.
+pub fn diff_config_files<P: AsRef<Path>, Q: AsRef<Path>>(
+    left: P,
+    right: Q,
+) -> Result<Vec<ConfigDifference>> {
+    Ok(ConfigFile::read(left)?.diff(&ConfigFile::read(right)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn difference(
+        section: &str,
+        key: &str,
+        left: Option<&str>,
+        right: Option<&str>,
+    ) -> ConfigDifference {
+        ConfigDifference {
+            section: section.to_string(),
+            key: key.to_string(),
+            left: left.map(str::to_string),
+            right: right.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn parses_sections_comments_and_bom() {
+        let config = ConfigFile::parse(
+            "\u{feff}; exported\nVersion=1\n\n[Device]\n# comment\nExposureTime = 1000.000000\nGain=2\nGain=3\n",
+        )
+        .unwrap();
+        assert_eq!(config.get("", "Version"), Some("1"));
+        assert_eq!(config.get("Device", "ExposureTime"), Some("1000.000000"));
+        assert_eq!(config.get("Device", "Gain"), Some("3"));
+        assert_eq!(
+            config.section("Device").unwrap().keys(),
+            vec!["ExposureTime", "Gain"]
+        );
+        assert_eq!(config.get("Device", "Width"), None);
+    }
+
+    #[test]
+    fn rejects_a_line_that_is_neither_section_nor_entry() {
+        assert!(ConfigFile::parse("[Device]\nbad line\n").is_err());
+    }
+
+    #[test]
+    fn diff_compares_numbers_by_value_and_reports_missing_keys() {
+        let left = ConfigFile::parse("[Device]\nExposureTime=1000.000000\nGain=2\n[Info]\nSN=A\n")
+            .unwrap();
+        let right =
+            ConfigFile::parse("[Device]\nExposureTime=1000\nGain=3\nWidth=10\n[Other]\nX=1\n")
+                .unwrap();
+        assert_eq!(
+            left.diff(&right),
+            vec![
+                difference("Device", "Gain", Some("2"), Some("3")),
+                difference("Device", "Width", None, Some("10")),
+                difference("Info", "SN", Some("A"), None),
+                difference("Other", "X", None, Some("1")),
+            ]
+        );
+        assert!(left.diff(&left).is_empty());
+    }
+}
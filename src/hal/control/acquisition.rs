@@ -1,9 +1,21 @@
 //! Placeholder
 
-use crate::error::Result;
+use crate::error::{Error, ErrorKind, MutexExt, MutexType, Result};
 use crate::hal::config::*;
-use crate::hal::device::gxi_send_command;
-use crate::raw::gx_enum::GX_FEATURE_ID;
+use crate::hal::control::image_format::{gxi_get_height, gxi_get_pixel_format, gxi_get_width};
+use crate::hal::device::{GxiFrame, gxi_get_frame, gxi_send_command};
+use crate::hal::event::*;
+use crate::raw::gx_enum::{
+    GX_ACQUISITION_BURST_MODE_ENTRY, GX_ACQUISITION_FRAME_RATE_MODE_ENTRY,
+    GX_ACQUISITION_MODE_ENTRY, GX_EVENT_SELECTOR_ENTRY, GX_FEATURE_ID, GX_STATUS_LIST,
+    GX_TRIGGER_ACTIVATION_ENTRY, GX_TRIGGER_MODE_ENTRY, GX_TRIGGER_SELECTOR_ENTRY,
+    GX_TRIGGER_SOURCE_ENTRY,
+};
+use crate::raw::gx_pixel_format::PixelFormatEntry;
+
+use std::ffi::c_void;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "solo")]
 pub fn gxi_get_acquisition_mode() -> Result<i64> {
@@ -19,9 +31,32 @@ pub fn gxi_set_acquisition_mode(acquisition_mode: i64) -> Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "solo")]
+pub fn gxi_set_acquisition_mode_single_frame() -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_ACQUISITION_MODE,
+        &(GX_ACQUISITION_MODE_ENTRY::GX_ACQ_MODE_SINGLE_FRAME as i64),
+    )?;
+    println!("Now, acquisition mode is single frame");
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_acquisition_mode_multi_frame() -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_ACQUISITION_MODE,
+        &(GX_ACQUISITION_MODE_ENTRY::GX_ACQ_MODE_MULITI_FRAME as i64),
+    )?;
+    println!("Now, acquisition mode is multi frame");
+    Ok(())
+}
+
 #[cfg(feature = "solo")]
 pub fn gxi_set_acquisition_mode_continuous() -> Result<()> {
-    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_ACQUISITION_MODE, &1i64)?;
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_ACQUISITION_MODE,
+        &(GX_ACQUISITION_MODE_ENTRY::GX_ACQ_MODE_CONTINUOUS as i64),
+    )?;
     println!("Now, acquisition mode is continuous");
     Ok(())
 }
@@ -40,6 +75,151 @@ pub fn gxi_acquisition_stop() -> Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "solo")]
+pub fn gxi_get_acquisition_frame_count() -> Result<i64> {
+    let acquisition_frame_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_INT_ACQUISITION_FRAME_COUNT)?;
+    println!(
+        "Now, acquisition frame count is {}",
+        acquisition_frame_count
+    );
+    Ok(acquisition_frame_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_acquisition_frame_count(acquisition_frame_count: i64) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_INT_ACQUISITION_FRAME_COUNT,
+        &acquisition_frame_count,
+    )?;
+    println!(
+        "Now, acquisition frame count is {}",
+        acquisition_frame_count
+    );
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_acquisition_burst_frame_count() -> Result<i64> {
+    let acquisition_burst_frame_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_INT_ACQUISITION_BURST_FRAME_COUNT)?;
+    println!(
+        "Now, acquisition burst frame count is {}",
+        acquisition_burst_frame_count
+    );
+    Ok(acquisition_burst_frame_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_acquisition_burst_frame_count(acquisition_burst_frame_count: i64) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_INT_ACQUISITION_BURST_FRAME_COUNT,
+        &acquisition_burst_frame_count,
+    )?;
+    println!(
+        "Now, acquisition burst frame count is {}",
+        acquisition_burst_frame_count
+    );
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_acquisition_burst_mode() -> Result<i64> {
+    let acquisition_burst_mode: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_ACQUISITION_BURST_MODE)?;
+    println!("Now, acquisition burst mode is {}", acquisition_burst_mode);
+    Ok(acquisition_burst_mode)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_acquisition_burst_mode(
+    acquisition_burst_mode: GX_ACQUISITION_BURST_MODE_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_ACQUISITION_BURST_MODE,
+        &(acquisition_burst_mode as i64),
+    )?;
+    println!(
+        "Now, acquisition burst mode is {:?}",
+        acquisition_burst_mode
+    );
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_acquisition_frame_rate_mode() -> Result<i64> {
+    let acquisition_frame_rate_mode: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_ACQUISITION_FRAME_RATE_MODE)?;
+    println!(
+        "Now, acquisition frame rate mode is {}",
+        acquisition_frame_rate_mode
+    );
+    Ok(acquisition_frame_rate_mode)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_acquisition_frame_rate_mode(
+    acquisition_frame_rate_mode: GX_ACQUISITION_FRAME_RATE_MODE_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_ACQUISITION_FRAME_RATE_MODE,
+        &(acquisition_frame_rate_mode as i64),
+    )?;
+    println!(
+        "Now, acquisition frame rate mode is {:?}",
+        acquisition_frame_rate_mode
+    );
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_acquisition_frame_rate() -> Result<f64> {
+    let acquisition_frame_rate: f64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_FLOAT_ACQUISITION_FRAME_RATE)?;
+    println!("Now, acquisition frame rate is {}", acquisition_frame_rate);
+    Ok(acquisition_frame_rate)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_acquisition_frame_rate(acquisition_frame_rate: f64) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_FLOAT_ACQUISITION_FRAME_RATE,
+        &acquisition_frame_rate,
+    )?;
+    println!("Now, acquisition frame rate is {}", acquisition_frame_rate);
+    Ok(())
+}
+
+/// The frame rate the device actually reaches with the current exposure, ROI and bandwidth.
+#[cfg(feature = "solo")]
+pub fn gxi_get_current_acquisition_frame_rate() -> Result<f64> {
+    let current_acquisition_frame_rate: f64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_FLOAT_CURRENT_ACQUISITION_FRAME_RATE)?;
+    println!(
+        "Now, current acquisition frame rate is {}",
+        current_acquisition_frame_rate
+    );
+    Ok(current_acquisition_frame_rate)
+}
+
+/// Switch the frame rate control on with `frame_rate` as target, and return the rate the
+/// device can achieve, which is lower when the exposure or the bandwidth does not allow it.
+#[cfg(feature = "solo")]
+pub fn gxi_set_target_frame_rate(frame_rate: f64) -> Result<f64> {
+    gxi_set_acquisition_frame_rate_mode(
+        GX_ACQUISITION_FRAME_RATE_MODE_ENTRY::GX_ACQUISITION_FRAME_RATE_MODE_ON,
+    )?;
+    gxi_set_acquisition_frame_rate(frame_rate)?;
+    let achieved_frame_rate = gxi_get_current_acquisition_frame_rate()?;
+    if achieved_frame_rate < frame_rate {
+        println!(
+            "Target frame rate {} is limited to {} by the exposure or bandwidth",
+            frame_rate, achieved_frame_rate
+        );
+    }
+    Ok(achieved_frame_rate)
+}
+
 #[cfg(feature = "solo")]
 pub fn gxi_get_trigger_selector() -> Result<i64> {
     let trigger_selector: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_TRIGGER_SELECTOR)?;
@@ -56,8 +236,15 @@ pub fn gxi_set_trigger_selector(trigger_selector: i64) -> Result<()> {
 
 #[cfg(feature = "solo")]
 pub fn gxi_set_trigger_selector_frame_start() -> Result<()> {
-    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_TRIGGER_SELECTOR, &0i64)?;
-    println!("Now, trigger selector is 0");
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_TRIGGER_SELECTOR, &1i64)?;
+    println!("Now, trigger selector is 1");
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_trigger_selector_frame_burst_start() -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_TRIGGER_SELECTOR, &2i64)?;
+    println!("Now, trigger selector is 2");
     Ok(())
 }
 
@@ -82,6 +269,13 @@ pub fn gxi_set_trigger_mode_off() -> Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "solo")]
+pub fn gxi_set_trigger_mode_on() -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_TRIGGER_MODE, &1i64)?;
+    println!("Now, trigger mode is 1");
+    Ok(())
+}
+
 #[cfg(feature = "solo")]
 pub fn gxi_get_trigger_source() -> Result<i64> {
     let trigger_source: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_TRIGGER_SOURCE)?;
@@ -103,6 +297,13 @@ pub fn gxi_set_trigger_source_software() -> Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "solo")]
+pub fn gxi_send_trigger_software() -> Result<()> {
+    gxi_send_command(GX_FEATURE_ID::GX_COMMAND_TRIGGER_SOFTWARE)?;
+    println!("Software trigger sent");
+    Ok(())
+}
+
 #[cfg(feature = "solo")]
 pub fn gxi_get_trigger_activation() -> Result<i64> {
     let trigger_activation: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_TRIGGER_ACTIVATION)?;
@@ -365,3 +566,575 @@ pub fn gxi_set_aaroi_offset_y(aaroi_offset_y: i64) -> Result<()> {
 //     println!("Now, expected gray value is {}",expected_gray_value);
 //     Ok(())
 // }
+
+//----------------------------------------------------------
+//---------------Trigger Sessions---------------------------
+//----------------------------------------------------------
+
+/// Switch the frame start trigger on, with the software trigger as its source.
+#[cfg(feature = "solo")]
+pub fn gxi_use_software_trigger() -> Result<()> {
+    gxi_set_trigger_selector_frame_start()?;
+    gxi_set_trigger_mode_on()?;
+    gxi_set_trigger_source_software()?;
+    *GXI_LAST_TRIGGERED_FRAME_ID.lock_safe(MutexType::Event)? = None;
+    Ok(())
+}
+
+// 上一次gxi_trigger_and_wait拿到的帧号，重新开始采集后帧号会从头算，所以在
+// gxi_use_software_trigger和gxi_send_command发AcquisitionStart时清掉
+pub(crate) static GXI_LAST_TRIGGERED_FRAME_ID: LazyLock<Arc<Mutex<Option<u64>>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(None)));
+
+/// Fire a software trigger and wait for the frame it produced.
+///
+/// The trigger must be set up with `gxi_use_software_trigger` and the acquisition started.
+/// Frames already queued are dropped first, and any frame whose ID is not newer than the
+/// last one seen before the trigger is dropped while waiting, so a late frame of an earlier
+/// trigger is not returned in place of this one.
+#[cfg(feature = "solo")]
+pub fn gxi_trigger_and_wait(timeout: u32) -> Result<GxiFrame> {
+    let mut last_frame_id = *GXI_LAST_TRIGGERED_FRAME_ID.lock_safe(MutexType::Event)?;
+    loop {
+        match gxi_get_frame(0) {
+            Ok(frame) => last_frame_id = last_frame_id.max(Some(frame.frame_id)),
+            Err(e) if is_timeout(&e) => break,
+            Err(e) => return Err(e),
+        }
+    }
+
+    gxi_send_trigger_software()?;
+    let deadline = Instant::now() + Duration::from_millis(timeout as u64);
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let frame = gxi_get_frame(remaining.as_millis() as u32)?;
+        if last_frame_id.is_some_and(|last_frame_id| frame.frame_id <= last_frame_id) {
+            println!(
+                "Dropped stale frame {} after software trigger",
+                frame.frame_id
+            );
+            if remaining.is_zero() {
+                return Err(Error::new(ErrorKind::GxStatusError(
+                    GX_STATUS_LIST::GX_STATUS_TIMEOUT as i32,
+                )));
+            }
+            continue;
+        }
+        *GXI_LAST_TRIGGERED_FRAME_ID.lock_safe(MutexType::Event)? = Some(frame.frame_id);
+        println!("Software trigger produced frame {}", frame.frame_id);
+        return Ok(frame);
+    }
+}
+
+/// Host side arrival times of the FRAMESTART_OVERTRIGGER events of the running session.
+pub static GXI_MISSED_TRIGGERS: LazyLock<Arc<Mutex<Vec<Instant>>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(Vec::new())));
+
+// 回调跑在SDK的事件线程里，这里只记录时间，不去拿GXI的锁读事件数据，
+// 否则注销回调时会和持有GXI锁的主线程互相等待
+extern "C" fn gxi_frame_start_overtrigger_callback(
+    _feature_id: GX_FEATURE_ID,
+    _user_param: *mut c_void,
+) {
+    match GXI_MISSED_TRIGGERS.lock_safe(MutexType::Event) {
+        Ok(mut missed_triggers) => missed_triggers.push(Instant::now()),
+        Err(e) => println!("Failed to record missed trigger: {}", e),
+    }
+}
+
+/// Summary of a `HardwareTriggerSession`.
+#[derive(Debug, Clone)]
+pub struct HardwareTriggerReport {
+    pub frames_received: u64,
+    /// `None` when the device does not implement the FRAMESTART_OVERTRIGGER event.
+    pub missed_triggers: Option<Vec<Instant>>,
+    /// Device frame ID and timestamp carried by the latest FRAMESTART_OVERTRIGGER event.
+    pub last_missed_frame_id: Option<i64>,
+    pub last_missed_timestamp: Option<i64>,
+}
+
+/// A frame start trigger driven by an external line, reporting the triggers the camera
+/// dropped because it was still busy with the previous frame.
+///
+/// Start it with `start()`, the acquisition itself is started and stopped by the session.
+#[derive(Debug)]
+pub struct HardwareTriggerSession {
+    frames_received: u64,
+    overtrigger_callback: Option<GxiFeatureCallback>,
+    stopped: bool,
+}
+
+#[cfg(feature = "solo")]
+impl HardwareTriggerSession {
+    pub fn start(
+        trigger_source: GX_TRIGGER_SOURCE_ENTRY,
+        trigger_activation: GX_TRIGGER_ACTIVATION_ENTRY,
+    ) -> Result<Self> {
+        gxi_set_trigger_selector_frame_start()?;
+        gxi_set_trigger_mode(GX_TRIGGER_MODE_ENTRY::GX_TRIGGER_MODE_ON as i64)?;
+        gxi_set_trigger_source(trigger_source as i64)?;
+        gxi_set_trigger_activation(trigger_activation as i64)?;
+
+        GXI_MISSED_TRIGGERS.lock_safe(MutexType::Event)?.clear();
+        let overtrigger_callback = if gxi_is_implemented(
+            GX_FEATURE_ID::GX_INT_EVENT_FRAMESTART_OVERTRIGGER,
+        )? {
+            gxi_enable_event(
+                GX_EVENT_SELECTOR_ENTRY::GX_ENUM_EVENT_SELECTOR_FRAMESTART_OVERTRIGGER,
+            )?;
+            Some(gxi_register_feature_callback(
+                GX_FEATURE_ID::GX_INT_EVENT_FRAMESTART_OVERTRIGGER,
+                gxi_frame_start_overtrigger_callback,
+            )?)
+        } else {
+            println!(
+                "FRAMESTART_OVERTRIGGER event is not implemented, missed triggers will not be reported"
+            );
+            None
+        };
+
+        gxi_acquisition_start()?;
+        Ok(Self {
+            frames_received: 0,
+            overtrigger_callback,
+            stopped: false,
+        })
+    }
+
+    /// Wait for the frame of the next hardware trigger.
+    pub fn next_frame(&mut self, timeout: u32) -> Result<GxiFrame> {
+        let frame = gxi_get_frame(timeout)?;
+        self.frames_received += 1;
+        Ok(frame)
+    }
+
+    /// Host side arrival times of the triggers missed so far, `None` if they are not reported.
+    pub fn missed_triggers(&self) -> Result<Option<Vec<Instant>>> {
+        if self.overtrigger_callback.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(
+            GXI_MISSED_TRIGGERS.lock_safe(MutexType::Event)?.clone(),
+        ))
+    }
+
+    pub fn report(&self) -> Result<HardwareTriggerReport> {
+        let missed_triggers = self.missed_triggers()?;
+        let has_missed = missed_triggers.as_ref().is_some_and(|m| !m.is_empty());
+        let (last_missed_frame_id, last_missed_timestamp) = if has_missed {
+            (
+                Some(gxi_get_int(
+                    GX_FEATURE_ID::GX_INT_EVENT_FRAMESTART_OVERTRIGGER_FRAMEID,
+                )?),
+                Some(gxi_get_int(
+                    GX_FEATURE_ID::GX_INT_EVENT_FRAMESTART_OVERTRIGGER_TIMESTAMP,
+                )?),
+            )
+        } else {
+            (None, None)
+        };
+
+        Ok(HardwareTriggerReport {
+            frames_received: self.frames_received,
+            missed_triggers,
+            last_missed_frame_id,
+            last_missed_timestamp,
+        })
+    }
+
+    /// Stop the acquisition, switch the event off and return the final report.
+    pub fn stop(mut self) -> Result<HardwareTriggerReport> {
+        let report = self.report()?;
+        self.shutdown()?;
+        Ok(report)
+    }
+
+    fn shutdown(&mut self) -> Result<()> {
+        if self.stopped {
+            return Ok(());
+        }
+        self.stopped = true;
+        gxi_acquisition_stop()?;
+        if let Some(overtrigger_callback) = self.overtrigger_callback.take() {
+            gxi_disable_event(
+                GX_EVENT_SELECTOR_ENTRY::GX_ENUM_EVENT_SELECTOR_FRAMESTART_OVERTRIGGER,
+            )?;
+            gxi_unregister_feature_callback(overtrigger_callback)?;
+        }
+        gxi_set_trigger_mode_off()
+    }
+}
+
+#[cfg(feature = "solo")]
+impl Drop for HardwareTriggerSession {
+    fn drop(&mut self) {
+        if let Err(e) = self.shutdown() {
+            println!("Failed to stop hardware trigger session: {}", e);
+        }
+    }
+}
+
+//----------------------------------------------------------
+//---------------Frame Sequences----------------------------
+//----------------------------------------------------------
+
+/// The frames collected by `gxi_capture_sequence` or `gxi_capture_burst`.
+#[derive(Debug, Clone)]
+pub struct FrameSequence {
+    pub requested: usize,
+    pub frames: Vec<GxiFrame>,
+    pub elapsed: Duration,
+}
+
+impl FrameSequence {
+    /// How many of the requested frames did not arrive before the timeout.
+    pub fn shortfall(&self) -> usize {
+        self.requested.saturating_sub(self.frames.len())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.shortfall() == 0 && self.incomplete_frames() == 0
+    }
+
+    /// Frames that arrived, but with a non-success status (e.g. lost packets).
+    pub fn incomplete_frames(&self) -> usize {
+        self.frames.iter().filter(|frame| frame.status != 0).count()
+    }
+
+    pub fn timestamps(&self) -> Vec<u64> {
+        self.frames.iter().map(|frame| frame.timestamp).collect()
+    }
+
+    /// Frame IDs skipped by the device between the first and the last frame received.
+    pub fn missing_frame_ids(&self) -> Vec<u64> {
+        self.frames
+            .windows(2)
+            .flat_map(|pair| (pair[0].frame_id + 1)..pair[1].frame_id)
+            .collect()
+    }
+}
+
+// 超时说明设备没有再送帧上来，这算作缺帧，不当作错误
+fn is_timeout(error: &Error) -> bool {
+    matches!(
+        *error.inner,
+        ErrorKind::GxStatusError(status) if status == GX_STATUS_LIST::GX_STATUS_TIMEOUT as i32
+    )
+}
+
+// 采集前改动的设置，记下旧值后按相反的顺序写回去。中途出错用?返回时drop也会写回，
+// 所以选择器要在切换之前记下，它后面记下的值都是在切换后的选择器下读的
+#[cfg(feature = "solo")]
+#[derive(Default)]
+struct SavedSettings {
+    saved: Vec<(GX_FEATURE_ID, i64)>,
+}
+
+#[cfg(feature = "solo")]
+impl SavedSettings {
+    fn save(&mut self, feature_id: GX_FEATURE_ID) -> Result<i64> {
+        let value: i64 = gxi_get_feature_value(feature_id)?;
+        self.saved.push((feature_id, value));
+        Ok(value)
+    }
+
+    /// Write the saved values back, all of them even when one fails, the first error is
+    /// returned.
+    fn restore(&mut self) -> Result<()> {
+        let mut result = Ok(());
+        while let Some((feature_id, value)) = self.saved.pop() {
+            if let Err(e) = gxi_set_feature_value(feature_id, &value) {
+                println!("Failed to restore {:?}: {}", feature_id, e);
+                result = result.and(Err(e));
+            }
+        }
+        result
+    }
+}
+
+#[cfg(feature = "solo")]
+impl Drop for SavedSettings {
+    fn drop(&mut self) {
+        let _ = self.restore();
+    }
+}
+
+#[cfg(feature = "solo")]
+fn gxi_collect_frames(frames: &mut Vec<GxiFrame>, count: usize, timeout: u32) -> Result<bool> {
+    for _ in 0..count {
+        match gxi_get_frame(timeout) {
+            Ok(frame) => frames.push(frame),
+            Err(e) if is_timeout(&e) => return Ok(false),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(true)
+}
+
+/// Acquire exactly `frame_count` frames in SingleFrame or MultiFrame mode.
+///
+/// Each frame waits at most `timeout` ms; frames that never arrive are reported by
+/// `FrameSequence::shortfall` instead of an error. The frame start trigger must be off, it
+/// fails with `InvalidParameter` otherwise. The trigger selector, acquisition mode and frame
+/// count are restored afterwards, also when it fails.
+#[cfg(feature = "solo")]
+pub fn gxi_capture_sequence(frame_count: usize, timeout: u32) -> Result<FrameSequence> {
+    if frame_count == 0 {
+        return Err(Error::new(ErrorKind::InvalidParameter(
+            "frame count must be at least 1".to_string(),
+        )));
+    }
+
+    let mut settings = SavedSettings::default();
+    settings.save(GX_FEATURE_ID::GX_ENUM_TRIGGER_SELECTOR)?;
+    gxi_set_trigger_selector_frame_start()?;
+    if gxi_get_trigger_mode()? != GX_TRIGGER_MODE_ENTRY::GX_TRIGGER_MODE_OFF as i64 {
+        return Err(Error::new(ErrorKind::InvalidParameter(
+            "the frame start trigger is on, switch it off to capture a sequence".to_string(),
+        )));
+    }
+    settings.save(GX_FEATURE_ID::GX_ENUM_ACQUISITION_MODE)?;
+    if frame_count == 1 {
+        gxi_set_acquisition_mode_single_frame()?;
+    } else {
+        settings.save(GX_FEATURE_ID::GX_INT_ACQUISITION_FRAME_COUNT)?;
+        gxi_set_acquisition_mode_multi_frame()?;
+        gxi_set_acquisition_frame_count(frame_count as i64)?;
+    }
+
+    let started_at = Instant::now();
+    let mut frames = Vec::with_capacity(frame_count);
+    gxi_acquisition_start()?;
+    let collected = gxi_collect_frames(&mut frames, frame_count, timeout);
+    let stopped = gxi_acquisition_stop();
+    let restored = settings.restore();
+    collected?;
+    stopped?;
+    restored?;
+
+    let sequence = FrameSequence {
+        requested: frame_count,
+        frames,
+        elapsed: started_at.elapsed(),
+    };
+    println!(
+        "Captured {} of {} frames",
+        sequence.frames.len(),
+        sequence.requested
+    );
+    Ok(sequence)
+}
+
+/// Acquire `frame_count` frames in bursts of `frames_per_trigger`, one software trigger each.
+///
+/// The burst length goes to `GX_INT_ACQUISITION_BURST_FRAME_COUNT` under the frame burst start
+/// trigger; the last burst is cut short so exactly `frame_count` frames are requested. A burst
+/// that does not complete in time ends the capture and is reported by `shortfall`. The
+/// acquisition mode, trigger selector, the burst trigger's mode and source and the burst
+/// length are put back afterwards, also when it fails.
+#[cfg(feature = "solo")]
+pub fn gxi_capture_burst(
+    frame_count: usize,
+    frames_per_trigger: usize,
+    timeout: u32,
+) -> Result<FrameSequence> {
+    if frame_count == 0 || frames_per_trigger == 0 {
+        return Err(Error::new(ErrorKind::InvalidParameter(
+            "frame count and frames per trigger must be at least 1".to_string(),
+        )));
+    }
+
+    // 触发模式和触发源是按selector分别保存的，先切到FrameBurstStart再记下它原来的值，
+    // 调用方在FrameStart上配好的硬件触发不受影响
+    let mut settings = SavedSettings::default();
+    settings.save(GX_FEATURE_ID::GX_ENUM_ACQUISITION_MODE)?;
+    settings.save(GX_FEATURE_ID::GX_ENUM_TRIGGER_SELECTOR)?;
+    gxi_set_trigger_selector(
+        GX_TRIGGER_SELECTOR_ENTRY::GX_ENUM_TRIGGER_SELECTOR_FRAME_BURST_START as i64,
+    )?;
+    settings.save(GX_FEATURE_ID::GX_ENUM_TRIGGER_MODE)?;
+    settings.save(GX_FEATURE_ID::GX_ENUM_TRIGGER_SOURCE)?;
+    settings.save(GX_FEATURE_ID::GX_INT_ACQUISITION_BURST_FRAME_COUNT)?;
+
+    gxi_set_acquisition_mode_continuous()?;
+    gxi_set_trigger_mode_on()?;
+    gxi_set_trigger_source_software()?;
+    gxi_set_acquisition_burst_frame_count(frames_per_trigger as i64)?;
+
+    let started_at = Instant::now();
+    let mut frames = Vec::with_capacity(frame_count);
+    gxi_acquisition_start()?;
+    let collected = (|| -> Result<()> {
+        while frames.len() < frame_count {
+            let burst = frames_per_trigger.min(frame_count - frames.len());
+            if burst != frames_per_trigger {
+                gxi_set_acquisition_burst_frame_count(burst as i64)?;
+            }
+            gxi_send_trigger_software()?;
+            if !gxi_collect_frames(&mut frames, burst, timeout)? {
+                break;
+            }
+        }
+        Ok(())
+    })();
+    let stopped = gxi_acquisition_stop();
+    let restored = settings.restore();
+    collected?;
+    stopped?;
+    restored?;
+
+    let sequence = FrameSequence {
+        requested: frame_count,
+        frames,
+        elapsed: started_at.elapsed(),
+    };
+    println!(
+        "Captured {} of {} frames in bursts of {}",
+        sequence.frames.len(),
+        sequence.requested,
+        frames_per_trigger
+    );
+    Ok(sequence)
+}
+
+//----------------------------------------------------------
+//---------------Frame Rate Estimation----------------------
+//----------------------------------------------------------
+
+/// A frame layout to estimate the frame rate for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameFormat {
+    pub width: i64,
+    pub height: i64,
+    pub pixel_format: i64,
+}
+
+impl FrameFormat {
+    pub fn frame_bytes(&self) -> Result<i64> {
+        let pixel_format = PixelFormatEntry::try_from(self.pixel_format)?;
+        Ok(pixel_format.image_size(self.width.max(0) as usize, self.height.max(0) as usize) as i64)
+    }
+}
+
+/// The device state the frame rate estimate is based on, read once by `gxi_get_frame_rate_model`.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameRateModel {
+    pub format: FrameFormat,
+    /// Highest frame rate the device takes for `format`, the maximum of the acquisition frame
+    /// rate range, so it is not capped by the frame rate target.
+    pub frame_rate_max: f64,
+    /// Exposure time in us.
+    pub exposure_time: f64,
+    /// Link throughput limit in bytes per second, `None` when the limit is not active.
+    pub link_throughput_limit: Option<i64>,
+}
+
+/// Which limit decides the estimated maximum frame rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameRateLimit {
+    Readout,
+    Exposure,
+    Bandwidth,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FrameRateEstimate {
+    pub max_frame_rate: f64,
+    pub limited_by: FrameRateLimit,
+    pub readout_frame_rate: f64,
+    pub exposure_frame_rate: f64,
+    pub bandwidth_frame_rate: Option<f64>,
+}
+
+impl FrameRateModel {
+    /// Estimate the maximum frame rate for `format`, without touching the device.
+    ///
+    /// The sensor reads out line by line, so the readout limit scales with the height only;
+    /// the bandwidth limit scales with the frame size in bytes; the exposure bounds the rate
+    /// at `1e6 / exposure_time`.
+    pub fn estimate(&self, format: &FrameFormat) -> Result<FrameRateEstimate> {
+        let frame_bytes = format.frame_bytes()?;
+        if format.width <= 0 || format.height <= 0 || frame_bytes <= 0 {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "invalid frame format {:?}",
+                format
+            ))));
+        }
+
+        let readout_frame_rate =
+            self.frame_rate_max * self.format.height as f64 / format.height as f64;
+        let exposure_frame_rate = if self.exposure_time > 0.0 {
+            1e6 / self.exposure_time
+        } else {
+            f64::INFINITY
+        };
+        let bandwidth_frame_rate = self
+            .link_throughput_limit
+            .map(|throughput| throughput as f64 / frame_bytes as f64);
+
+        let mut estimate = FrameRateEstimate {
+            max_frame_rate: readout_frame_rate,
+            limited_by: FrameRateLimit::Readout,
+            readout_frame_rate,
+            exposure_frame_rate,
+            bandwidth_frame_rate,
+        };
+        if exposure_frame_rate < estimate.max_frame_rate {
+            estimate.max_frame_rate = exposure_frame_rate;
+            estimate.limited_by = FrameRateLimit::Exposure;
+        }
+        if let Some(bandwidth_frame_rate) = bandwidth_frame_rate {
+            if bandwidth_frame_rate < estimate.max_frame_rate {
+                estimate.max_frame_rate = bandwidth_frame_rate;
+                estimate.limited_by = FrameRateLimit::Bandwidth;
+            }
+        }
+        Ok(estimate)
+    }
+}
+
+/// Read the current ROI, pixel format, frame rate range, exposure and link limit, without
+/// writing any of them.
+#[cfg(feature = "solo")]
+pub fn gxi_get_frame_rate_model() -> Result<FrameRateModel> {
+    let format = FrameFormat {
+        width: gxi_get_width()?,
+        height: gxi_get_height()?,
+        pixel_format: gxi_get_pixel_format()?,
+    };
+
+    // 开着帧率控制时当前帧率被目标值封顶，目标帧率范围的最大值才是不封顶的帧率；
+    // 这里只读不写，采集中调用也不会改变帧率
+    let frame_rate_max = gxi_get_float_range(GX_FEATURE_ID::GX_FLOAT_ACQUISITION_FRAME_RATE)?.dMax;
+    let exposure_time = gxi_get_float(GX_FEATURE_ID::GX_FLOAT_EXPOSURE_TIME)?;
+
+    let link_throughput_limit =
+        if gxi_is_implemented(GX_FEATURE_ID::GX_ENUM_DEVICE_LINK_THROUGHPUT_LIMIT_MODE)?
+            && gxi_get_enum(GX_FEATURE_ID::GX_ENUM_DEVICE_LINK_THROUGHPUT_LIMIT_MODE)? == 1
+        {
+            Some(gxi_get_int(
+                GX_FEATURE_ID::GX_INT_DEVICE_LINK_THROUGHPUT_LIMIT,
+            )?)
+        } else {
+            None
+        };
+
+    Ok(FrameRateModel {
+        format,
+        frame_rate_max,
+        exposure_time,
+        link_throughput_limit,
+    })
+}
+
+/// Estimate the maximum frame rate of a proposed ROI and pixel format from the current device
+/// state. The device keeps its settings, use it before committing to a new ROI.
+#[cfg(feature = "solo")]
+pub fn gxi_estimate_max_frame_rate(format: &FrameFormat) -> Result<FrameRateEstimate> {
+    let estimate = gxi_get_frame_rate_model()?.estimate(format)?;
+    println!(
+        "Estimated max frame rate is {} ({:?} limited)",
+        estimate.max_frame_rate, estimate.limited_by
+    );
+    Ok(estimate)
+}
@@ -1,8 +1,9 @@
-//! Placeholder
+//! Chunk data, the per frame metadata (frame ID, timestamp ...) the camera appends to the image payload.
 
-use crate::error::Result;
+use crate::error::{Error, ErrorKind, Result};
 use crate::hal::config::*;
-use crate::raw::gx_enum::GX_FEATURE_ID;
+use crate::hal::device::GxiFrame;
+use crate::raw::gx_enum::{GX_CHUNK_SELECTOR_ENTRY, GX_FEATURE_ID};
 
 #[cfg(feature = "solo")]
 pub fn gxi_get_chunk_mode_active() -> Result<bool> {
@@ -34,18 +35,25 @@ pub fn gxi_set_chunk_selector(chunk_selector: i64) -> Result<()> {
 
 #[cfg(feature = "solo")]
 pub fn gxi_set_chunk_selector_frame_id() -> Result<()> {
-    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_CHUNK_SELECTOR, &0i64)?;
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_CHUNK_SELECTOR, &1i64)?;
     println!("Now, chunk selector is frame id");
     Ok(())
 }
 
 #[cfg(feature = "solo")]
 pub fn gxi_set_chunk_selector_timestamp() -> Result<()> {
-    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_CHUNK_SELECTOR, &1i64)?;
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_CHUNK_SELECTOR, &2i64)?;
     println!("Now, chunk selector is timestamp");
     Ok(())
 }
 
+#[cfg(feature = "solo")]
+pub fn gxi_set_chunk_selector_counter_value() -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_CHUNK_SELECTOR, &3i64)?;
+    println!("Now, chunk selector is counter value");
+    Ok(())
+}
+
 #[cfg(feature = "solo")]
 pub fn gxi_get_chunk_enable() -> Result<bool> {
     let chunk_enable: bool = gxi_get_feature_value(GX_FEATURE_ID::GX_BOOL_CHUNK_ENABLE)?;
@@ -60,10 +68,305 @@ pub fn gxi_set_chunk_enable(chunk_enable: bool) -> Result<()> {
     Ok(())
 }
 
-// I cant find the feature id for chunk timestamp
-// #[cfg(feature = "solo")]
-// pub fn gxi_get_chunk_timestamp() -> Result<i64> {
-//     let chunk_timestamp:i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_INT_CHUNK_TIMESTAMP)?;
-//     println!("Now, chunk timestamp is {}",chunk_timestamp);
-//     Ok(chunk_timestamp)
-// }
+/// Switch chunk mode on and enable exactly the `chunks` given, the other selectors are
+/// disabled. Set it before starting acquisition, the payload size changes with it.
+#[cfg(feature = "solo")]
+pub fn gxi_enable_chunks(chunks: &[GX_CHUNK_SELECTOR_ENTRY]) -> Result<()> {
+    gxi_set_chunk_mode_active(true)?;
+    let available = gxi_get_enum_description(GX_FEATURE_ID::GX_ENUM_CHUNK_SELECTOR)?;
+    for chunk in [
+        GX_CHUNK_SELECTOR_ENTRY::GX_CHUNK_SELECTOR_CHUNK_FRAME_ID,
+        GX_CHUNK_SELECTOR_ENTRY::GX_CHUNK_SELECTOR_CHUNK_TIME_STAMP,
+        GX_CHUNK_SELECTOR_ENTRY::GX_CHUNK_SELECTOR_CHUNK_COUNTER_VALUE,
+    ] {
+        let enable = chunks.contains(&chunk);
+        // 设备不一定有全部的选择器，没有的只在要打开时才算错误
+        if !enable && !available.iter().any(|entry| entry.n_value == chunk as i64) {
+            continue;
+        }
+        gxi_set_chunk_selector(chunk as i64)?;
+        gxi_set_chunk_enable(enable)?;
+    }
+    println!("Successfully enabled chunks {:?}", chunks);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_disable_chunks() -> Result<()> {
+    gxi_set_chunk_mode_active(false)
+}
+
+//----------------------------------------------------------
+//---------------Chunk Parsing------------------------------
+//----------------------------------------------------------
+
+// 每块数据后面跟着8字节的尾部：4字节块ID + 4字节块长度。
+// GigE Vision用大端，USB3 Vision用小端，解析时两种都试，能刚好走回图像数据末尾的那种就是对的
+const CHUNK_TRAILER_SIZE: usize = 8;
+
+/// IDs the camera tags each chunk with. They come from the device description file and
+/// differ from the chunk selector values, so there is no default; a `None` ID reads as `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkIds {
+    pub frame_id: Option<u32>,
+    pub timestamp: Option<u32>,
+    pub counter_value: Option<u32>,
+    pub exposure_time: Option<u32>,
+    pub gain: Option<u32>,
+    pub line_status: Option<u32>,
+}
+
+/// One chunk cut out of the payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameChunk {
+    pub id: u32,
+    pub data: Vec<u8>,
+}
+
+/// The chunks of one frame in payload order, with typed access through `ChunkIds`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameChunks {
+    pub chunks: Vec<FrameChunk>,
+    pub big_endian: bool,
+    pub ids: ChunkIds,
+}
+
+impl FrameChunks {
+    /// Split the chunks off the payload tail. `image_size` is the image part in bytes, which
+    /// may itself be wrapped as the first chunk. A payload that parses in both byte orders
+    /// with different chunks is rejected, there is no telling which one the camera sent.
+    pub fn parse(payload: &[u8], image_size: usize, ids: ChunkIds) -> Result<Self> {
+        let little = parse_chunks(payload, image_size, false);
+        let big = parse_chunks(payload, image_size, true);
+        let (chunks, big_endian) = match (little, big) {
+            (Some(little), Some(big)) if little == big => (little, false),
+            (Some(_), Some(_)) => {
+                return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                    "chunk layout of a {} byte payload parses in both byte orders",
+                    payload.len()
+                ))));
+            }
+            (Some(little), None) => (little, false),
+            (None, Some(big)) => (big, true),
+            (None, None) => {
+                return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                    "no chunk layout matches a {} byte payload with a {} byte image",
+                    payload.len(),
+                    image_size
+                ))));
+            }
+        };
+        Ok(FrameChunks {
+            chunks,
+            big_endian,
+            ids,
+        })
+    }
+
+    pub fn get(&self, id: u32) -> Option<&FrameChunk> {
+        self.chunks.iter().find(|chunk| chunk.id == id)
+    }
+
+    fn read_u64(&self, id: Option<u32>) -> Option<u64> {
+        let data = &self.get(id?)?.data;
+        match data.len() {
+            8 => {
+                let bytes: [u8; 8] = data[..8].try_into().ok()?;
+                Some(if self.big_endian {
+                    u64::from_be_bytes(bytes)
+                } else {
+                    u64::from_le_bytes(bytes)
+                })
+            }
+            4 => {
+                let bytes: [u8; 4] = data[..4].try_into().ok()?;
+                Some(if self.big_endian {
+                    u32::from_be_bytes(bytes)
+                } else {
+                    u32::from_le_bytes(bytes)
+                } as u64)
+            }
+            _ => None,
+        }
+    }
+
+    fn read_f64(&self, id: Option<u32>) -> Option<f64> {
+        let data = &self.get(id?)?.data;
+        let bits = self.read_u64(id)?;
+        match data.len() {
+            8 => Some(f64::from_bits(bits)),
+            4 => Some(f32::from_bits(bits as u32) as f64),
+            _ => None,
+        }
+    }
+
+    pub fn frame_id(&self) -> Option<u64> {
+        self.read_u64(self.ids.frame_id)
+    }
+
+    /// Timestamp in device ticks.
+    pub fn timestamp(&self) -> Option<u64> {
+        self.read_u64(self.ids.timestamp)
+    }
+
+    pub fn counter_value(&self) -> Option<u64> {
+        self.read_u64(self.ids.counter_value)
+    }
+
+    /// Exposure time in us.
+    pub fn exposure_time(&self) -> Option<f64> {
+        self.read_f64(self.ids.exposure_time)
+    }
+
+    /// Gain in dB.
+    pub fn gain(&self) -> Option<f64> {
+        self.read_f64(self.ids.gain)
+    }
+
+    /// Line levels, bit n is Line n.
+    pub fn line_status(&self) -> Option<u64> {
+        self.read_u64(self.ids.line_status)
+    }
+}
+
+fn parse_chunks(payload: &[u8], image_size: usize, big_endian: bool) -> Option<Vec<FrameChunk>> {
+    let read_u32 = |bytes: &[u8]| {
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        }
+    };
+
+    let mut chunks = Vec::new();
+    let mut end = payload.len();
+    while end > image_size {
+        let trailer_start = end.checked_sub(CHUNK_TRAILER_SIZE)?;
+        let id = read_u32(&payload[trailer_start..trailer_start + 4]);
+        let length = read_u32(&payload[trailer_start + 4..end]) as usize;
+        let start = trailer_start.checked_sub(length)?;
+        chunks.push(FrameChunk {
+            id,
+            data: payload[start..trailer_start].to_vec(),
+        });
+        end = start;
+    }
+
+    if end == 0 && image_size > 0 {
+        // 图像本身也被包成了第一个块，它不算元数据
+        let image = chunks.pop()?;
+        if image.data.len() != image_size {
+            return None;
+        }
+    } else if end != image_size {
+        return None;
+    }
+    chunks.reverse();
+    Some(chunks)
+}
+
+impl GxiFrame {
+    /// Size of the image part of the payload, from the frame size and pixel format.
+    pub fn image_size(&self) -> Result<usize> {
+        Ok(self
+            .pixel_format_entry()?
+            .image_size(self.width.max(0) as usize, self.height.max(0) as usize))
+    }
+
+    /// The chunk data the camera appended to this frame, see `gxi_enable_chunks`.
+    pub fn chunks(&self, ids: ChunkIds) -> Result<FrameChunks> {
+        FrameChunks::parse(&self.data, self.image_size()?, ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME_ID_CHUNK: u32 = 0x0A00_0001;
+    const IMAGE_CHUNK: u32 = 0x0A00_0000;
+
+    fn ids() -> ChunkIds {
+        ChunkIds {
+            frame_id: Some(FRAME_ID_CHUNK),
+            timestamp: None,
+            counter_value: None,
+            exposure_time: None,
+            gain: None,
+            line_status: None,
+        }
+    }
+
+    fn chunk(id: u32, data: &[u8], big_endian: bool) -> Vec<u8> {
+        let mut bytes = data.to_vec();
+        let (id, length) = if big_endian {
+            (id.to_be_bytes(), (data.len() as u32).to_be_bytes())
+        } else {
+            (id.to_le_bytes(), (data.len() as u32).to_le_bytes())
+        };
+        bytes.extend_from_slice(&id);
+        bytes.extend_from_slice(&length);
+        bytes
+    }
+
+    fn payload(big_endian: bool) -> Vec<u8> {
+        let frame_id = if big_endian {
+            42u64.to_be_bytes()
+        } else {
+            42u64.to_le_bytes()
+        };
+        let mut payload = vec![0xAA; 16];
+        payload.extend(chunk(FRAME_ID_CHUNK, &frame_id, big_endian));
+        payload
+    }
+
+    #[test]
+    fn parses_little_endian_trailer() {
+        let chunks = FrameChunks::parse(&payload(false), 16, ids()).unwrap();
+        assert!(!chunks.big_endian);
+        assert_eq!(chunks.chunks.len(), 1);
+        assert_eq!(chunks.frame_id(), Some(42));
+    }
+
+    #[test]
+    fn parses_big_endian_trailer() {
+        let chunks = FrameChunks::parse(&payload(true), 16, ids()).unwrap();
+        assert!(chunks.big_endian);
+        assert_eq!(chunks.frame_id(), Some(42));
+    }
+
+    #[test]
+    fn drops_image_wrapped_as_chunk() {
+        let mut payload = chunk(IMAGE_CHUNK, &[0xAA; 16], false);
+        payload.extend(chunk(FRAME_ID_CHUNK, &7u64.to_le_bytes(), false));
+        let chunks = FrameChunks::parse(&payload, 16, ids()).unwrap();
+        assert_eq!(chunks.chunks.len(), 1);
+        assert_eq!(chunks.chunks[0].id, FRAME_ID_CHUNK);
+        assert_eq!(chunks.frame_id(), Some(7));
+    }
+
+    #[test]
+    fn rejects_truncated_payload() {
+        let payload = payload(false);
+        assert!(FrameChunks::parse(&payload[..payload.len() - 4], 16, ids()).is_err());
+    }
+
+    #[test]
+    fn rejects_layout_valid_in_both_byte_orders() {
+        // 长度为0时两种字节序都能走回图像末尾，但块ID不同
+        let mut payload = vec![0xAA; 16];
+        payload.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(FrameChunks::parse(&payload, 16, ids()).is_err());
+    }
+
+    #[test]
+    fn without_ids_values_are_none() {
+        let no_ids = ChunkIds {
+            frame_id: None,
+            ..ids()
+        };
+        let chunks = FrameChunks::parse(&payload(false), 16, no_ids).unwrap();
+        assert_eq!(chunks.frame_id(), None);
+    }
+}
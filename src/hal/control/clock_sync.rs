@@ -0,0 +1,291 @@
+//! Correlation of the device timestamp clock with the host wall clock, to put frame timestamps of several cameras on one time line.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::hal::control::device::{
+    gxi_get_timestamp_latch_value, gxi_get_timestamp_tick_frequency, gxi_latch_timestamp,
+};
+use crate::hal::device::GxiFrame;
+
+use std::collections::VecDeque;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// One device time latched against the host clock.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClockSample {
+    pub device_ticks: u64,
+    /// Host time halfway through the latch round trip.
+    pub host_time: SystemTime,
+    pub round_trip: Duration,
+}
+
+/// Latch the device clock once. The device time is taken to be latched halfway between
+/// sending the command and it returning.
+#[cfg(feature = "solo")]
+pub fn gxi_sample_device_clock() -> Result<ClockSample> {
+    let before = SystemTime::now();
+    gxi_latch_timestamp()?;
+    let after = SystemTime::now();
+    let device_ticks = gxi_get_timestamp_latch_value()? as u64;
+
+    let round_trip = after.duration_since(before).unwrap_or_default();
+    Ok(ClockSample {
+        device_ticks,
+        host_time: before + round_trip / 2,
+        round_trip,
+    })
+}
+
+/// `host = reference_host + offset + (1 + drift) * (ticks - reference_ticks) / tick_frequency`,
+/// fitted by least squares over the clock samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClockModel {
+    pub tick_frequency: f64,
+    pub reference_ticks: u64,
+    pub reference_host: SystemTime,
+    /// Seconds.
+    pub offset: f64,
+    /// Host seconds gained per device second, 1e-6 is 1 ppm.
+    pub drift: f64,
+    /// Root mean square of the fit residuals in seconds.
+    pub residual: f64,
+}
+
+impl ClockModel {
+    /// Fit the model, one sample gives an offset only and no drift.
+    pub fn fit(samples: &[ClockSample], tick_frequency: f64) -> Result<Self> {
+        let reference = samples.first().ok_or_else(|| {
+            Error::new(ErrorKind::InvalidParameter(
+                "a clock model needs at least one sample".to_string(),
+            ))
+        })?;
+        if tick_frequency <= 0.0 {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "tick frequency must be positive, got {}",
+                tick_frequency
+            ))));
+        }
+
+        // 以第一个样本为原点，避免纪元秒数和tick数太大丢精度
+        let points = samples
+            .iter()
+            .map(|sample| {
+                let device =
+                    (sample.device_ticks as f64 - reference.device_ticks as f64) / tick_frequency;
+                (
+                    device,
+                    seconds_between(reference.host_time, sample.host_time),
+                )
+            })
+            .collect::<Vec<(f64, f64)>>();
+
+        let n = points.len() as f64;
+        let mean_device = points.iter().map(|(d, _)| d).sum::<f64>() / n;
+        let mean_host = points.iter().map(|(_, h)| h).sum::<f64>() / n;
+        let spread = points
+            .iter()
+            .map(|(d, _)| (d - mean_device).powi(2))
+            .sum::<f64>();
+        let slope = if spread > 0.0 {
+            points
+                .iter()
+                .map(|(d, h)| (d - mean_device) * (h - mean_host))
+                .sum::<f64>()
+                / spread
+        } else {
+            1.0
+        };
+        let offset = mean_host - slope * mean_device;
+        let residual = (points
+            .iter()
+            .map(|(d, h)| (h - offset - slope * d).powi(2))
+            .sum::<f64>()
+            / n)
+            .sqrt();
+
+        Ok(ClockModel {
+            tick_frequency,
+            reference_ticks: reference.device_ticks,
+            reference_host: reference.host_time,
+            offset,
+            drift: slope - 1.0,
+            residual,
+        })
+    }
+
+    pub fn drift_ppm(&self) -> f64 {
+        self.drift * 1e6
+    }
+
+    /// Host wall clock time of a device timestamp.
+    pub fn to_host_time(&self, device_ticks: u64) -> SystemTime {
+        let device = (device_ticks as f64 - self.reference_ticks as f64) / self.tick_frequency;
+        add_seconds(
+            self.reference_host,
+            self.offset + (1.0 + self.drift) * device,
+        )
+    }
+
+    /// Device timestamp of a host wall clock time.
+    pub fn to_device_ticks(&self, host_time: SystemTime) -> u64 {
+        let host = seconds_between(self.reference_host, host_time);
+        let device = (host - self.offset) / (1.0 + self.drift);
+        (self.reference_ticks as f64 + device * self.tick_frequency).max(0.0) as u64
+    }
+
+    /// Host wall clock time the frame was stamped at.
+    pub fn frame_time(&self, frame: &GxiFrame) -> SystemTime {
+        self.to_host_time(frame.timestamp)
+    }
+}
+
+fn seconds_between(from: SystemTime, to: SystemTime) -> f64 {
+    match to.duration_since(from) {
+        Ok(duration) => duration.as_secs_f64(),
+        Err(e) => -e.duration().as_secs_f64(),
+    }
+}
+
+fn add_seconds(time: SystemTime, seconds: f64) -> SystemTime {
+    if seconds >= 0.0 {
+        time + Duration::from_secs_f64(seconds)
+    } else {
+        time - Duration::from_secs_f64(-seconds)
+    }
+}
+
+/// Keeps the latest `window` clock samples and refits the model on every new one, so the
+/// drift estimate follows temperature changes of the device oscillator.
+#[derive(Debug, Clone)]
+pub struct ClockSync {
+    tick_frequency: f64,
+    window: usize,
+    samples: VecDeque<ClockSample>,
+}
+
+impl ClockSync {
+    pub fn new(tick_frequency: f64, window: usize) -> Self {
+        ClockSync {
+            tick_frequency,
+            window: window.max(1),
+            samples: VecDeque::with_capacity(window.max(1)),
+        }
+    }
+
+    pub fn samples(&self) -> &VecDeque<ClockSample> {
+        &self.samples
+    }
+
+    pub fn add_sample(&mut self, sample: ClockSample) -> Result<ClockModel> {
+        if self.samples.len() == self.window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+        self.model()
+    }
+
+    /// Fit over the samples whose latch round trip is within twice the fastest one, the
+    /// slow round trips are the ones the host was interrupted in.
+    pub fn model(&self) -> Result<ClockModel> {
+        let fastest = self
+            .samples
+            .iter()
+            .map(|sample| sample.round_trip)
+            .min()
+            .unwrap_or_default();
+        let samples = self
+            .samples
+            .iter()
+            .filter(|sample| sample.round_trip <= fastest * 2)
+            .copied()
+            .collect::<Vec<ClockSample>>();
+        ClockModel::fit(&samples, self.tick_frequency)
+    }
+
+    /// Latch the device clock once more and refit.
+    #[cfg(feature = "solo")]
+    pub fn sample(&mut self) -> Result<ClockModel> {
+        let sample = gxi_sample_device_clock()?;
+        self.add_sample(sample)
+    }
+}
+
+/// Latch the device clock `count` times, `interval` apart, and fit the model. A longer span
+/// between the first and last latch gives a better drift estimate.
+#[cfg(feature = "solo")]
+pub fn gxi_sync_device_clock(count: usize, interval: Duration) -> Result<ClockSync> {
+    let tick_frequency = gxi_get_timestamp_tick_frequency()? as f64;
+    let mut clock_sync = ClockSync::new(tick_frequency, count);
+    for index in 0..count.max(1) {
+        if index > 0 {
+            thread::sleep(interval);
+        }
+        clock_sync.sample()?;
+    }
+    let model = clock_sync.model()?;
+    println!(
+        "Successfully synced device clock, offset {} s, drift {} ppm, residual {} s",
+        model.offset,
+        model.drift_ppm(),
+        model.residual
+    );
+    Ok(clock_sync)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TICK_FREQUENCY: f64 = 1e9;
+
+    fn sample(device_ticks: u64, host_offset: Duration) -> ClockSample {
+        ClockSample {
+            device_ticks,
+            host_time: SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000) + host_offset,
+            round_trip: Duration::from_micros(100),
+        }
+    }
+
+    #[test]
+    fn fits_drift_of_a_linear_clock() {
+        // 设备时钟比主机慢100ppm
+        let samples = (0..10u64)
+            .map(|i| {
+                let device_seconds = i as f64;
+                sample(
+                    5_000 + (device_seconds * TICK_FREQUENCY) as u64,
+                    Duration::from_secs_f64(device_seconds * 1.0001),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let model = ClockModel::fit(&samples, TICK_FREQUENCY).unwrap();
+        assert!(
+            (model.drift_ppm() - 100.0).abs() < 0.1,
+            "{}",
+            model.drift_ppm()
+        );
+        assert!(model.offset.abs() < 1e-6);
+        assert!(model.residual < 1e-6);
+
+        let ticks = 5_000 + 4 * TICK_FREQUENCY as u64;
+        let host = model.to_host_time(ticks);
+        let error = seconds_between(samples[4].host_time, host).abs();
+        assert!(error < 1e-6, "{}", error);
+        assert!(model.to_device_ticks(host).abs_diff(ticks) < 1_000);
+    }
+
+    #[test]
+    fn single_sample_gives_offset_only() {
+        let model = ClockModel::fit(&[sample(42, Duration::ZERO)], TICK_FREQUENCY).unwrap();
+        assert_eq!(model.drift, 0.0);
+        assert_eq!(model.offset, 0.0);
+        assert_eq!(model.to_host_time(42), model.reference_host);
+    }
+
+    #[test]
+    fn rejects_no_samples_and_bad_frequency() {
+        assert!(ClockModel::fit(&[], TICK_FREQUENCY).is_err());
+        assert!(ClockModel::fit(&[sample(42, Duration::ZERO)], 0.0).is_err());
+    }
+}
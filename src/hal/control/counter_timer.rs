@@ -0,0 +1,554 @@
+//! Counter and timer features, e.g. a timer started on exposure start to drive a strobe light.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::hal::config::*;
+use crate::hal::device::gxi_send_command;
+use crate::raw::gx_enum::{
+    GX_COUNTER_EVENT_SOURCE_ENTRY, GX_COUNTER_RESET_ACTIVATION_ENTRY,
+    GX_COUNTER_RESET_SOURCE_ENTRY, GX_COUNTER_SELECTOR_ENTRY, GX_COUNTER_TRIGGER_SOURCE_ENTRY,
+    GX_FEATURE_ID, GX_TIMER_SELECTOR_ENTRY, GX_TIMER_TRIGGER_ACTIVATION_ENTRY,
+    GX_TIMER_TRIGGER_SOURCE_ENTRY,
+};
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_timer_selector() -> Result<i64> {
+    let timer_selector: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_TIMER_SELECTOR)?;
+    println!("Now, timer selector is {}", timer_selector);
+    Ok(timer_selector)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_timer_selector(timer_selector: GX_TIMER_SELECTOR_ENTRY) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_TIMER_SELECTOR,
+        &(timer_selector as i64),
+    )?;
+    println!("Now, timer selector is {:?}", timer_selector);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_timer_duration() -> Result<f64> {
+    let timer_duration: f64 = gxi_get_feature_value(GX_FEATURE_ID::GX_FLOAT_TIMER_DURATION)?;
+    println!("Now, timer duration is {}", timer_duration);
+    Ok(timer_duration)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_timer_duration(timer_duration: f64) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_FLOAT_TIMER_DURATION, &timer_duration)?;
+    println!("Now, timer duration is {}", timer_duration);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_timer_delay() -> Result<f64> {
+    let timer_delay: f64 = gxi_get_feature_value(GX_FEATURE_ID::GX_FLOAT_TIMER_DELAY)?;
+    println!("Now, timer delay is {}", timer_delay);
+    Ok(timer_delay)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_timer_delay(timer_delay: f64) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_FLOAT_TIMER_DELAY, &timer_delay)?;
+    println!("Now, timer delay is {}", timer_delay);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_timer_trigger_source() -> Result<i64> {
+    let timer_trigger_source: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_TIMER_TRIGGER_SOURCE)?;
+    println!("Now, timer trigger source is {}", timer_trigger_source);
+    Ok(timer_trigger_source)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_timer_trigger_source(
+    timer_trigger_source: GX_TIMER_TRIGGER_SOURCE_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_TIMER_TRIGGER_SOURCE,
+        &(timer_trigger_source as i64),
+    )?;
+    println!("Now, timer trigger source is {:?}", timer_trigger_source);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_timer_trigger_activation() -> Result<i64> {
+    let timer_trigger_activation: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_TIMER_TRIGGER_ACTIVATION)?;
+    println!(
+        "Now, timer trigger activation is {}",
+        timer_trigger_activation
+    );
+    Ok(timer_trigger_activation)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_timer_trigger_activation(
+    timer_trigger_activation: GX_TIMER_TRIGGER_ACTIVATION_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_TIMER_TRIGGER_ACTIVATION,
+        &(timer_trigger_activation as i64),
+    )?;
+    println!(
+        "Now, timer trigger activation is {:?}",
+        timer_trigger_activation
+    );
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_counter_selector() -> Result<i64> {
+    let counter_selector: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_COUNTER_SELECTOR)?;
+    println!("Now, counter selector is {}", counter_selector);
+    Ok(counter_selector)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_counter_selector(counter_selector: GX_COUNTER_SELECTOR_ENTRY) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_COUNTER_SELECTOR,
+        &(counter_selector as i64),
+    )?;
+    println!("Now, counter selector is {:?}", counter_selector);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_counter_event_source() -> Result<i64> {
+    let counter_event_source: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_COUNTER_EVENT_SOURCE)?;
+    println!("Now, counter event source is {}", counter_event_source);
+    Ok(counter_event_source)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_counter_event_source(
+    counter_event_source: GX_COUNTER_EVENT_SOURCE_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_COUNTER_EVENT_SOURCE,
+        &(counter_event_source as i64),
+    )?;
+    println!("Now, counter event source is {:?}", counter_event_source);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_counter_reset_source() -> Result<i64> {
+    let counter_reset_source: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_COUNTER_RESET_SOURCE)?;
+    println!("Now, counter reset source is {}", counter_reset_source);
+    Ok(counter_reset_source)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_counter_reset_source(
+    counter_reset_source: GX_COUNTER_RESET_SOURCE_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_COUNTER_RESET_SOURCE,
+        &(counter_reset_source as i64),
+    )?;
+    println!("Now, counter reset source is {:?}", counter_reset_source);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_counter_reset_activation() -> Result<i64> {
+    let counter_reset_activation: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_COUNTER_RESET_ACTIVATION)?;
+    println!(
+        "Now, counter reset activation is {}",
+        counter_reset_activation
+    );
+    Ok(counter_reset_activation)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_counter_reset_activation(
+    counter_reset_activation: GX_COUNTER_RESET_ACTIVATION_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_COUNTER_RESET_ACTIVATION,
+        &(counter_reset_activation as i64),
+    )?;
+    println!(
+        "Now, counter reset activation is {:?}",
+        counter_reset_activation
+    );
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_counter_trigger_source() -> Result<i64> {
+    let counter_trigger_source: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_COUNTER_TRIGGER_SOURCE)?;
+    println!("Now, counter trigger source is {}", counter_trigger_source);
+    Ok(counter_trigger_source)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_counter_trigger_source(
+    counter_trigger_source: GX_COUNTER_TRIGGER_SOURCE_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_COUNTER_TRIGGER_SOURCE,
+        &(counter_trigger_source as i64),
+    )?;
+    println!(
+        "Now, counter trigger source is {:?}",
+        counter_trigger_source
+    );
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_counter_duration() -> Result<i64> {
+    let counter_duration: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_INT_COUNTER_DURATION)?;
+    println!("Now, counter duration is {}", counter_duration);
+    Ok(counter_duration)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_counter_duration(counter_duration: i64) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_INT_COUNTER_DURATION, &counter_duration)?;
+    println!("Now, counter duration is {}", counter_duration);
+    Ok(())
+}
+
+/// Software reset of the selected counter, it starts counting again from 0.
+#[cfg(feature = "solo")]
+pub fn gxi_reset_counter() -> Result<()> {
+    gxi_send_command(GX_FEATURE_ID::GX_COMMAND_COUNTER_RESET)?;
+    println!("Counter reset");
+    Ok(())
+}
+
+//----------------------------------------------------------
+//---------------Counter Timer Pair-------------------------
+//----------------------------------------------------------
+
+/// Settings of one timer and one counter, `None` fields are left as they are on the device.
+#[derive(Debug, Clone)]
+pub struct CounterTimerConfig {
+    pub timer: GX_TIMER_SELECTOR_ENTRY,
+    pub counter: GX_COUNTER_SELECTOR_ENTRY,
+    /// Timer pulse length in us.
+    pub timer_duration: Option<f64>,
+    /// Delay in us between the timer trigger and the pulse.
+    pub timer_delay: Option<f64>,
+    pub timer_trigger_source: Option<GX_TIMER_TRIGGER_SOURCE_ENTRY>,
+    pub timer_trigger_activation: Option<GX_TIMER_TRIGGER_ACTIVATION_ENTRY>,
+    pub counter_event_source: Option<GX_COUNTER_EVENT_SOURCE_ENTRY>,
+    pub counter_reset_source: Option<GX_COUNTER_RESET_SOURCE_ENTRY>,
+    pub counter_reset_activation: Option<GX_COUNTER_RESET_ACTIVATION_ENTRY>,
+    pub counter_trigger_source: Option<GX_COUNTER_TRIGGER_SOURCE_ENTRY>,
+    /// Number of events the counter counts before it ends.
+    pub counter_duration: Option<i64>,
+}
+
+pub struct CounterTimerBuilder {
+    config: CounterTimerConfig,
+}
+
+impl CounterTimerBuilder {
+    pub fn new(timer: GX_TIMER_SELECTOR_ENTRY, counter: GX_COUNTER_SELECTOR_ENTRY) -> Self {
+        Self {
+            config: CounterTimerConfig {
+                timer,
+                counter,
+                timer_duration: None,
+                timer_delay: None,
+                timer_trigger_source: None,
+                timer_trigger_activation: None,
+                counter_event_source: None,
+                counter_reset_source: None,
+                counter_reset_activation: None,
+                counter_trigger_source: None,
+                counter_duration: None,
+            },
+        }
+    }
+
+    pub fn timer_duration(mut self, value: f64) -> Self {
+        self.config.timer_duration = Some(value);
+        self
+    }
+
+    pub fn timer_delay(mut self, value: f64) -> Self {
+        self.config.timer_delay = Some(value);
+        self
+    }
+
+    pub fn timer_trigger_source(mut self, value: GX_TIMER_TRIGGER_SOURCE_ENTRY) -> Self {
+        self.config.timer_trigger_source = Some(value);
+        self
+    }
+
+    pub fn timer_trigger_activation(mut self, value: GX_TIMER_TRIGGER_ACTIVATION_ENTRY) -> Self {
+        self.config.timer_trigger_activation = Some(value);
+        self
+    }
+
+    pub fn counter_event_source(mut self, value: GX_COUNTER_EVENT_SOURCE_ENTRY) -> Self {
+        self.config.counter_event_source = Some(value);
+        self
+    }
+
+    pub fn counter_reset_source(mut self, value: GX_COUNTER_RESET_SOURCE_ENTRY) -> Self {
+        self.config.counter_reset_source = Some(value);
+        self
+    }
+
+    pub fn counter_reset_activation(mut self, value: GX_COUNTER_RESET_ACTIVATION_ENTRY) -> Self {
+        self.config.counter_reset_activation = Some(value);
+        self
+    }
+
+    pub fn counter_trigger_source(mut self, value: GX_COUNTER_TRIGGER_SOURCE_ENTRY) -> Self {
+        self.config.counter_trigger_source = Some(value);
+        self
+    }
+
+    pub fn counter_duration(mut self, value: i64) -> Self {
+        self.config.counter_duration = Some(value);
+        self
+    }
+
+    pub fn build(self) -> Result<CounterTimerConfig> {
+        let invalid = |name: &str, value: String| {
+            Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "{} must not be negative, got {}",
+                name, value
+            ))))
+        };
+        if let Some(value) = self.config.timer_duration.filter(|v| *v < 0.0) {
+            return invalid("timer duration", value.to_string());
+        }
+        if let Some(value) = self.config.timer_delay.filter(|v| *v < 0.0) {
+            return invalid("timer delay", value.to_string());
+        }
+        if let Some(value) = self.config.counter_duration.filter(|v| *v < 0) {
+            return invalid("counter duration", value.to_string());
+        }
+        Ok(self.config)
+    }
+}
+
+/// The timer and counter settings as read back from the device, `None` when not implemented.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CounterTimerState {
+    pub timer_duration: Option<f64>,
+    pub timer_delay: Option<f64>,
+    pub timer_trigger_source: Option<i64>,
+    pub timer_trigger_activation: Option<i64>,
+    pub counter_event_source: Option<i64>,
+    pub counter_reset_source: Option<i64>,
+    pub counter_reset_activation: Option<i64>,
+    pub counter_trigger_source: Option<i64>,
+    pub counter_duration: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FeatureValue {
+    Int(i64),
+    Float(f64),
+}
+
+#[cfg(feature = "solo")]
+fn gxi_write_feature(feature_id: GX_FEATURE_ID, value: FeatureValue) -> Result<()> {
+    match value {
+        FeatureValue::Int(value) => gxi_set_feature_value(feature_id, &value),
+        FeatureValue::Float(value) => gxi_set_feature_value(feature_id, &value),
+    }
+}
+
+#[cfg(feature = "solo")]
+fn gxi_read_feature(feature_id: GX_FEATURE_ID, like: FeatureValue) -> Result<FeatureValue> {
+    Ok(match like {
+        FeatureValue::Int(_) => FeatureValue::Int(gxi_get_feature_value(feature_id)?),
+        FeatureValue::Float(_) => FeatureValue::Float(gxi_get_feature_value(feature_id)?),
+    })
+}
+
+#[cfg(feature = "solo")]
+fn gxi_read_optional<T: std::any::Any + std::fmt::Debug + Clone>(
+    feature_id: GX_FEATURE_ID,
+) -> Result<Option<T>> {
+    if gxi_is_implemented(feature_id)? {
+        Ok(Some(gxi_get_feature_value(feature_id)?))
+    } else {
+        Ok(None)
+    }
+}
+
+type FeatureWrites = Vec<(GX_FEATURE_ID, FeatureValue)>;
+
+fn timer_writes(config: &CounterTimerConfig) -> FeatureWrites {
+    let mut writes = Vec::new();
+    if let Some(value) = config.timer_trigger_source {
+        writes.push((
+            GX_FEATURE_ID::GX_ENUM_TIMER_TRIGGER_SOURCE,
+            FeatureValue::Int(value as i64),
+        ));
+    }
+    if let Some(value) = config.timer_trigger_activation {
+        writes.push((
+            GX_FEATURE_ID::GX_ENUM_TIMER_TRIGGER_ACTIVATION,
+            FeatureValue::Int(value as i64),
+        ));
+    }
+    if let Some(value) = config.timer_delay {
+        writes.push((
+            GX_FEATURE_ID::GX_FLOAT_TIMER_DELAY,
+            FeatureValue::Float(value),
+        ));
+    }
+    if let Some(value) = config.timer_duration {
+        writes.push((
+            GX_FEATURE_ID::GX_FLOAT_TIMER_DURATION,
+            FeatureValue::Float(value),
+        ));
+    }
+    writes
+}
+
+fn counter_writes(config: &CounterTimerConfig) -> FeatureWrites {
+    let mut writes = Vec::new();
+    if let Some(value) = config.counter_event_source {
+        writes.push((
+            GX_FEATURE_ID::GX_ENUM_COUNTER_EVENT_SOURCE,
+            FeatureValue::Int(value as i64),
+        ));
+    }
+    if let Some(value) = config.counter_reset_source {
+        writes.push((
+            GX_FEATURE_ID::GX_ENUM_COUNTER_RESET_SOURCE,
+            FeatureValue::Int(value as i64),
+        ));
+    }
+    if let Some(value) = config.counter_reset_activation {
+        writes.push((
+            GX_FEATURE_ID::GX_ENUM_COUNTER_RESET_ACTIVATION,
+            FeatureValue::Int(value as i64),
+        ));
+    }
+    if let Some(value) = config.counter_trigger_source {
+        writes.push((
+            GX_FEATURE_ID::GX_ENUM_COUNTER_TRIGGER_SOURCE,
+            FeatureValue::Int(value as i64),
+        ));
+    }
+    if let Some(value) = config.counter_duration {
+        writes.push((
+            GX_FEATURE_ID::GX_INT_COUNTER_DURATION,
+            FeatureValue::Int(value),
+        ));
+    }
+    writes
+}
+
+// 写之前先确认每个feature都可写，并记下旧值用于回滚
+#[cfg(feature = "solo")]
+fn gxi_snapshot_features(writes: &FeatureWrites) -> Result<FeatureWrites> {
+    let mut snapshot = Vec::with_capacity(writes.len());
+    for (feature_id, value) in writes {
+        if !gxi_is_writable(*feature_id)? {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "{:?} is not writable",
+                feature_id
+            ))));
+        }
+        snapshot.push((*feature_id, gxi_read_feature(*feature_id, *value)?));
+    }
+    Ok(snapshot)
+}
+
+#[cfg(feature = "solo")]
+fn gxi_write_features(writes: &FeatureWrites) -> Result<()> {
+    for (feature_id, value) in writes {
+        gxi_write_feature(*feature_id, *value)?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+fn gxi_restore_features(snapshot: &FeatureWrites) {
+    for (feature_id, value) in snapshot {
+        if let Err(e) = gxi_write_feature(*feature_id, *value) {
+            println!("Failed to restore {:?}: {}", feature_id, e);
+        }
+    }
+}
+
+/// Read the settings of `timer` and `counter`. Leaves those two selected.
+#[cfg(feature = "solo")]
+pub fn gxi_get_counter_timer_state(
+    timer: GX_TIMER_SELECTOR_ENTRY,
+    counter: GX_COUNTER_SELECTOR_ENTRY,
+) -> Result<CounterTimerState> {
+    gxi_set_timer_selector(timer)?;
+    let timer_duration = gxi_read_optional(GX_FEATURE_ID::GX_FLOAT_TIMER_DURATION)?;
+    let timer_delay = gxi_read_optional(GX_FEATURE_ID::GX_FLOAT_TIMER_DELAY)?;
+    let timer_trigger_source = gxi_read_optional(GX_FEATURE_ID::GX_ENUM_TIMER_TRIGGER_SOURCE)?;
+    let timer_trigger_activation =
+        gxi_read_optional(GX_FEATURE_ID::GX_ENUM_TIMER_TRIGGER_ACTIVATION)?;
+
+    gxi_set_counter_selector(counter)?;
+    Ok(CounterTimerState {
+        timer_duration,
+        timer_delay,
+        timer_trigger_source,
+        timer_trigger_activation,
+        counter_event_source: gxi_read_optional(GX_FEATURE_ID::GX_ENUM_COUNTER_EVENT_SOURCE)?,
+        counter_reset_source: gxi_read_optional(GX_FEATURE_ID::GX_ENUM_COUNTER_RESET_SOURCE)?,
+        counter_reset_activation: gxi_read_optional(
+            GX_FEATURE_ID::GX_ENUM_COUNTER_RESET_ACTIVATION,
+        )?,
+        counter_trigger_source: gxi_read_optional(GX_FEATURE_ID::GX_ENUM_COUNTER_TRIGGER_SOURCE)?,
+        counter_duration: gxi_read_optional(GX_FEATURE_ID::GX_INT_COUNTER_DURATION)?,
+    })
+}
+
+/// Apply a timer and counter pair as a whole.
+///
+/// Every feature is checked writable and its old value saved before anything is written. If a
+/// write fails, the features already written are set back, so the pair is never left half
+/// configured. Returns the settings read back from the device.
+#[cfg(feature = "solo")]
+pub fn gxi_apply_counter_timer_config(config: &CounterTimerConfig) -> Result<CounterTimerState> {
+    let timer_writes = timer_writes(config);
+    let counter_writes = counter_writes(config);
+
+    gxi_set_timer_selector(config.timer)?;
+    let timer_snapshot = gxi_snapshot_features(&timer_writes)?;
+    gxi_set_counter_selector(config.counter)?;
+    let counter_snapshot = gxi_snapshot_features(&counter_writes)?;
+
+    gxi_set_timer_selector(config.timer)?;
+    if let Err(e) = gxi_write_features(&timer_writes) {
+        gxi_restore_features(&timer_snapshot);
+        return Err(e);
+    }
+    let counter_written =
+        gxi_set_counter_selector(config.counter).and_then(|_| gxi_write_features(&counter_writes));
+    if let Err(e) = counter_written {
+        gxi_restore_features(&counter_snapshot);
+        if gxi_set_timer_selector(config.timer).is_ok() {
+            gxi_restore_features(&timer_snapshot);
+        }
+        return Err(e);
+    }
+
+    let state = gxi_get_counter_timer_state(config.timer, config.counter)?;
+    println!(
+        "Successfully applied {:?} and {:?}",
+        config.timer, config.counter
+    );
+    Ok(state)
+}
@@ -0,0 +1,613 @@
+//! Data stream layer features (GX_DS_*), such as the frame and packet statistics of the stream.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::hal::base::gxi_check;
+use crate::hal::check::check_gx_status;
+use crate::hal::config::*;
+use crate::hal::device::gxi_get_device_handle;
+use crate::raw::gx_enum::{
+    GX_DS_RESEND_MODE_ENTRY, GX_DS_STREAM_BUFFER_HANDLING_MODE_ENTRY, GX_FEATURE_ID,
+};
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{JoinHandle, sleep, spawn};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_announced_buffer_count() -> Result<i64> {
+    let announced_buffer_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_DS_INT_ANNOUNCED_BUFFER_COUNT)?;
+    println!("Now, announced buffer count is {}", announced_buffer_count);
+    Ok(announced_buffer_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_delivered_frame_count() -> Result<i64> {
+    let delivered_frame_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_DS_INT_DELIVERED_FRAME_COUNT)?;
+    println!("Now, delivered frame count is {}", delivered_frame_count);
+    Ok(delivered_frame_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_lost_frame_count() -> Result<i64> {
+    let lost_frame_count: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_DS_INT_LOST_FRAME_COUNT)?;
+    println!("Now, lost frame count is {}", lost_frame_count);
+    Ok(lost_frame_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_incomplete_frame_count() -> Result<i64> {
+    let incomplete_frame_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_DS_INT_INCOMPLETE_FRAME_COUNT)?;
+    println!("Now, incomplete frame count is {}", incomplete_frame_count);
+    Ok(incomplete_frame_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_delivered_packet_count() -> Result<i64> {
+    let delivered_packet_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_DS_INT_DELIVERED_PACKET_COUNT)?;
+    println!("Now, delivered packet count is {}", delivered_packet_count);
+    Ok(delivered_packet_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_resend_packet_count() -> Result<i64> {
+    let resend_packet_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_DS_INT_RESEND_PACKET_COUNT)?;
+    println!("Now, resend packet count is {}", resend_packet_count);
+    Ok(resend_packet_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_rescued_packet_count() -> Result<i64> {
+    let rescued_packet_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_DS_INT_RESCUED_PACKED_COUNT)?;
+    println!("Now, rescued packet count is {}", rescued_packet_count);
+    Ok(rescued_packet_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_resend_command_count() -> Result<i64> {
+    let resend_command_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_DS_INT_RESEND_COMMAND_COUNT)?;
+    println!("Now, resend command count is {}", resend_command_count);
+    Ok(resend_command_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_unexpected_packet_count() -> Result<i64> {
+    let unexpected_packet_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_DS_INT_UNEXPECTED_PACKED_COUNT)?;
+    println!(
+        "Now, unexpected packet count is {}",
+        unexpected_packet_count
+    );
+    Ok(unexpected_packet_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_missing_block_id_count() -> Result<i64> {
+    let missing_block_id_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_DS_INT_MISSING_BLOCKID_COUNT)?;
+    println!("Now, missing block id count is {}", missing_block_id_count);
+    Ok(missing_block_id_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_stream_buffer_handling_mode() -> Result<i64> {
+    let buffer_handling_mode: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_DS_ENUM_STREAM_BUFFER_HANDLING_MODE)?;
+    println!(
+        "Now, stream buffer handling mode is {}",
+        buffer_handling_mode
+    );
+    Ok(buffer_handling_mode)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_stream_buffer_handling_mode(
+    buffer_handling_mode: GX_DS_STREAM_BUFFER_HANDLING_MODE_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_DS_ENUM_STREAM_BUFFER_HANDLING_MODE,
+        &(buffer_handling_mode as i64),
+    )?;
+    println!(
+        "Now, stream buffer handling mode is {:?}",
+        buffer_handling_mode
+    );
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_stream_buffer_handling_mode_oldest_first() -> Result<()> {
+    gxi_set_stream_buffer_handling_mode(
+        GX_DS_STREAM_BUFFER_HANDLING_MODE_ENTRY::GX_DS_STREAM_BUFFER_HANDLING_MODE_OLDEST_FIRST,
+    )
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_stream_buffer_handling_mode_newest_only() -> Result<()> {
+    gxi_set_stream_buffer_handling_mode(
+        GX_DS_STREAM_BUFFER_HANDLING_MODE_ENTRY::GX_DS_STREAM_BUFFER_HANDLING_MODE_NEWEST_ONLY,
+    )
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_resend_mode() -> Result<i64> {
+    let resend_mode: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_DS_ENUM_RESEND_MODE)?;
+    println!("Now, resend mode is {}", resend_mode);
+    Ok(resend_mode)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_resend_mode(resend_mode: GX_DS_RESEND_MODE_ENTRY) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_DS_ENUM_RESEND_MODE, &(resend_mode as i64))?;
+    println!("Now, resend mode is {:?}", resend_mode);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_stream_transfer_size() -> Result<i64> {
+    let transfer_size: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_DS_INT_STREAM_TRANSFER_SIZE)?;
+    println!("Now, stream transfer size is {}", transfer_size);
+    Ok(transfer_size)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_stream_transfer_size(transfer_size: i64) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_DS_INT_STREAM_TRANSFER_SIZE,
+        &transfer_size,
+    )?;
+    println!("Now, stream transfer size is {}", transfer_size);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_stream_transfer_number_urb() -> Result<i64> {
+    let number_urb: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_DS_INT_STREAM_TRANSFER_NUMBER_URB)?;
+    println!("Now, stream transfer number urb is {}", number_urb);
+    Ok(number_urb)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_stream_transfer_number_urb(number_urb: i64) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_DS_INT_STREAM_TRANSFER_NUMBER_URB,
+        &number_urb,
+    )?;
+    println!("Now, stream transfer number urb is {}", number_urb);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_socket_buffer_size() -> Result<i64> {
+    let socket_buffer_size: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_DS_INT_SOCKET_BUFFER_SIZE)?;
+    println!("Now, socket buffer size is {}", socket_buffer_size);
+    Ok(socket_buffer_size)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_socket_buffer_size(socket_buffer_size: i64) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_DS_INT_SOCKET_BUFFER_SIZE,
+        &socket_buffer_size,
+    )?;
+    println!("Now, socket buffer size is {}", socket_buffer_size);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_block_timeout() -> Result<i64> {
+    let block_timeout: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_DS_INT_BLOCK_TIMEOUT)?;
+    println!("Now, block timeout is {}", block_timeout);
+    Ok(block_timeout)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_block_timeout(block_timeout: i64) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_DS_INT_BLOCK_TIMEOUT, &block_timeout)?;
+    println!("Now, block timeout is {}", block_timeout);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_acquisition_buffer_number(buffer_number: u64) -> Result<()> {
+    let gxi_device = gxi_get_device_handle()?;
+    let status = gxi_check(|gxi| gxi.gx_set_acquisition_buffer_number(gxi_device, buffer_number))?;
+
+    check_gx_status(status)?;
+    println!("Now, acquisition buffer number is {}", buffer_number);
+    Ok(())
+}
+
+//----------------------------------------------------------
+//---------------Stream Statistics--------------------------
+//----------------------------------------------------------
+
+/// A snapshot of the data stream counters.
+///
+/// The packet counters only exist on GigE Vision devices, so they are `None` when the device does not implement them.
+#[derive(Debug, Clone)]
+pub struct StreamStats {
+    pub captured_at: Instant,
+    pub announced_buffer_count: i64,
+    pub delivered_frame_count: i64,
+    pub lost_frame_count: i64,
+    pub incomplete_frame_count: i64,
+    pub delivered_packet_count: Option<i64>,
+    pub resend_packet_count: Option<i64>,
+    pub rescued_packet_count: Option<i64>,
+    pub resend_command_count: Option<i64>,
+    pub unexpected_packet_count: Option<i64>,
+    pub missing_block_id_count: Option<i64>,
+}
+
+/// The change of the data stream counters between two `StreamStats` snapshots.
+#[derive(Debug, Clone)]
+pub struct StreamStatsDelta {
+    pub elapsed: Duration,
+    pub delivered_frames: i64,
+    pub lost_frames: i64,
+    pub incomplete_frames: i64,
+    pub resend_packets: Option<i64>,
+    pub missing_block_ids: Option<i64>,
+    /// Delivered frames per second.
+    pub frame_rate: f64,
+    /// Lost frames per second.
+    pub lost_frame_rate: f64,
+    /// Incomplete frames per second.
+    pub incomplete_frame_rate: f64,
+    /// Resent packets per second.
+    pub resend_packet_rate: Option<f64>,
+}
+
+impl StreamStats {
+    /// Compute the deltas and rates from an earlier snapshot to this one.
+    pub fn delta_since(&self, earlier: &StreamStats) -> StreamStatsDelta {
+        let elapsed = self
+            .captured_at
+            .saturating_duration_since(earlier.captured_at);
+        let secs = elapsed.as_secs_f64();
+        let per_sec = |count: i64| if secs > 0.0 { count as f64 / secs } else { 0.0 };
+        let diff = |now: Option<i64>, before: Option<i64>| match (now, before) {
+            (Some(now), Some(before)) => Some(now - before),
+            _ => None,
+        };
+
+        let delivered_frames = self.delivered_frame_count - earlier.delivered_frame_count;
+        let lost_frames = self.lost_frame_count - earlier.lost_frame_count;
+        let incomplete_frames = self.incomplete_frame_count - earlier.incomplete_frame_count;
+        let resend_packets = diff(self.resend_packet_count, earlier.resend_packet_count);
+
+        StreamStatsDelta {
+            elapsed,
+            delivered_frames,
+            lost_frames,
+            incomplete_frames,
+            resend_packets,
+            missing_block_ids: diff(self.missing_block_id_count, earlier.missing_block_id_count),
+            frame_rate: per_sec(delivered_frames),
+            lost_frame_rate: per_sec(lost_frames),
+            incomplete_frame_rate: per_sec(incomplete_frames),
+            resend_packet_rate: resend_packets.map(per_sec),
+        }
+    }
+}
+
+impl StreamStatsDelta {
+    /// The share of frames lost in this period, from 0.0 to 1.0.
+    pub fn lost_frame_ratio(&self) -> f64 {
+        let total = self.delivered_frames + self.lost_frames;
+        if total > 0 {
+            self.lost_frames as f64 / total as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(feature = "solo")]
+fn gxi_get_optional_int(feature_id: GX_FEATURE_ID) -> Result<Option<i64>> {
+    if gxi_is_implemented(feature_id)? {
+        Ok(Some(gxi_get_int(feature_id)?))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_stream_stats() -> Result<StreamStats> {
+    let stream_stats = StreamStats {
+        captured_at: Instant::now(),
+        announced_buffer_count: gxi_get_announced_buffer_count()?,
+        delivered_frame_count: gxi_get_delivered_frame_count()?,
+        lost_frame_count: gxi_get_lost_frame_count()?,
+        incomplete_frame_count: gxi_get_incomplete_frame_count()?,
+        delivered_packet_count: gxi_get_optional_int(
+            GX_FEATURE_ID::GX_DS_INT_DELIVERED_PACKET_COUNT,
+        )?,
+        resend_packet_count: gxi_get_optional_int(GX_FEATURE_ID::GX_DS_INT_RESEND_PACKET_COUNT)?,
+        rescued_packet_count: gxi_get_optional_int(GX_FEATURE_ID::GX_DS_INT_RESCUED_PACKED_COUNT)?,
+        resend_command_count: gxi_get_optional_int(GX_FEATURE_ID::GX_DS_INT_RESEND_COMMAND_COUNT)?,
+        unexpected_packet_count: gxi_get_optional_int(
+            GX_FEATURE_ID::GX_DS_INT_UNEXPECTED_PACKED_COUNT,
+        )?,
+        missing_block_id_count: gxi_get_optional_int(
+            GX_FEATURE_ID::GX_DS_INT_MISSING_BLOCKID_COUNT,
+        )?,
+    };
+    println!("Now, stream stats is {:?}", stream_stats);
+    Ok(stream_stats)
+}
+
+/// Samples `StreamStats` and reports the deltas and rates against the previous sample.
+pub struct StreamStatsSampler {
+    pub interval: Duration,
+    last: Option<StreamStats>,
+}
+
+impl StreamStatsSampler {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: None,
+        }
+    }
+
+    /// Take a snapshot now. The first call only records the baseline and returns `None`.
+    #[cfg(feature = "solo")]
+    pub fn sample(&mut self) -> Result<Option<StreamStatsDelta>> {
+        let stream_stats = gxi_get_stream_stats()?;
+        let delta = self
+            .last
+            .as_ref()
+            .map(|last| stream_stats.delta_since(last));
+        self.last = Some(stream_stats);
+        Ok(delta)
+    }
+
+    /// Sample every `interval` on a background thread and hand each delta to `on_sample`.
+    #[cfg(feature = "solo")]
+    pub fn spawn<F>(mut self, mut on_sample: F) -> StreamStatsMonitor
+    where
+        F: FnMut(&StreamStatsDelta) + Send + 'static,
+    {
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = running.clone();
+        let handle = spawn(move || {
+            while thread_running.load(Ordering::Relaxed) {
+                match self.sample() {
+                    Ok(Some(delta)) => on_sample(&delta),
+                    Ok(None) => {}
+                    Err(e) => {
+                        println!("Failed to sample stream stats: {:?}", e);
+                        break;
+                    }
+                }
+                sleep(self.interval);
+            }
+        });
+
+        StreamStatsMonitor {
+            running,
+            handle: Some(handle),
+        }
+    }
+}
+
+/// Handle of a running `StreamStatsSampler` thread, stop it with `stop()` or by dropping it.
+pub struct StreamStatsMonitor {
+    running: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl StreamStatsMonitor {
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for StreamStatsMonitor {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+//----------------------------------------------------------
+//---------------Stream Config------------------------------
+//----------------------------------------------------------
+
+/// Data stream settings, applied together by `gxi_apply_stream_config` before the acquisition starts.
+///
+/// The settings left as `None` keep the current device value.
+#[derive(Debug, Clone, Default)]
+pub struct StreamConfig {
+    pub buffer_handling_mode: Option<GX_DS_STREAM_BUFFER_HANDLING_MODE_ENTRY>,
+    pub resend_mode: Option<GX_DS_RESEND_MODE_ENTRY>,
+    pub transfer_size: Option<i64>,
+    pub transfer_number_urb: Option<i64>,
+    pub socket_buffer_size: Option<i64>,
+    pub block_timeout: Option<i64>,
+    pub acquisition_buffer_number: Option<u64>,
+}
+
+pub struct StreamConfigBuilder {
+    config: StreamConfig,
+}
+
+impl Default for StreamConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamConfigBuilder {
+    pub fn new() -> Self {
+        Self {
+            config: StreamConfig::default(),
+        }
+    }
+
+    pub fn buffer_handling_mode(mut self, value: GX_DS_STREAM_BUFFER_HANDLING_MODE_ENTRY) -> Self {
+        self.config.buffer_handling_mode = Some(value);
+        self
+    }
+
+    /// Only deliver the latest frame, the older ones are dropped. For latency sensitive work.
+    pub fn newest_only(self) -> Self {
+        self.buffer_handling_mode(
+            GX_DS_STREAM_BUFFER_HANDLING_MODE_ENTRY::GX_DS_STREAM_BUFFER_HANDLING_MODE_NEWEST_ONLY,
+        )
+    }
+
+    /// Deliver every frame in order, until the buffers run out.
+    pub fn oldest_first(self) -> Self {
+        self.buffer_handling_mode(
+            GX_DS_STREAM_BUFFER_HANDLING_MODE_ENTRY::GX_DS_STREAM_BUFFER_HANDLING_MODE_OLDEST_FIRST,
+        )
+    }
+
+    pub fn resend_mode(mut self, value: GX_DS_RESEND_MODE_ENTRY) -> Self {
+        self.config.resend_mode = Some(value);
+        self
+    }
+
+    pub fn transfer_size(mut self, value: i64) -> Self {
+        self.config.transfer_size = Some(value);
+        self
+    }
+
+    pub fn transfer_number_urb(mut self, value: i64) -> Self {
+        self.config.transfer_number_urb = Some(value);
+        self
+    }
+
+    pub fn socket_buffer_size(mut self, value: i64) -> Self {
+        self.config.socket_buffer_size = Some(value);
+        self
+    }
+
+    pub fn block_timeout(mut self, value: i64) -> Self {
+        self.config.block_timeout = Some(value);
+        self
+    }
+
+    pub fn acquisition_buffer_number(mut self, value: u64) -> Self {
+        self.config.acquisition_buffer_number = Some(value);
+        self
+    }
+
+    pub fn build(self) -> Result<StreamConfig> {
+        let positive = [
+            ("transfer_size", self.config.transfer_size),
+            ("transfer_number_urb", self.config.transfer_number_urb),
+            ("socket_buffer_size", self.config.socket_buffer_size),
+            ("block_timeout", self.config.block_timeout),
+        ];
+        for (name, value) in positive {
+            if let Some(value) = value {
+                if value <= 0 {
+                    return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                        "{} must be positive, got {}.",
+                        name, value
+                    ))));
+                }
+            }
+        }
+        if self.config.acquisition_buffer_number == Some(0) {
+            return Err(Error::new(ErrorKind::InvalidParameter(
+                "acquisition_buffer_number must be at least 1.".to_string(),
+            )));
+        }
+        Ok(self.config)
+    }
+}
+
+// 先检查当前是否可写（采集中数据流参数是只读的），再检查是否落在设备给出的范围和步长上
+#[cfg(feature = "solo")]
+fn gxi_check_stream_int(feature_id: GX_FEATURE_ID, value: i64) -> Result<()> {
+    gxi_check_stream_writable(feature_id)?;
+    let range = gxi_get_int_range(feature_id)?;
+    let on_increment = range.nInc <= 1 || (value - range.nMin) % range.nInc == 0;
+    if value < range.nMin || value > range.nMax || !on_increment {
+        return Err(Error::new(ErrorKind::InvalidParameter(format!(
+            "{:?} must be in [{}, {}] with increment {}, got {}.",
+            feature_id, range.nMin, range.nMax, range.nInc, value
+        ))));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+fn gxi_check_stream_writable(feature_id: GX_FEATURE_ID) -> Result<()> {
+    if gxi_is_writable(feature_id)? {
+        Ok(())
+    } else {
+        Err(Error::new(ErrorKind::InvalidParameter(format!(
+            "{:?} is not writable now, stream config must be applied before acquisition starts.",
+            feature_id
+        ))))
+    }
+}
+
+/// Validate every setting against the device first, then write them. Nothing is written if any check fails.
+#[cfg(feature = "solo")]
+pub fn gxi_apply_stream_config(config: &StreamConfig) -> Result<()> {
+    if config.buffer_handling_mode.is_some() {
+        gxi_check_stream_writable(GX_FEATURE_ID::GX_DS_ENUM_STREAM_BUFFER_HANDLING_MODE)?;
+    }
+    if config.resend_mode.is_some() {
+        gxi_check_stream_writable(GX_FEATURE_ID::GX_DS_ENUM_RESEND_MODE)?;
+    }
+    let int_settings = [
+        (
+            GX_FEATURE_ID::GX_DS_INT_STREAM_TRANSFER_SIZE,
+            config.transfer_size,
+        ),
+        (
+            GX_FEATURE_ID::GX_DS_INT_STREAM_TRANSFER_NUMBER_URB,
+            config.transfer_number_urb,
+        ),
+        (
+            GX_FEATURE_ID::GX_DS_INT_SOCKET_BUFFER_SIZE,
+            config.socket_buffer_size,
+        ),
+        (GX_FEATURE_ID::GX_DS_INT_BLOCK_TIMEOUT, config.block_timeout),
+    ];
+    for (feature_id, value) in int_settings {
+        if let Some(value) = value {
+            gxi_check_stream_int(feature_id, value)?;
+        }
+    }
+
+    if let Some(buffer_handling_mode) = config.buffer_handling_mode {
+        gxi_set_stream_buffer_handling_mode(buffer_handling_mode)?;
+    }
+    if let Some(resend_mode) = config.resend_mode {
+        gxi_set_resend_mode(resend_mode)?;
+    }
+    for (feature_id, value) in int_settings {
+        if let Some(value) = value {
+            gxi_set_int(feature_id, value)?;
+        }
+    }
+    if let Some(buffer_number) = config.acquisition_buffer_number {
+        gxi_set_acquisition_buffer_number(buffer_number)?;
+    }
+    println!("Successfully applied stream config {:?}", config);
+    Ok(())
+}
@@ -2,7 +2,8 @@
 
 use crate::error::Result;
 use crate::hal::config::*;
-use crate::raw::gx_enum::GX_FEATURE_ID;
+use crate::hal::device::gxi_send_command;
+use crate::raw::gx_enum::{GX_DEVICE_TEMPERATURE_SELECTOR_ENTRY, GX_FEATURE_ID};
 
 #[cfg(feature = "solo")]
 pub fn gxi_get_device_vendor_name() -> Result<String> {
@@ -143,3 +144,97 @@ pub fn gxi_get_device_link_current_throughput() -> Result<i64> {
     println!("Now, link selector is {}", link_selector);
     Ok(link_selector)
 }
+
+/// Ticks per second of the device timestamp clock.
+#[cfg(feature = "solo")]
+pub fn gxi_get_timestamp_tick_frequency() -> Result<i64> {
+    let tick_frequency: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_INT_TIMESTAMP_TICK_FREQUENCY)?;
+    println!("Now, timestamp tick frequency is {}", tick_frequency);
+    Ok(tick_frequency)
+}
+
+/// Copy the current device time into the timestamp latch value.
+#[cfg(feature = "solo")]
+pub fn gxi_latch_timestamp() -> Result<()> {
+    gxi_send_command(GX_FEATURE_ID::GX_COMMAND_TIMESTAMP_LATCH)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_reset_timestamp() -> Result<()> {
+    gxi_send_command(GX_FEATURE_ID::GX_COMMAND_TIMESTAMP_RESET)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_reset_timestamp_latch() -> Result<()> {
+    gxi_send_command(GX_FEATURE_ID::GX_COMMAND_TIMESTAMP_LATCH_RESET)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_timestamp_latch_value() -> Result<i64> {
+    let latch_value: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_INT_TIMESTAMP_LATCH_VALUE)?;
+    println!("Now, timestamp latch value is {}", latch_value);
+    Ok(latch_value)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_device_temperature_selector() -> Result<i64> {
+    let temperature_selector: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_DEVICE_TEMPERATURE_SELECTOR)?;
+    println!(
+        "Now, device temperature selector is {}",
+        temperature_selector
+    );
+    Ok(temperature_selector)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_device_temperature_selector(
+    temperature_selector: GX_DEVICE_TEMPERATURE_SELECTOR_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_DEVICE_TEMPERATURE_SELECTOR,
+        &(temperature_selector as i64),
+    )?;
+    println!(
+        "Now, device temperature selector is {:?}",
+        temperature_selector
+    );
+    Ok(())
+}
+
+/// Temperature in Celsius at the selected point.
+#[cfg(feature = "solo")]
+pub fn gxi_get_device_temperature() -> Result<f64> {
+    let temperature: f64 = gxi_get_feature_value(GX_FEATURE_ID::GX_FLOAT_DEVICE_TEMPERATURE)?;
+    println!("Now, device temperature is {}", temperature);
+    Ok(temperature)
+}
+
+/// Select `temperature_selector` and read its temperature, `None` when the device has no
+/// such temperature.
+#[cfg(feature = "solo")]
+pub fn gxi_get_device_temperature_of(
+    temperature_selector: GX_DEVICE_TEMPERATURE_SELECTOR_ENTRY,
+) -> Result<Option<f64>> {
+    if !gxi_is_implemented(GX_FEATURE_ID::GX_FLOAT_DEVICE_TEMPERATURE)? {
+        return Ok(None);
+    }
+    // 没有温度选择器的设备只有一个测温点
+    if gxi_is_implemented(GX_FEATURE_ID::GX_ENUM_DEVICE_TEMPERATURE_SELECTOR)? {
+        let available =
+            gxi_get_enum_description(GX_FEATURE_ID::GX_ENUM_DEVICE_TEMPERATURE_SELECTOR)?;
+        if !available
+            .iter()
+            .any(|entry| entry.n_value == temperature_selector as i64)
+        {
+            return Ok(None);
+        }
+        gxi_set_device_temperature_selector(temperature_selector)?;
+    } else if temperature_selector
+        != GX_DEVICE_TEMPERATURE_SELECTOR_ENTRY::GX_DEVICE_TEMPERATURE_SELECTOR_SENSOR
+    {
+        return Ok(None);
+    }
+    Ok(Some(gxi_get_device_temperature()?))
+}
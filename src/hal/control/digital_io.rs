@@ -1,22 +1,269 @@
-//! Placeholder, but I found that no feature_id here
-
-// use crate::hal::config::*;
-// use crate::raw::gx_enum::GX_FEATURE_ID;
-// use crate::error::Result;
-
-// - [ ] gxi_get_user_output_value()
-// - [ ] gxi_set_user_output_value()
-
-// #[cfg(feature = "solo")]
-// pub fn gxi_get_user_output_value() -> Result<bool> {
-//     let user_output_value:bool = gxi_get_feature_value(GX_FEATURE_ID::GX_BOOL_USER_OUTPUT_VALUE)?;
-//     println!("Now, user output value is {}",user_output_value);
-//     Ok(user_output_value)
-// }
-
-// #[cfg(feature = "solo")]
-// pub fn gxi_set_user_output_value(user_output_value:bool) -> Result<()> {
-//     gxi_set_feature_value(GX_FEATURE_ID::GX_BOOL_USER_OUTPUT_VALUE, &user_output_value)?;
-//     println!("Successfully set user output value to {}",user_output_value);
-//     Ok(())
-// }
+//! Digital I/O lines, user outputs and the strobe output.
+
+use crate::error::Result;
+use crate::hal::config::*;
+use crate::raw::gx_enum::{
+    GX_FEATURE_ID, GX_LINE_MODE_ENTRY, GX_LINE_SELECTOR_ENTRY, GX_LINE_SOURCE_ENTRY,
+    GX_STROBE_SWITCH_ENTRY, GX_USER_OUTPUT_MODE_ENTRY, GX_USER_OUTPUT_SELECTOR_ENTRY,
+};
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_line_selector() -> Result<i64> {
+    let line_selector: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_LINE_SELECTOR)?;
+    println!("Now, line selector is {}", line_selector);
+    Ok(line_selector)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_line_selector(line_selector: GX_LINE_SELECTOR_ENTRY) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_LINE_SELECTOR,
+        &(line_selector as i64),
+    )?;
+    println!("Now, line selector is {:?}", line_selector);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_line_mode() -> Result<i64> {
+    let line_mode: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_LINE_MODE)?;
+    println!("Now, line mode is {}", line_mode);
+    Ok(line_mode)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_line_mode(line_mode: GX_LINE_MODE_ENTRY) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_LINE_MODE, &(line_mode as i64))?;
+    println!("Now, line mode is {:?}", line_mode);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_line_mode_input() -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_LINE_MODE, &0i64)?;
+    println!("Now, line mode is 0");
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_line_mode_output() -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_LINE_MODE, &1i64)?;
+    println!("Now, line mode is 1");
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_line_inverter() -> Result<bool> {
+    let line_inverter: bool = gxi_get_feature_value(GX_FEATURE_ID::GX_BOOL_LINE_INVERTER)?;
+    println!("Now, line inverter is {}", line_inverter);
+    Ok(line_inverter)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_line_inverter(line_inverter: bool) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_BOOL_LINE_INVERTER, &line_inverter)?;
+    println!("Now, line inverter is {}", line_inverter);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_line_source() -> Result<i64> {
+    let line_source: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_LINE_SOURCE)?;
+    println!("Now, line source is {}", line_source);
+    Ok(line_source)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_line_source(line_source: GX_LINE_SOURCE_ENTRY) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_LINE_SOURCE, &(line_source as i64))?;
+    println!("Now, line source is {:?}", line_source);
+    Ok(())
+}
+
+/// Level of the selected line.
+#[cfg(feature = "solo")]
+pub fn gxi_get_line_status() -> Result<bool> {
+    let line_status: bool = gxi_get_feature_value(GX_FEATURE_ID::GX_BOOL_LINE_STATUS)?;
+    println!("Now, line status is {}", line_status);
+    Ok(line_status)
+}
+
+/// Levels of all lines in one bit field, bit n is the line with selector value n.
+#[cfg(feature = "solo")]
+pub fn gxi_get_line_status_all() -> Result<i64> {
+    let line_status_all: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_INT_LINE_STATUS_ALL)?;
+    println!("Now, line status all is {:#b}", line_status_all);
+    Ok(line_status_all)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_line_delay() -> Result<i64> {
+    let line_delay: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_INT_LINE_DELAY)?;
+    println!("Now, line delay is {}", line_delay);
+    Ok(line_delay)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_line_delay(line_delay: i64) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_INT_LINE_DELAY, &line_delay)?;
+    println!("Now, line delay is {}", line_delay);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_user_output_selector() -> Result<i64> {
+    let user_output_selector: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_USER_OUTPUT_SELECTOR)?;
+    println!("Now, user output selector is {}", user_output_selector);
+    Ok(user_output_selector)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_user_output_selector(
+    user_output_selector: GX_USER_OUTPUT_SELECTOR_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_USER_OUTPUT_SELECTOR,
+        &(user_output_selector as i64),
+    )?;
+    println!("Now, user output selector is {:?}", user_output_selector);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_user_output_value() -> Result<bool> {
+    let user_output_value: bool = gxi_get_feature_value(GX_FEATURE_ID::GX_BOOL_USER_OUTPUT_VALUE)?;
+    println!("Now, user output value is {}", user_output_value);
+    Ok(user_output_value)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_user_output_value(user_output_value: bool) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_BOOL_USER_OUTPUT_VALUE, &user_output_value)?;
+    println!("Now, user output value is {}", user_output_value);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_user_output_mode() -> Result<i64> {
+    let user_output_mode: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_USER_OUTPUT_MODE)?;
+    println!("Now, user output mode is {}", user_output_mode);
+    Ok(user_output_mode)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_user_output_mode(user_output_mode: GX_USER_OUTPUT_MODE_ENTRY) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_USER_OUTPUT_MODE,
+        &(user_output_mode as i64),
+    )?;
+    println!("Now, user output mode is {:?}", user_output_mode);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_strobe_switch() -> Result<i64> {
+    let strobe_switch: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_STROBE_SWITCH)?;
+    println!("Now, strobe switch is {}", strobe_switch);
+    Ok(strobe_switch)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_strobe_switch(strobe_switch: GX_STROBE_SWITCH_ENTRY) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_STROBE_SWITCH,
+        &(strobe_switch as i64),
+    )?;
+    println!("Now, strobe switch is {:?}", strobe_switch);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_pulse_width() -> Result<f64> {
+    let pulse_width: f64 = gxi_get_feature_value(GX_FEATURE_ID::GX_FLOAT_PULSE_WIDTH)?;
+    println!("Now, pulse width is {}", pulse_width);
+    Ok(pulse_width)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_pulse_width(pulse_width: f64) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_FLOAT_PULSE_WIDTH, &pulse_width)?;
+    println!("Now, pulse width is {}", pulse_width);
+    Ok(())
+}
+
+//----------------------------------------------------------
+//---------------Line Helpers-------------------------------
+//----------------------------------------------------------
+
+/// Drive `line` with the strobe signal, for syncing a flash with the exposure.
+///
+/// Older models have no line source and use the strobe switch instead, both are handled.
+/// `inverted` gives an active low strobe.
+#[cfg(feature = "solo")]
+pub fn gxi_configure_strobe_output(line: GX_LINE_SELECTOR_ENTRY, inverted: bool) -> Result<()> {
+    gxi_set_line_selector(line)?;
+    gxi_set_line_mode(GX_LINE_MODE_ENTRY::GX_ENUM_LINE_MODE_OUTPUT)?;
+    if gxi_is_implemented(GX_FEATURE_ID::GX_ENUM_LINE_SOURCE)? {
+        gxi_set_line_source(GX_LINE_SOURCE_ENTRY::GX_ENUM_LINE_SOURCE_STROBE)?;
+    }
+    if gxi_is_implemented(GX_FEATURE_ID::GX_BOOL_LINE_INVERTER)? {
+        gxi_set_line_inverter(inverted)?;
+    }
+    if gxi_is_implemented(GX_FEATURE_ID::GX_ENUM_STROBE_SWITCH)? {
+        gxi_set_strobe_switch(GX_STROBE_SWITCH_ENTRY::GX_STROBE_SWITCH_ON)?;
+    }
+    println!("Now, {:?} outputs the strobe", line);
+    Ok(())
+}
+
+/// Drive `line` from the user output `user_output` and set its level.
+#[cfg(feature = "solo")]
+pub fn gxi_configure_user_output(
+    line: GX_LINE_SELECTOR_ENTRY,
+    user_output: GX_USER_OUTPUT_SELECTOR_ENTRY,
+    value: bool,
+) -> Result<()> {
+    let line_source = match user_output {
+        GX_USER_OUTPUT_SELECTOR_ENTRY::GX_USER_OUTPUT_SELECTOR_OUTPUT0 => {
+            GX_LINE_SOURCE_ENTRY::GX_ENUM_LINE_SOURCE_USEROUTPUT0
+        }
+        GX_USER_OUTPUT_SELECTOR_ENTRY::GX_USER_OUTPUT_SELECTOR_OUTPUT1 => {
+            GX_LINE_SOURCE_ENTRY::GX_ENUM_LINE_SOURCE_USEROUTPUT1
+        }
+        GX_USER_OUTPUT_SELECTOR_ENTRY::GX_USER_OUTPUT_SELECTOR_OUTPUT2 => {
+            GX_LINE_SOURCE_ENTRY::GX_ENUM_LINE_SOURCE_USEROUTPUT2
+        }
+        GX_USER_OUTPUT_SELECTOR_ENTRY::GX_USER_OUTPUT_SELECTOR_OUTPUT3 => {
+            GX_LINE_SOURCE_ENTRY::GX_ENUM_LINE_SOURCE_USEROUTPUT3
+        }
+        GX_USER_OUTPUT_SELECTOR_ENTRY::GX_USER_OUTPUT_SELECTOR_OUTPUT4 => {
+            GX_LINE_SOURCE_ENTRY::GX_ENUM_LINE_SOURCE_USEROUTPUT4
+        }
+        GX_USER_OUTPUT_SELECTOR_ENTRY::GX_USER_OUTPUT_SELECTOR_OUTPUT5 => {
+            GX_LINE_SOURCE_ENTRY::GX_ENUM_LINE_SOURCE_USEROUTPUT5
+        }
+        GX_USER_OUTPUT_SELECTOR_ENTRY::GX_USER_OUTPUT_SELECTOR_OUTPUT6 => {
+            GX_LINE_SOURCE_ENTRY::GX_ENUM_LINE_SOURCE_USEROUTPUT6
+        }
+    };
+
+    gxi_set_line_selector(line)?;
+    gxi_set_line_mode(GX_LINE_MODE_ENTRY::GX_ENUM_LINE_MODE_OUTPUT)?;
+    gxi_set_line_source(line_source)?;
+    gxi_set_user_output_selector(user_output)?;
+    gxi_set_user_output_value(value)
+}
+
+/// Configure `line` as an input and read its level.
+#[cfg(feature = "solo")]
+pub fn gxi_read_input_line(line: GX_LINE_SELECTOR_ENTRY) -> Result<bool> {
+    gxi_set_line_selector(line)?;
+    gxi_set_line_mode(GX_LINE_MODE_ENTRY::GX_ENUM_LINE_MODE_INPUT)?;
+    gxi_get_line_status()
+}
+
+/// Level of `line` in a `gxi_get_line_status_all` bit field.
+pub fn line_status_bit(line_status_all: i64, line: GX_LINE_SELECTOR_ENTRY) -> bool {
+    (line_status_all >> (line as i64)) & 1 == 1
+}
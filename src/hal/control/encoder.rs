@@ -0,0 +1,215 @@
+//! Quadrature encoder inputs, e.g. a conveyor encoder read back for position and whose phase A pulses can pace a line scan camera.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::hal::config::*;
+use crate::hal::control::acquisition::{
+    gxi_set_trigger_mode_off, gxi_set_trigger_mode_on, gxi_set_trigger_selector_frame_start,
+    gxi_set_trigger_source,
+};
+use crate::hal::control::counter_timer::{
+    gxi_reset_counter, gxi_set_counter_duration, gxi_set_counter_event_source,
+    gxi_set_counter_reset_source, gxi_set_counter_selector,
+};
+use crate::raw::gx_enum::{
+    GX_COUNTER_EVENT_SOURCE_ENTRY, GX_COUNTER_RESET_SOURCE_ENTRY, GX_COUNTER_SELECTOR_ENTRY,
+    GX_ENCODER_MODE_ENTRY, GX_ENCODER_SELECTOR_ENTRY, GX_ENCODER_SOURCEA_ENTRY,
+    GX_ENCODER_SOURCEB_ENTRY, GX_FEATURE_ID, GX_TRIGGER_SOURCE_ENTRY,
+};
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_encoder_selector() -> Result<i64> {
+    let encoder_selector: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_ENCODER_SELECTOR)?;
+    println!("Now, encoder selector is {}", encoder_selector);
+    Ok(encoder_selector)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_encoder_selector(encoder_selector: GX_ENCODER_SELECTOR_ENTRY) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_ENCODER_SELECTOR,
+        &(encoder_selector as i64),
+    )?;
+    println!("Now, encoder selector is {:?}", encoder_selector);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_encoder_source_a() -> Result<i64> {
+    let encoder_source_a: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_ENCODER_SOURCEA)?;
+    println!("Now, encoder source a is {}", encoder_source_a);
+    Ok(encoder_source_a)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_encoder_source_a(encoder_source_a: GX_ENCODER_SOURCEA_ENTRY) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_ENCODER_SOURCEA,
+        &(encoder_source_a as i64),
+    )?;
+    println!("Now, encoder source a is {:?}", encoder_source_a);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_encoder_source_b() -> Result<i64> {
+    let encoder_source_b: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_ENCODER_SOURCEB)?;
+    println!("Now, encoder source b is {}", encoder_source_b);
+    Ok(encoder_source_b)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_encoder_source_b(encoder_source_b: GX_ENCODER_SOURCEB_ENTRY) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_ENCODER_SOURCEB,
+        &(encoder_source_b as i64),
+    )?;
+    println!("Now, encoder source b is {:?}", encoder_source_b);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_encoder_mode() -> Result<i64> {
+    let encoder_mode: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_ENCODER_MODE)?;
+    println!("Now, encoder mode is {}", encoder_mode);
+    Ok(encoder_mode)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_encoder_mode(encoder_mode: GX_ENCODER_MODE_ENTRY) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_ENCODER_MODE, &(encoder_mode as i64))?;
+    println!("Now, encoder mode is {:?}", encoder_mode);
+    Ok(())
+}
+
+/// The direction the selected encoder last moved in, 0 forward and 1 backward.
+#[cfg(feature = "solo")]
+pub fn gxi_get_encoder_direction() -> Result<i64> {
+    let encoder_direction: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_ENCODER_DIRECTION)?;
+    println!("Now, encoder direction is {}", encoder_direction);
+    Ok(encoder_direction)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_encoder_value() -> Result<i64> {
+    let encoder_value: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_INT_ENCODER_VALUE)?;
+    println!("Now, encoder value is {}", encoder_value);
+    Ok(encoder_value)
+}
+
+//----------------------------------------------------------
+//---------------Encoder Position---------------------------
+//----------------------------------------------------------
+
+/// Select `encoder` and wire its phase A and B inputs.
+#[cfg(feature = "solo")]
+pub fn gxi_configure_encoder(
+    encoder: GX_ENCODER_SELECTOR_ENTRY,
+    source_a: GX_ENCODER_SOURCEA_ENTRY,
+    source_b: GX_ENCODER_SOURCEB_ENTRY,
+    mode: GX_ENCODER_MODE_ENTRY,
+) -> Result<()> {
+    gxi_set_encoder_selector(encoder)?;
+    gxi_set_encoder_source_a(source_a)?;
+    gxi_set_encoder_source_b(source_b)?;
+    gxi_set_encoder_mode(mode)
+}
+
+/// Position and last direction of an encoder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncoderPosition {
+    pub value: i64,
+    pub forward: bool,
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_encoder_position(encoder: GX_ENCODER_SELECTOR_ENTRY) -> Result<EncoderPosition> {
+    gxi_set_encoder_selector(encoder)?;
+    Ok(EncoderPosition {
+        value: gxi_get_encoder_value()?,
+        forward: gxi_get_encoder_direction()? == 0,
+    })
+}
+
+//----------------------------------------------------------
+//---------------Encoder Pulse Divider Trigger--------------
+//----------------------------------------------------------
+
+// 计数器只能数Line0~3上的事件，Line4/5接的A相没办法用来分频
+fn counter_event_source_for(
+    source_a: GX_ENCODER_SOURCEA_ENTRY,
+) -> Result<GX_COUNTER_EVENT_SOURCE_ENTRY> {
+    match source_a {
+        GX_ENCODER_SOURCEA_ENTRY::GX_ENUM_SOURCEA_LINE0 => {
+            Ok(GX_COUNTER_EVENT_SOURCE_ENTRY::GX_COUNTER_EVENT_SOURCE_LINE0)
+        }
+        GX_ENCODER_SOURCEA_ENTRY::GX_ENUM_SOURCEA_LINE1 => {
+            Ok(GX_COUNTER_EVENT_SOURCE_ENTRY::GX_COUNTER_EVENT_SOURCE_LINE1)
+        }
+        GX_ENCODER_SOURCEA_ENTRY::GX_ENUM_SOURCEA_LINE2 => {
+            Ok(GX_COUNTER_EVENT_SOURCE_ENTRY::GX_COUNTER_EVENT_SOURCE_LINE2)
+        }
+        GX_ENCODER_SOURCEA_ENTRY::GX_ENUM_SOURCEA_LINE3 => {
+            Ok(GX_COUNTER_EVENT_SOURCE_ENTRY::GX_COUNTER_EVENT_SOURCE_LINE3)
+        }
+        _ => Err(Error::new(ErrorKind::InvalidParameter(format!(
+            "encoder ticks on {:?} cannot be counted, phase A must be on Line0 to Line3",
+            source_a
+        )))),
+    }
+}
+
+/// Trigger a frame every `ticks` pulses on the phase A line of `encoder`, a pulse divider
+/// rather than a position trigger.
+///
+/// `encoder` is configured with `gxi_configure_encoder` so its position and direction can be
+/// read back, but the camera has no trigger or counter source that follows the encoder
+/// count, so counter 2 counts the raw phase A pulses, restarts itself when it ends, and its
+/// end is the frame start trigger. The direction is ignored: a conveyor running backwards
+/// still triggers, check `gxi_get_encoder_position(encoder)?.forward` to drop those frames.
+/// Acquisition has to be started afterwards.
+#[cfg(feature = "solo")]
+pub fn gxi_set_encoder_pulse_divider_trigger(
+    encoder: GX_ENCODER_SELECTOR_ENTRY,
+    source_a: GX_ENCODER_SOURCEA_ENTRY,
+    source_b: GX_ENCODER_SOURCEB_ENTRY,
+    ticks: i64,
+) -> Result<()> {
+    if ticks < 1 {
+        return Err(Error::new(ErrorKind::InvalidParameter(format!(
+            "an encoder trigger needs at least 1 tick, got {}",
+            ticks
+        ))));
+    }
+    let counter_event_source = counter_event_source_for(source_a)?;
+    gxi_configure_encoder(
+        encoder,
+        source_a,
+        source_b,
+        GX_ENCODER_MODE_ENTRY::GX_ENUM_HIGH_RESOLUTION,
+    )?;
+
+    gxi_set_counter_selector(GX_COUNTER_SELECTOR_ENTRY::GX_COUNTER_SELECTOR_COUNTER2)?;
+    gxi_set_counter_event_source(counter_event_source)?;
+    gxi_set_counter_duration(ticks)?;
+    gxi_set_counter_reset_source(
+        GX_COUNTER_RESET_SOURCE_ENTRY::GX_COUNTER_RESET_SOURCE_COUNTER2END,
+    )?;
+    gxi_reset_counter()?;
+
+    gxi_set_trigger_selector_frame_start()?;
+    gxi_set_trigger_mode_on()?;
+    gxi_set_trigger_source(GX_TRIGGER_SOURCE_ENTRY::GX_TRIGGER_SOURCE_COUNTER2END as i64)?;
+    println!(
+        "Successfully set trigger every {} phase A pulses of {:?} on {:?}",
+        ticks, encoder, source_a
+    );
+    Ok(())
+}
+
+/// Switch the trigger off and stop counter 2 counting phase A pulses.
+#[cfg(feature = "solo")]
+pub fn gxi_clear_encoder_pulse_divider_trigger() -> Result<()> {
+    gxi_set_trigger_mode_off()?;
+    gxi_set_counter_selector(GX_COUNTER_SELECTOR_ENTRY::GX_COUNTER_SELECTOR_COUNTER2)?;
+    gxi_set_counter_event_source(GX_COUNTER_EVENT_SOURCE_ENTRY::GX_COUNTER_EVENT_SOURCE_OFF)
+}
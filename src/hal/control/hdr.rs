@@ -0,0 +1,293 @@
+//! HDR and multi-gray control (MGC), where the camera cycles through a sequence of exposure and gain pairs.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::hal::config::*;
+use crate::hal::device::GxiFrame;
+use crate::raw::gx_enum::{GX_FEATURE_ID, GX_HDR_MODE_ENTRY, GX_MGC_CONTROL_MODE_ENTRY};
+#[cfg(feature = "solo")]
+use crate::raw::gx_struct::GX_FLOAT_RANGE;
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_hdr_mode() -> Result<i64> {
+    let hdr_mode: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_HDR_MODE)?;
+    println!("Now, hdr mode is {}", hdr_mode);
+    Ok(hdr_mode)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_hdr_mode(hdr_mode: GX_HDR_MODE_ENTRY) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_HDR_MODE, &(hdr_mode as i64))?;
+    println!("Now, hdr mode is {:?}", hdr_mode);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_hdr_target_long_value() -> Result<i64> {
+    let hdr_target_long_value: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_INT_HDR_TARGET_LONG_VALUE)?;
+    println!("Now, hdr target long value is {}", hdr_target_long_value);
+    Ok(hdr_target_long_value)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_hdr_target_long_value(hdr_target_long_value: i64) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_INT_HDR_TARGET_LONG_VALUE,
+        &hdr_target_long_value,
+    )?;
+    println!("Now, hdr target long value is {}", hdr_target_long_value);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_hdr_target_short_value() -> Result<i64> {
+    let hdr_target_short_value: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_INT_HDR_TARGET_SHORT_VALUE)?;
+    println!("Now, hdr target short value is {}", hdr_target_short_value);
+    Ok(hdr_target_short_value)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_hdr_target_short_value(hdr_target_short_value: i64) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_INT_HDR_TARGET_SHORT_VALUE,
+        &hdr_target_short_value,
+    )?;
+    println!("Now, hdr target short value is {}", hdr_target_short_value);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_hdr_target_main_value() -> Result<i64> {
+    let hdr_target_main_value: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_INT_HDR_TARGET_MAIN_VALUE)?;
+    println!("Now, hdr target main value is {}", hdr_target_main_value);
+    Ok(hdr_target_main_value)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_hdr_target_main_value(hdr_target_main_value: i64) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_INT_HDR_TARGET_MAIN_VALUE,
+        &hdr_target_main_value,
+    )?;
+    println!("Now, hdr target main value is {}", hdr_target_main_value);
+    Ok(())
+}
+
+/// Set the long, short and main gray targets, then switch the HDR on.
+#[cfg(feature = "solo")]
+pub fn gxi_enable_hdr(long_value: i64, short_value: i64, main_value: i64) -> Result<()> {
+    gxi_set_hdr_target_long_value(long_value)?;
+    gxi_set_hdr_target_short_value(short_value)?;
+    gxi_set_hdr_target_main_value(main_value)?;
+    gxi_set_hdr_mode(GX_HDR_MODE_ENTRY::GX_HDR_MODE_CONTINUOUS)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_mgc_mode() -> Result<i64> {
+    let mgc_mode: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_MGC_MODE)?;
+    println!("Now, mgc mode is {}", mgc_mode);
+    Ok(mgc_mode)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_mgc_mode(mgc_mode: GX_MGC_CONTROL_MODE_ENTRY) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_MGC_MODE, &(mgc_mode as i64))?;
+    println!("Now, mgc mode is {:?}", mgc_mode);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_mgc_selector() -> Result<i64> {
+    let mgc_selector: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_INT_MGC_SELECTOR)?;
+    println!("Now, mgc selector is {}", mgc_selector);
+    Ok(mgc_selector)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_mgc_selector(mgc_selector: i64) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_INT_MGC_SELECTOR, &mgc_selector)?;
+    println!("Now, mgc selector is {}", mgc_selector);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_mgc_exposure_time() -> Result<f64> {
+    let mgc_exposure_time: f64 = gxi_get_feature_value(GX_FEATURE_ID::GX_FLOAT_MGC_EXPOSURE_TIME)?;
+    println!("Now, mgc exposure time is {}", mgc_exposure_time);
+    Ok(mgc_exposure_time)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_mgc_exposure_time(mgc_exposure_time: f64) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_FLOAT_MGC_EXPOSURE_TIME,
+        &mgc_exposure_time,
+    )?;
+    println!("Now, mgc exposure time is {}", mgc_exposure_time);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_mgc_gain() -> Result<f64> {
+    let mgc_gain: f64 = gxi_get_feature_value(GX_FEATURE_ID::GX_FLOAT_MGC_GAIN)?;
+    println!("Now, mgc gain is {}", mgc_gain);
+    Ok(mgc_gain)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_mgc_gain(mgc_gain: f64) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_FLOAT_MGC_GAIN, &mgc_gain)?;
+    println!("Now, mgc gain is {}", mgc_gain);
+    Ok(())
+}
+
+//----------------------------------------------------------
+//---------------Multi Gray Sequence------------------------
+//----------------------------------------------------------
+
+/// The MGC mode that cycles through `len` frames, the device only supports 2 or 4.
+pub fn mgc_mode_for_len(len: usize) -> Result<GX_MGC_CONTROL_MODE_ENTRY> {
+    match len {
+        2 => Ok(GX_MGC_CONTROL_MODE_ENTRY::GX_MGC_MODE_TWO_FRAME),
+        4 => Ok(GX_MGC_CONTROL_MODE_ENTRY::GX_MGC_MODE_FOUR_FRAME),
+        _ => Err(Error::new(ErrorKind::InvalidParameter(format!(
+            "a multi gray sequence has 2 or 4 steps, got {}",
+            len
+        )))),
+    }
+}
+
+// MGC选择器不一定从0开始，以设备给出的最小值作为第0步
+#[cfg(feature = "solo")]
+fn gxi_get_mgc_selector_base() -> Result<i64> {
+    Ok(gxi_get_int_range(GX_FEATURE_ID::GX_INT_MGC_SELECTOR)?.nMin)
+}
+
+#[cfg(feature = "solo")]
+fn check_in_range(feature_id: GX_FEATURE_ID, value: f64, range: &GX_FLOAT_RANGE) -> Result<()> {
+    if !(range.dMin..=range.dMax).contains(&value) {
+        return Err(Error::new(ErrorKind::InvalidParameter(format!(
+            "{:?} {} is out of range [{}, {}]",
+            feature_id, value, range.dMin, range.dMax
+        ))));
+    }
+    Ok(())
+}
+
+/// Program the multi gray sequence as `(exposure_time, gain)` steps and switch it on.
+///
+/// Exposure is in us and gain in dB. All steps are checked against the device ranges before
+/// anything is written, an out of range step fails with `InvalidParameter` and leaves the
+/// device as it was. When the device refuses a write halfway, the multi gray control is
+/// switched off again.
+#[cfg(feature = "solo")]
+pub fn gxi_set_mgc_sequence(steps: &[(f64, f64)]) -> Result<()> {
+    let mgc_mode = mgc_mode_for_len(steps.len())?;
+    let exposure_range = gxi_get_float_range(GX_FEATURE_ID::GX_FLOAT_MGC_EXPOSURE_TIME)?;
+    let gain_range = gxi_get_float_range(GX_FEATURE_ID::GX_FLOAT_MGC_GAIN)?;
+    for (exposure_time, gain) in steps {
+        check_in_range(
+            GX_FEATURE_ID::GX_FLOAT_MGC_EXPOSURE_TIME,
+            *exposure_time,
+            &exposure_range,
+        )?;
+        check_in_range(GX_FEATURE_ID::GX_FLOAT_MGC_GAIN, *gain, &gain_range)?;
+    }
+    let selector_base = gxi_get_mgc_selector_base()?;
+
+    gxi_set_mgc_mode(mgc_mode)?;
+    let written = steps
+        .iter()
+        .enumerate()
+        .try_for_each(|(index, (exposure_time, gain))| {
+            gxi_set_mgc_selector(selector_base + index as i64)?;
+            gxi_set_mgc_exposure_time(*exposure_time)?;
+            gxi_set_mgc_gain(*gain)
+        });
+    if let Err(e) = written {
+        // 写到一半失败时不能让相机按半套参数循环曝光
+        if let Err(disable_error) = gxi_disable_mgc() {
+            println!("Failed to switch off multi gray control: {}", disable_error);
+        }
+        return Err(e);
+    }
+    println!("Now, multi gray sequence is {:?}", steps);
+    Ok(())
+}
+
+/// Read back the `(exposure_time, gain)` steps, empty when the multi gray control is off.
+#[cfg(feature = "solo")]
+pub fn gxi_get_mgc_sequence() -> Result<Vec<(f64, f64)>> {
+    let len = match gxi_get_mgc_mode()? {
+        1 => 2,
+        2 => 4,
+        _ => return Ok(Vec::new()),
+    };
+
+    let selector_base = gxi_get_mgc_selector_base()?;
+    let mut steps = Vec::with_capacity(len);
+    for index in 0..len {
+        gxi_set_mgc_selector(selector_base + index as i64)?;
+        steps.push((gxi_get_mgc_exposure_time()?, gxi_get_mgc_gain()?));
+    }
+    Ok(steps)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_disable_mgc() -> Result<()> {
+    gxi_set_mgc_mode(GX_MGC_CONTROL_MODE_ENTRY::GX_MGC_MODE_OFF)
+}
+
+/// A frame with the multi gray step it was exposed with.
+#[derive(Debug, Clone)]
+pub struct MultiGrayFrame {
+    pub sequence_index: usize,
+    pub exposure_time: f64,
+    pub gain: f64,
+    pub frame: GxiFrame,
+}
+
+/// Tags frames with their multi gray step.
+///
+/// The camera steps through the sequence once per frame, so the step follows from the frame
+/// ID. The first frame tagged is taken as step 0 unless an origin is given with `with_origin`;
+/// frames lost on the way do not shift the steps of the following ones.
+#[derive(Debug, Clone)]
+pub struct MultiGrayTagger {
+    steps: Vec<(f64, f64)>,
+    origin_frame_id: Option<u64>,
+}
+
+impl MultiGrayTagger {
+    pub fn new(steps: Vec<(f64, f64)>) -> Result<Self> {
+        mgc_mode_for_len(steps.len())?;
+        Ok(Self {
+            steps,
+            origin_frame_id: None,
+        })
+    }
+
+    /// Use `frame_id` as the frame exposed with step 0.
+    pub fn with_origin(mut self, frame_id: u64) -> Self {
+        self.origin_frame_id = Some(frame_id);
+        self
+    }
+
+    pub fn sequence_index(&mut self, frame_id: u64) -> usize {
+        let origin_frame_id = *self.origin_frame_id.get_or_insert(frame_id);
+        (frame_id.wrapping_sub(origin_frame_id) % self.steps.len() as u64) as usize
+    }
+
+    pub fn tag(&mut self, frame: GxiFrame) -> MultiGrayFrame {
+        let sequence_index = self.sequence_index(frame.frame_id);
+        let (exposure_time, gain) = self.steps[sequence_index];
+        MultiGrayFrame {
+            sequence_index,
+            exposure_time,
+            gain,
+            frame,
+        }
+    }
+}
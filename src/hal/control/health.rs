@@ -0,0 +1,408 @@
+//! Device health monitoring: temperatures, link throughput and data stream errors sampled at an interval, with threshold alerts and a history ring buffer.
+
+use crate::error::Result;
+use crate::hal::config::*;
+use crate::hal::control::data_stream::{StreamStatsDelta, StreamStatsSampler};
+use crate::hal::control::device::gxi_get_device_temperature_of;
+use crate::raw::gx_enum::{GX_DEVICE_TEMPERATURE_SELECTOR_ENTRY, GX_FEATURE_ID};
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{JoinHandle, sleep, spawn};
+use std::time::{Duration, SystemTime};
+
+/// One health reading. Values the device does not provide are `None`, `stream` is `None`
+/// for the first reading since the counters need a baseline.
+#[derive(Debug, Clone)]
+pub struct HealthSample {
+    pub captured_at: SystemTime,
+    /// Celsius.
+    pub sensor_temperature: Option<f64>,
+    /// Celsius.
+    pub mainboard_temperature: Option<f64>,
+    /// Bytes per second.
+    pub link_throughput: Option<i64>,
+    pub stream: Option<StreamStatsDelta>,
+}
+
+/// Limits a `HealthSample` is checked against, `None` limits are not checked.
+#[derive(Debug, Clone, Default)]
+pub struct HealthThresholds {
+    pub max_sensor_temperature: Option<f64>,
+    pub max_mainboard_temperature: Option<f64>,
+    /// Bytes per second.
+    pub min_link_throughput: Option<i64>,
+    /// From 0.0 to 1.0.
+    pub max_lost_frame_ratio: Option<f64>,
+    /// Incomplete frames per sample period.
+    pub max_incomplete_frames: Option<i64>,
+    /// Resent packets per sample period.
+    pub max_resend_packets: Option<i64>,
+}
+
+/// A threshold a `HealthSample` crossed, with the value that crossed it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HealthAlert {
+    SensorTemperature(f64),
+    MainboardTemperature(f64),
+    LinkThroughput(i64),
+    LostFrameRatio(f64),
+    IncompleteFrames(i64),
+    ResendPackets(i64),
+}
+
+impl HealthThresholds {
+    pub fn check(&self, sample: &HealthSample) -> Vec<HealthAlert> {
+        let mut alerts = Vec::new();
+        let above = |value: Option<f64>, limit: Option<f64>| match (value, limit) {
+            (Some(value), Some(limit)) if value > limit => Some(value),
+            _ => None,
+        };
+
+        if let Some(value) = above(sample.sensor_temperature, self.max_sensor_temperature) {
+            alerts.push(HealthAlert::SensorTemperature(value));
+        }
+        if let Some(value) = above(sample.mainboard_temperature, self.max_mainboard_temperature) {
+            alerts.push(HealthAlert::MainboardTemperature(value));
+        }
+        if let (Some(value), Some(limit)) = (sample.link_throughput, self.min_link_throughput) {
+            if value < limit {
+                alerts.push(HealthAlert::LinkThroughput(value));
+            }
+        }
+        if let Some(stream) = &sample.stream {
+            if let Some(value) = above(Some(stream.lost_frame_ratio()), self.max_lost_frame_ratio) {
+                alerts.push(HealthAlert::LostFrameRatio(value));
+            }
+            if let Some(limit) = self.max_incomplete_frames {
+                if stream.incomplete_frames > limit {
+                    alerts.push(HealthAlert::IncompleteFrames(stream.incomplete_frames));
+                }
+            }
+            if let (Some(value), Some(limit)) = (stream.resend_packets, self.max_resend_packets) {
+                if value > limit {
+                    alerts.push(HealthAlert::ResendPackets(value));
+                }
+            }
+        }
+        alerts
+    }
+}
+
+/// The latest `capacity` health samples, oldest first.
+#[derive(Debug, Clone)]
+pub struct HealthHistory {
+    capacity: usize,
+    samples: VecDeque<HealthSample>,
+}
+
+impl HealthHistory {
+    pub fn new(capacity: usize) -> Self {
+        HealthHistory {
+            capacity: capacity.max(1),
+            samples: VecDeque::with_capacity(capacity.max(1)),
+        }
+    }
+
+    pub fn push(&mut self, sample: HealthSample) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn latest(&self) -> Option<&HealthSample> {
+        self.samples.back()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &HealthSample> {
+        self.samples.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+}
+
+/// Readings the monitor thread could not take. The thread keeps sampling after a failure,
+/// so a device that recovers shows up again in the history.
+#[derive(Debug, Clone, Default)]
+pub struct HealthFailures {
+    pub count: u64,
+    /// The latest failure, as text since `Error` cannot be cloned.
+    pub last_error: Option<String>,
+    pub last_failed_at: Option<SystemTime>,
+}
+
+#[cfg(feature = "solo")]
+fn gxi_get_optional_link_throughput() -> Result<Option<i64>> {
+    if gxi_is_implemented(GX_FEATURE_ID::GX_INT_DEVICE_LINK_CURRENT_THROUGHPUT)? {
+        Ok(Some(gxi_get_feature_value(
+            GX_FEATURE_ID::GX_INT_DEVICE_LINK_CURRENT_THROUGHPUT,
+        )?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Samples the device health every `interval`, checks it against the thresholds and keeps
+/// the readings in a shared `HealthHistory`.
+pub struct HealthMonitor {
+    pub interval: Duration,
+    pub thresholds: HealthThresholds,
+    stream_sampler: StreamStatsSampler,
+    history: Arc<Mutex<HealthHistory>>,
+}
+
+impl HealthMonitor {
+    pub fn new(interval: Duration, thresholds: HealthThresholds, history_capacity: usize) -> Self {
+        Self {
+            interval,
+            thresholds,
+            stream_sampler: StreamStatsSampler::new(interval),
+            history: Arc::new(Mutex::new(HealthHistory::new(history_capacity))),
+        }
+    }
+
+    /// The history, shared with the monitor thread once spawned.
+    pub fn history(&self) -> Arc<Mutex<HealthHistory>> {
+        self.history.clone()
+    }
+
+    /// Take one reading now, add it to the history and return it with its alerts.
+    #[cfg(feature = "solo")]
+    pub fn sample(&mut self) -> Result<(HealthSample, Vec<HealthAlert>)> {
+        let sample = HealthSample {
+            captured_at: SystemTime::now(),
+            sensor_temperature: gxi_get_device_temperature_of(
+                GX_DEVICE_TEMPERATURE_SELECTOR_ENTRY::GX_DEVICE_TEMPERATURE_SELECTOR_SENSOR,
+            )?,
+            mainboard_temperature: gxi_get_device_temperature_of(
+                GX_DEVICE_TEMPERATURE_SELECTOR_ENTRY::GX_DEVICE_TEMPERATURE_SELECTOR_MAINBOARD,
+            )?,
+            link_throughput: gxi_get_optional_link_throughput()?,
+            stream: self.stream_sampler.sample()?,
+        };
+        let alerts = self.thresholds.check(&sample);
+        self.history
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(sample.clone());
+        Ok((sample, alerts))
+    }
+
+    /// Sample every `interval` on a background thread and hand the alerts of each reading
+    /// to `on_alert`, readings without alerts only go to the history. Failed readings are
+    /// counted in `HealthMonitorHandle::failures` and the thread carries on.
+    #[cfg(feature = "solo")]
+    pub fn spawn<F>(mut self, mut on_alert: F) -> HealthMonitorHandle
+    where
+        F: FnMut(&HealthSample, &[HealthAlert]) + Send + 'static,
+    {
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = running.clone();
+        let history = self.history.clone();
+        let failures = Arc::new(Mutex::new(HealthFailures::default()));
+        let thread_failures = failures.clone();
+        let handle = spawn(move || {
+            let mut failing = false;
+            while thread_running.load(Ordering::Relaxed) {
+                match self.sample() {
+                    Ok((sample, alerts)) => {
+                        if failing {
+                            println!("Device health sampling recovered");
+                            failing = false;
+                        }
+                        if !alerts.is_empty() {
+                            on_alert(&sample, &alerts);
+                        }
+                    }
+                    Err(e) => {
+                        // 只在开始失败时打印一次，之后的失败只计数，不每个周期刷屏
+                        if !failing {
+                            println!("Failed to sample device health: {:?}", e);
+                            failing = true;
+                        }
+                        let mut failures = thread_failures
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner);
+                        failures.count += 1;
+                        failures.last_error = Some(e.to_string());
+                        failures.last_failed_at = Some(SystemTime::now());
+                    }
+                }
+                sleep(self.interval);
+            }
+        });
+
+        HealthMonitorHandle {
+            running,
+            history,
+            failures,
+            handle: Some(handle),
+        }
+    }
+}
+
+/// Handle of a running `HealthMonitor` thread, stop it with `stop()` or by dropping it.
+pub struct HealthMonitorHandle {
+    running: Arc<AtomicBool>,
+    history: Arc<Mutex<HealthHistory>>,
+    failures: Arc<Mutex<HealthFailures>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl HealthMonitorHandle {
+    /// A copy of the history so far, for display.
+    pub fn history(&self) -> HealthHistory {
+        self.history
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// A copy of the failure count and latest failure so far.
+    pub fn failures(&self) -> HealthFailures {
+        self.failures
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for HealthMonitorHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> HealthSample {
+        HealthSample {
+            captured_at: SystemTime::UNIX_EPOCH,
+            sensor_temperature: Some(50.0),
+            mainboard_temperature: Some(60.0),
+            link_throughput: Some(100_000_000),
+            stream: Some(StreamStatsDelta {
+                elapsed: Duration::from_secs(1),
+                delivered_frames: 90,
+                lost_frames: 10,
+                incomplete_frames: 3,
+                resend_packets: Some(20),
+                missing_block_ids: None,
+                frame_rate: 90.0,
+                lost_frame_rate: 10.0,
+                incomplete_frame_rate: 3.0,
+                resend_packet_rate: Some(20.0),
+            }),
+        }
+    }
+
+    fn thresholds() -> HealthThresholds {
+        HealthThresholds {
+            max_sensor_temperature: Some(50.0),
+            max_mainboard_temperature: Some(60.0),
+            min_link_throughput: Some(100_000_000),
+            max_lost_frame_ratio: Some(0.1),
+            max_incomplete_frames: Some(3),
+            max_resend_packets: Some(20),
+        }
+    }
+
+    #[test]
+    fn values_at_the_limits_do_not_alert() {
+        assert!(thresholds().check(&sample()).is_empty());
+        assert!(HealthThresholds::default().check(&sample()).is_empty());
+    }
+
+    #[test]
+    fn every_crossed_limit_alerts_with_its_value() {
+        let mut sample = sample();
+        sample.sensor_temperature = Some(50.5);
+        sample.mainboard_temperature = Some(61.0);
+        sample.link_throughput = Some(99_999_999);
+        let stream = sample.stream.as_mut().unwrap();
+        stream.lost_frames = 30;
+        stream.incomplete_frames = 4;
+        stream.resend_packets = Some(21);
+
+        assert_eq!(
+            thresholds().check(&sample),
+            vec![
+                HealthAlert::SensorTemperature(50.5),
+                HealthAlert::MainboardTemperature(61.0),
+                HealthAlert::LinkThroughput(99_999_999),
+                HealthAlert::LostFrameRatio(0.25),
+                HealthAlert::IncompleteFrames(4),
+                HealthAlert::ResendPackets(21),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_values_are_not_checked() {
+        let sample = HealthSample {
+            sensor_temperature: None,
+            mainboard_temperature: None,
+            link_throughput: None,
+            stream: None,
+            ..sample()
+        };
+        let strict = HealthThresholds {
+            max_sensor_temperature: Some(0.0),
+            max_mainboard_temperature: Some(0.0),
+            min_link_throughput: Some(i64::MAX),
+            max_lost_frame_ratio: Some(0.0),
+            max_incomplete_frames: Some(0),
+            max_resend_packets: Some(0),
+        };
+        assert!(strict.check(&sample).is_empty());
+    }
+
+    #[test]
+    fn history_keeps_the_latest_samples() {
+        let mut history = HealthHistory::new(3);
+        assert!(history.is_empty() && history.latest().is_none());
+        for link_throughput in 1..=5 {
+            history.push(HealthSample {
+                link_throughput: Some(link_throughput),
+                ..sample()
+            });
+        }
+        assert_eq!(history.len(), 3);
+        let kept: Vec<Option<i64>> = history.iter().map(|s| s.link_throughput).collect();
+        assert_eq!(kept, vec![Some(3), Some(4), Some(5)]);
+        assert_eq!(history.latest().unwrap().link_throughput, Some(5));
+    }
+
+    #[test]
+    fn history_keeps_at_least_one_sample() {
+        let mut history = HealthHistory::new(0);
+        history.push(sample());
+        history.push(HealthSample {
+            link_throughput: Some(1),
+            ..sample()
+        });
+        assert_eq!(history.len(), 1);
+        assert_eq!(history.latest().unwrap().link_throughput, Some(1));
+    }
+}
@@ -1,8 +1,13 @@
 //! Placeholder
 
-use crate::error::Result;
+use crate::error::{Error, ErrorKind, Result};
 use crate::hal::config::*;
-use crate::raw::gx_enum::GX_FEATURE_ID;
+use crate::raw::gx_enum::{
+    GX_BINNING_HORIZONTAL_MODE_ENTRY, GX_BINNING_VERTICAL_MODE_ENTRY, GX_FEATURE_ID,
+    GX_REGION_MODE_ENTRY, GX_REGION_SELECTOR_ENTRY, GX_REGION_SEND_MODE_ENTRY,
+    GX_SENSOR_SHUTTER_MODE_ENTRY,
+};
+use crate::raw::gx_struct::GX_INT_RANGE;
 
 #[cfg(feature = "solo")]
 pub fn gxi_get_sensor_width() -> Result<i64> {
@@ -109,6 +114,37 @@ pub fn gxi_set_region_selector_region0() -> Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "solo")]
+pub fn gxi_get_region_mode() -> Result<i64> {
+    let region_mode: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_REGION_MODE)?;
+    println!("Now, region mode is {}", region_mode);
+    Ok(region_mode)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_region_mode(region_mode: GX_REGION_MODE_ENTRY) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_REGION_MODE, &(region_mode as i64))?;
+    println!("Now, region mode is {:?}", region_mode);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_region_send_mode() -> Result<i64> {
+    let region_send_mode: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_REGION_SEND_MODE)?;
+    println!("Now, region send mode is {}", region_send_mode);
+    Ok(region_send_mode)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_region_send_mode(region_send_mode: GX_REGION_SEND_MODE_ENTRY) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_REGION_SEND_MODE,
+        &(region_send_mode as i64),
+    )?;
+    println!("Now, region send mode is {:?}", region_send_mode);
+    Ok(())
+}
+
 #[cfg(feature = "solo")]
 pub fn gxi_get_pixel_format() -> Result<i64> {
     let pixel_format: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_PIXEL_FORMAT)?;
@@ -144,6 +180,13 @@ pub fn gxi_get_pixel_size() -> Result<i64> {
     Ok(pixel_size)
 }
 
+#[cfg(feature = "solo")]
+pub fn gxi_get_sensor_bit_depth() -> Result<i64> {
+    let sensor_bit_depth: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_SENSOR_BIT_DEPTH)?;
+    println!("Now, sensor bit depth is {}", sensor_bit_depth);
+    Ok(sensor_bit_depth)
+}
+
 #[cfg(feature = "solo")]
 pub fn gxi_get_pixel_color_filter() -> Result<i64> {
     let pixel_color_filter: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_PIXEL_COLOR_FILTER)?;
@@ -205,3 +248,674 @@ pub fn gxi_set_test_pattern_off() -> Result<()> {
     println!("Now, test pattern is 0");
     Ok(())
 }
+
+//----------------------------------------------------------
+//---------------Binning and Decimation---------------------
+//----------------------------------------------------------
+
+// 合并和抽样都会改变宽高和偏移的取值范围，所以这里的设置函数都会顺带重新适配ROI，见gxi_refit_roi
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_binning_horizontal() -> Result<i64> {
+    let binning_horizontal: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_INT_BINNING_HORIZONTAL)?;
+    println!("Now, binning horizontal is {}", binning_horizontal);
+    Ok(binning_horizontal)
+}
+
+/// Set the horizontal binning and refit the ROI to cover the same sensor area.
+#[cfg(feature = "solo")]
+pub fn gxi_set_binning_horizontal(binning_horizontal: i64) -> Result<()> {
+    gxi_refit_roi(|| {
+        gxi_set_feature_value(
+            GX_FEATURE_ID::GX_INT_BINNING_HORIZONTAL,
+            &binning_horizontal,
+        )
+    })?;
+    println!("Now, binning horizontal is {}", binning_horizontal);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_binning_vertical() -> Result<i64> {
+    let binning_vertical: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_INT_BINNING_VERTICAL)?;
+    println!("Now, binning vertical is {}", binning_vertical);
+    Ok(binning_vertical)
+}
+
+/// Set the vertical binning and refit the ROI to cover the same sensor area.
+#[cfg(feature = "solo")]
+pub fn gxi_set_binning_vertical(binning_vertical: i64) -> Result<()> {
+    gxi_refit_roi(|| {
+        gxi_set_feature_value(GX_FEATURE_ID::GX_INT_BINNING_VERTICAL, &binning_vertical)
+    })?;
+    println!("Now, binning vertical is {}", binning_vertical);
+    Ok(())
+}
+
+/// Set both binning factors with one ROI refit.
+#[cfg(feature = "solo")]
+pub fn gxi_set_binning(horizontal: i64, vertical: i64) -> Result<RoiRanges> {
+    gxi_refit_roi(|| {
+        gxi_set_feature_value(GX_FEATURE_ID::GX_INT_BINNING_HORIZONTAL, &horizontal)?;
+        gxi_set_feature_value(GX_FEATURE_ID::GX_INT_BINNING_VERTICAL, &vertical)
+    })?;
+    println!("Now, binning is {}x{}", horizontal, vertical);
+    gxi_get_roi_ranges()
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_binning_horizontal_mode() -> Result<i64> {
+    let binning_horizontal_mode: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_BINNING_HORIZONTAL_MODE)?;
+    println!(
+        "Now, binning horizontal mode is {}",
+        binning_horizontal_mode
+    );
+    Ok(binning_horizontal_mode)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_binning_horizontal_mode(
+    binning_horizontal_mode: GX_BINNING_HORIZONTAL_MODE_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_BINNING_HORIZONTAL_MODE,
+        &(binning_horizontal_mode as i64),
+    )?;
+    println!(
+        "Now, binning horizontal mode is {:?}",
+        binning_horizontal_mode
+    );
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_binning_vertical_mode() -> Result<i64> {
+    let binning_vertical_mode: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_BINNING_VERTICAL_MODE)?;
+    println!("Now, binning vertical mode is {}", binning_vertical_mode);
+    Ok(binning_vertical_mode)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_binning_vertical_mode(
+    binning_vertical_mode: GX_BINNING_VERTICAL_MODE_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_BINNING_VERTICAL_MODE,
+        &(binning_vertical_mode as i64),
+    )?;
+    println!("Now, binning vertical mode is {:?}", binning_vertical_mode);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_decimation_horizontal() -> Result<i64> {
+    let decimation_horizontal: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_INT_DECIMATION_HORIZONTAL)?;
+    println!("Now, decimation horizontal is {}", decimation_horizontal);
+    Ok(decimation_horizontal)
+}
+
+/// Set the horizontal decimation and refit the ROI to cover the same sensor area.
+#[cfg(feature = "solo")]
+pub fn gxi_set_decimation_horizontal(decimation_horizontal: i64) -> Result<()> {
+    gxi_refit_roi(|| {
+        gxi_set_feature_value(
+            GX_FEATURE_ID::GX_INT_DECIMATION_HORIZONTAL,
+            &decimation_horizontal,
+        )
+    })?;
+    println!("Now, decimation horizontal is {}", decimation_horizontal);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_decimation_vertical() -> Result<i64> {
+    let decimation_vertical: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_INT_DECIMATION_VERTICAL)?;
+    println!("Now, decimation vertical is {}", decimation_vertical);
+    Ok(decimation_vertical)
+}
+
+/// Set the vertical decimation and refit the ROI to cover the same sensor area.
+#[cfg(feature = "solo")]
+pub fn gxi_set_decimation_vertical(decimation_vertical: i64) -> Result<()> {
+    gxi_refit_roi(|| {
+        gxi_set_feature_value(
+            GX_FEATURE_ID::GX_INT_DECIMATION_VERTICAL,
+            &decimation_vertical,
+        )
+    })?;
+    println!("Now, decimation vertical is {}", decimation_vertical);
+    Ok(())
+}
+
+/// Set both decimation factors with one ROI refit.
+#[cfg(feature = "solo")]
+pub fn gxi_set_decimation(horizontal: i64, vertical: i64) -> Result<RoiRanges> {
+    gxi_refit_roi(|| {
+        gxi_set_feature_value(GX_FEATURE_ID::GX_INT_DECIMATION_HORIZONTAL, &horizontal)?;
+        gxi_set_feature_value(GX_FEATURE_ID::GX_INT_DECIMATION_VERTICAL, &vertical)
+    })?;
+    println!("Now, decimation is {}x{}", horizontal, vertical);
+    gxi_get_roi_ranges()
+}
+
+//----------------------------------------------------------
+//---------------Reverse and Shutter------------------------
+//----------------------------------------------------------
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_reverse_x() -> Result<bool> {
+    let reverse_x: bool = gxi_get_feature_value(GX_FEATURE_ID::GX_BOOL_REVERSE_X)?;
+    println!("Now, reverse x is {}", reverse_x);
+    Ok(reverse_x)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_reverse_x(reverse_x: bool) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_BOOL_REVERSE_X, &reverse_x)?;
+    println!("Now, reverse x is {}", reverse_x);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_reverse_y() -> Result<bool> {
+    let reverse_y: bool = gxi_get_feature_value(GX_FEATURE_ID::GX_BOOL_REVERSE_Y)?;
+    println!("Now, reverse y is {}", reverse_y);
+    Ok(reverse_y)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_reverse_y(reverse_y: bool) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_BOOL_REVERSE_Y, &reverse_y)?;
+    println!("Now, reverse y is {}", reverse_y);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_sensor_shutter_mode() -> Result<i64> {
+    let sensor_shutter_mode: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_SENSOR_SHUTTER_MODE)?;
+    println!("Now, sensor shutter mode is {}", sensor_shutter_mode);
+    Ok(sensor_shutter_mode)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_sensor_shutter_mode(
+    sensor_shutter_mode: GX_SENSOR_SHUTTER_MODE_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_SENSOR_SHUTTER_MODE,
+        &(sensor_shutter_mode as i64),
+    )?;
+    println!("Now, sensor shutter mode is {:?}", sensor_shutter_mode);
+    Ok(())
+}
+
+//----------------------------------------------------------
+//---------------ROI Ranges---------------------------------
+//----------------------------------------------------------
+
+/// Valid values of an integer feature: `min` to `max` in steps of `inc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntRange {
+    pub min: i64,
+    pub max: i64,
+    pub inc: i64,
+}
+
+impl From<GX_INT_RANGE> for IntRange {
+    fn from(range: GX_INT_RANGE) -> Self {
+        IntRange {
+            min: range.nMin,
+            max: range.nMax,
+            inc: range.nInc.max(1),
+        }
+    }
+}
+
+// 字段是pub的，调用方可能自己填出inc为0的范围，按步长1处理，免得除零
+impl IntRange {
+    pub fn contains(&self, value: i64) -> bool {
+        value >= self.min && value <= self.max && (value - self.min) % self.inc.max(1) == 0
+    }
+
+    /// The nearest valid value not above `value`, clamped into the range.
+    pub fn snap(&self, value: i64) -> i64 {
+        let inc = self.inc.max(1);
+        let value = value.clamp(self.min, self.max.max(self.min));
+        self.min + (value - self.min) / inc * inc
+    }
+}
+
+/// Current ranges of the ROI features. The width and height maxima shrink as the offsets
+/// grow and the other way round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoiRanges {
+    pub width: IntRange,
+    pub height: IntRange,
+    pub offset_x: IntRange,
+    pub offset_y: IntRange,
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_roi_ranges() -> Result<RoiRanges> {
+    Ok(RoiRanges {
+        width: gxi_get_int_range(GX_FEATURE_ID::GX_INT_WIDTH)?.into(),
+        height: gxi_get_int_range(GX_FEATURE_ID::GX_INT_HEIGHT)?.into(),
+        offset_x: gxi_get_int_range(GX_FEATURE_ID::GX_INT_OFFSET_X)?.into(),
+        offset_y: gxi_get_int_range(GX_FEATURE_ID::GX_INT_OFFSET_Y)?.into(),
+    })
+}
+
+#[cfg(feature = "solo")]
+struct RoiAxis {
+    size: GX_FEATURE_ID,
+    offset: GX_FEATURE_ID,
+    max: GX_FEATURE_ID,
+    binning: GX_FEATURE_ID,
+    decimation: GX_FEATURE_ID,
+}
+
+#[cfg(feature = "solo")]
+const ROI_AXIS_X: RoiAxis = RoiAxis {
+    size: GX_FEATURE_ID::GX_INT_WIDTH,
+    offset: GX_FEATURE_ID::GX_INT_OFFSET_X,
+    max: GX_FEATURE_ID::GX_INT_WIDTH_MAX,
+    binning: GX_FEATURE_ID::GX_INT_BINNING_HORIZONTAL,
+    decimation: GX_FEATURE_ID::GX_INT_DECIMATION_HORIZONTAL,
+};
+
+#[cfg(feature = "solo")]
+const ROI_AXIS_Y: RoiAxis = RoiAxis {
+    size: GX_FEATURE_ID::GX_INT_HEIGHT,
+    offset: GX_FEATURE_ID::GX_INT_OFFSET_Y,
+    max: GX_FEATURE_ID::GX_INT_HEIGHT_MAX,
+    binning: GX_FEATURE_ID::GX_INT_BINNING_VERTICAL,
+    decimation: GX_FEATURE_ID::GX_INT_DECIMATION_VERTICAL,
+};
+
+/// Sensor pixels per image pixel along the axis, binning times decimation.
+#[cfg(feature = "solo")]
+fn gxi_get_axis_scale(axis: &RoiAxis) -> Result<i64> {
+    let mut scale = 1;
+    for feature_id in [axis.binning, axis.decimation] {
+        if gxi_is_implemented(feature_id)? {
+            scale *= gxi_get_feature_value::<i64>(feature_id)?.max(1);
+        }
+    }
+    Ok(scale)
+}
+
+/// Snap size and offset of one axis into their ranges, the size first and then the offset
+/// so the ROI still ends inside `size_range.max`, the full sensor size.
+fn fit_roi_axis(
+    size_range: IntRange,
+    offset_range: IntRange,
+    size: i64,
+    offset: i64,
+) -> (i64, i64) {
+    let size = size_range.snap(size);
+    let offset = IntRange {
+        max: size_range.max - size,
+        ..offset_range
+    }
+    .snap(offset);
+    (size, offset)
+}
+
+/// Write size and offset of one axis, snapped into the ranges valid right now. The offset
+/// goes to its minimum first so the new size never collides with the old offset.
+#[cfg(feature = "solo")]
+fn gxi_write_roi_axis(axis: &RoiAxis, size: i64, offset: i64) -> Result<(i64, i64)> {
+    let max: i64 = gxi_get_feature_value(axis.max)?;
+    let size_range = IntRange {
+        max,
+        ..IntRange::from(gxi_get_int_range(axis.size)?)
+    };
+    let offset_range = IntRange::from(gxi_get_int_range(axis.offset)?);
+    let (size, offset) = fit_roi_axis(size_range, offset_range, size, offset);
+
+    gxi_set_feature_value(axis.offset, &offset_range.min)?;
+    gxi_set_feature_value(axis.size, &size)?;
+    gxi_set_feature_value(axis.offset, &offset)?;
+    Ok((size, offset))
+}
+
+/// Binning and decimation factors the device implements, with their current values.
+#[cfg(feature = "solo")]
+fn gxi_get_scale_factors() -> Result<Vec<(GX_FEATURE_ID, i64)>> {
+    let mut factors = Vec::with_capacity(4);
+    for axis in [&ROI_AXIS_X, &ROI_AXIS_Y] {
+        for feature_id in [axis.binning, axis.decimation] {
+            if gxi_is_implemented(feature_id)? {
+                factors.push((feature_id, gxi_get_feature_value(feature_id)?));
+            }
+        }
+    }
+    Ok(factors)
+}
+
+/// Write back the factors and ROI saved before a refit, factors first so the old ROI fits.
+#[cfg(feature = "solo")]
+fn gxi_restore_scale(factors: &[(GX_FEATURE_ID, i64)], roi: &[(i64, i64, i64)]) -> Result<()> {
+    for (feature_id, value) in factors {
+        if gxi_get_feature_value::<i64>(*feature_id)? != *value {
+            gxi_set_feature_value(*feature_id, value)?;
+        }
+    }
+    for (axis, (_, size, offset)) in [&ROI_AXIS_X, &ROI_AXIS_Y].into_iter().zip(roi) {
+        gxi_write_roi_axis(axis, *size, *offset)?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+fn gxi_rescale_roi(before: &[(i64, i64, i64)]) -> Result<Vec<(i64, i64)>> {
+    let mut fitted = Vec::with_capacity(2);
+    for (axis, (old_scale, size, offset)) in [&ROI_AXIS_X, &ROI_AXIS_Y].into_iter().zip(before) {
+        let new_scale = gxi_get_axis_scale(axis)?;
+        fitted.push(gxi_write_roi_axis(
+            axis,
+            size * old_scale / new_scale,
+            offset * old_scale / new_scale,
+        )?);
+    }
+    Ok(fitted)
+}
+
+/// Run `change` and rescale the ROI from the old to the new binning and decimation, so it
+/// keeps covering the same sensor area as far as the new ranges allow.
+///
+/// If `change` or the refit fails, e.g. the horizontal binning was set but the vertical one
+/// was refused, the previous factors and ROI are written back before the error is returned.
+#[cfg(feature = "solo")]
+fn gxi_refit_roi<F>(change: F) -> Result<()>
+where
+    F: FnOnce() -> Result<()>,
+{
+    let factors = gxi_get_scale_factors()?;
+    let mut before = Vec::with_capacity(2);
+    for axis in [&ROI_AXIS_X, &ROI_AXIS_Y] {
+        before.push((
+            gxi_get_axis_scale(axis)?,
+            gxi_get_feature_value::<i64>(axis.size)?,
+            gxi_get_feature_value::<i64>(axis.offset)?,
+        ));
+    }
+
+    match change().and_then(|()| gxi_rescale_roi(&before)) {
+        Ok(fitted) => {
+            println!(
+                "Successfully refit ROI to {}x{} at ({}, {})",
+                fitted[0].0, fitted[1].0, fitted[0].1, fitted[1].1
+            );
+            Ok(())
+        }
+        Err(e) => {
+            // 和gxi_set_roi一样，回滚失败只打印，返回原来的错误
+            if let Err(rollback) = gxi_restore_scale(&factors, &before) {
+                println!(
+                    "Failed to roll back binning, decimation and ROI: {:?}",
+                    rollback
+                );
+            }
+            Err(e)
+        }
+    }
+}
+
+//----------------------------------------------------------
+//---------------ROI----------------------------------------
+//----------------------------------------------------------
+
+/// A region of interest in image pixels, after binning and decimation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rect {
+    pub x: i64,
+    pub y: i64,
+    pub width: i64,
+    pub height: i64,
+}
+
+impl Rect {
+    pub fn new(x: i64, y: i64, width: i64, height: i64) -> Self {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+/// The ROI of the selected region.
+#[cfg(feature = "solo")]
+pub fn gxi_get_roi() -> Result<Rect> {
+    Ok(Rect {
+        x: gxi_get_feature_value(GX_FEATURE_ID::GX_INT_OFFSET_X)?,
+        y: gxi_get_feature_value(GX_FEATURE_ID::GX_INT_OFFSET_Y)?,
+        width: gxi_get_feature_value(GX_FEATURE_ID::GX_INT_WIDTH)?,
+        height: gxi_get_feature_value(GX_FEATURE_ID::GX_INT_HEIGHT)?,
+    })
+}
+
+#[cfg(feature = "solo")]
+fn gxi_write_roi(rect: Rect) -> Result<Rect> {
+    let (width, x) = gxi_write_roi_axis(&ROI_AXIS_X, rect.width, rect.x)?;
+    let (height, y) = gxi_write_roi_axis(&ROI_AXIS_Y, rect.height, rect.y)?;
+    Ok(Rect {
+        x,
+        y,
+        width,
+        height,
+    })
+}
+
+/// Set the ROI of the selected region in one go and return the one actually set.
+///
+/// Sizes and offsets are rounded down to the increments the camera takes and clamped to the
+/// sensor, the writes are ordered so the offset never exceeds the new size. If a write fails,
+/// the previous ROI is written back before the error is returned.
+#[cfg(feature = "solo")]
+pub fn gxi_set_roi(rect: Rect) -> Result<Rect> {
+    if rect.width <= 0 || rect.height <= 0 || rect.x < 0 || rect.y < 0 {
+        return Err(Error::new(ErrorKind::InvalidParameter(format!(
+            "invalid ROI {:?}",
+            rect
+        ))));
+    }
+
+    let previous = gxi_get_roi()?;
+    match gxi_write_roi(rect) {
+        Ok(roi) => {
+            println!("Successfully set ROI to {:?}", roi);
+            Ok(roi)
+        }
+        Err(e) => {
+            // 回滚失败也只报原来的错误，原来的错误才是调用者要看的
+            if let Err(rollback) = gxi_write_roi(previous) {
+                println!("Failed to roll back ROI to {:?}: {:?}", previous, rollback);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Set a `width` x `height` ROI centred on the sensor.
+#[cfg(feature = "solo")]
+pub fn gxi_center_roi(width: i64, height: i64) -> Result<Rect> {
+    let max_width: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_INT_WIDTH_MAX)?;
+    let max_height: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_INT_HEIGHT_MAX)?;
+    let width_range = IntRange {
+        max: max_width,
+        ..IntRange::from(gxi_get_int_range(GX_FEATURE_ID::GX_INT_WIDTH)?)
+    };
+    let height_range = IntRange {
+        max: max_height,
+        ..IntRange::from(gxi_get_int_range(GX_FEATURE_ID::GX_INT_HEIGHT)?)
+    };
+    // 先按步长取好宽高，再居中，不然居中后的偏移会被取整带偏
+    let width = width_range.snap(width);
+    let height = height_range.snap(height);
+    gxi_set_roi(Rect {
+        x: (max_width - width) / 2,
+        y: (max_height - height) / 2,
+        width,
+        height,
+    })
+}
+
+//----------------------------------------------------------
+//---------------Multi ROI----------------------------------
+//----------------------------------------------------------
+
+/// Switch `region` on and set its ROI, see `gxi_set_roi`.
+#[cfg(feature = "solo")]
+pub fn gxi_set_region_roi(region: GX_REGION_SELECTOR_ENTRY, rect: Rect) -> Result<Rect> {
+    gxi_set_region_selector(region as i64)?;
+    gxi_set_region_mode(GX_REGION_MODE_ENTRY::GX_REGION_MODE_ON)?;
+    gxi_set_roi(rect)
+}
+
+/// Send only region 0 with the ROI `rect`.
+#[cfg(feature = "solo")]
+pub fn gxi_set_single_roi(rect: Rect) -> Result<Rect> {
+    if gxi_is_implemented(GX_FEATURE_ID::GX_ENUM_REGION_SEND_MODE)? {
+        gxi_set_region_send_mode(GX_REGION_SEND_MODE_ENTRY::GX_REGION_SEND_SINGLE_ROI_MODE)?;
+        gxi_set_region_roi(GX_REGION_SELECTOR_ENTRY::GX_REGION_SELECTOR_REGION0, rect)
+    } else {
+        gxi_set_roi(rect)
+    }
+}
+
+/// Send one region per rect, region n getting `rects[n]`, and switch the other regions off.
+/// Each region rolls back on its own, so on error the regions before it keep their new ROI.
+#[cfg(feature = "solo")]
+pub fn gxi_set_multi_roi(rects: &[Rect]) -> Result<Vec<Rect>> {
+    let regions = gxi_get_enum_description(GX_FEATURE_ID::GX_ENUM_REGION_SELECTOR)?;
+    if rects.is_empty() || rects.len() > regions.len() {
+        return Err(Error::new(ErrorKind::InvalidParameter(format!(
+            "the camera has {} regions, got {} ROIs",
+            regions.len(),
+            rects.len()
+        ))));
+    }
+
+    gxi_set_region_send_mode(GX_REGION_SEND_MODE_ENTRY::GX_REGION_SEND_MULTI_ROI_MODE)?;
+    let mut set = Vec::with_capacity(rects.len());
+    for (region, rect) in regions.iter().zip(rects) {
+        gxi_set_region_selector(region.n_value)?;
+        gxi_set_region_mode(GX_REGION_MODE_ENTRY::GX_REGION_MODE_ON)?;
+        set.push(gxi_set_roi(*rect)?);
+    }
+    for region in regions.iter().skip(rects.len()) {
+        gxi_set_region_selector(region.n_value)?;
+        gxi_set_region_mode(GX_REGION_MODE_ENTRY::GX_REGION_MODE_OFF)?;
+    }
+    println!("Successfully set {} ROIs", set.len());
+    Ok(set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn int_range_contains_only_values_on_the_grid() {
+        let range = IntRange {
+            min: 16,
+            max: 64,
+            inc: 8,
+        };
+        assert!(range.contains(16) && range.contains(24) && range.contains(64));
+        assert!(!range.contains(20));
+        assert!(!range.contains(8) && !range.contains(72));
+    }
+
+    #[test]
+    fn int_range_snaps_down_onto_the_grid() {
+        let range = IntRange {
+            min: 16,
+            max: 70,
+            inc: 8,
+        };
+        assert_eq!(range.snap(16), 16);
+        assert_eq!(range.snap(23), 16);
+        assert_eq!(range.snap(24), 24);
+        // 最大值不在步长上时取不超过它的那一格
+        assert_eq!(range.snap(70), 64);
+        assert_eq!(range.snap(1000), 64);
+        assert_eq!(range.snap(-5), 16);
+        for value in -10..100 {
+            assert!(range.contains(range.snap(value)), "{}", value);
+        }
+    }
+
+    #[test]
+    fn int_range_survives_odd_bounds() {
+        let empty = IntRange {
+            min: 10,
+            max: 4,
+            inc: 2,
+        };
+        assert_eq!(empty.snap(0), 10);
+        assert_eq!(empty.snap(100), 10);
+        assert!(!empty.contains(10));
+
+        let no_step = IntRange {
+            min: 0,
+            max: 10,
+            inc: 0,
+        };
+        assert!(no_step.contains(7));
+        assert_eq!(no_step.snap(7), 7);
+    }
+
+    #[test]
+    fn int_range_from_device_range_has_a_positive_step() {
+        let mut device_range = GX_INT_RANGE::new();
+        device_range.nMin = 8;
+        device_range.nMax = 4096;
+        device_range.nInc = 0;
+        assert_eq!(
+            IntRange::from(device_range),
+            IntRange {
+                min: 8,
+                max: 4096,
+                inc: 1,
+            }
+        );
+    }
+
+    const WIDTH: IntRange = IntRange {
+        min: 16,
+        max: 1920,
+        inc: 16,
+    };
+    const OFFSET_X: IntRange = IntRange {
+        min: 0,
+        max: 1904,
+        inc: 2,
+    };
+
+    #[test]
+    fn roi_axis_snaps_size_and_offset_down() {
+        assert_eq!(fit_roi_axis(WIDTH, OFFSET_X, 1000, 99), (992, 98));
+        assert_eq!(fit_roi_axis(WIDTH, OFFSET_X, 1920, 0), (1920, 0));
+        assert_eq!(fit_roi_axis(WIDTH, OFFSET_X, 1, 0), (16, 0));
+    }
+
+    #[test]
+    fn roi_axis_keeps_the_roi_on_the_sensor() {
+        assert_eq!(fit_roi_axis(WIDTH, OFFSET_X, 1000, 1900), (992, 928));
+        assert_eq!(fit_roi_axis(WIDTH, OFFSET_X, 5000, 100), (1920, 0));
+        for (size, offset) in [(640, 1700), (1919, 1), (17, 1903)] {
+            let (size, offset) = fit_roi_axis(WIDTH, OFFSET_X, size, offset);
+            assert!(WIDTH.contains(size) && OFFSET_X.contains(offset));
+            assert!(offset + size <= WIDTH.max, "{} + {}", offset, size);
+        }
+    }
+}
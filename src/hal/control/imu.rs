@@ -0,0 +1,653 @@
+//! IMU (accelerometer, gyro and thermometer) configuration, GX_BUFFER_IMU_DATA decoding, and pairing of frames with their nearest IMU samples.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::hal::config::*;
+use crate::hal::device::{GxiFrame, gxi_get_frame};
+use crate::raw::gx_enum::{
+    GX_FEATURE_ID, GX_IMU_CONFIG_ACC_ODR_ENTRY,
+    GX_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_FREQUENCY_ENTRY,
+    GX_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_SWITCH_ENTRY, GX_IMU_CONFIG_ACC_RANGE_ENTRY,
+    GX_IMU_CONFIG_GYRO_ODR_ENTRY, GX_IMU_CONFIG_GYRO_ODR_LOW_PASS_FILTER_SWITCH_ENTRY,
+    GX_IMU_CONFIG_GYRO_RANGE_ENTRY, GX_IMU_TEMPERATURE_ODR_ENTRY,
+};
+
+use std::collections::VecDeque;
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_imu_acc_range() -> Result<i64> {
+    let acc_range: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_ACC_RANGE)?;
+    println!("Now, imu acc range is {}", acc_range);
+    Ok(acc_range)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_imu_acc_range(acc_range: GX_IMU_CONFIG_ACC_RANGE_ENTRY) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_ACC_RANGE,
+        &(acc_range as i64),
+    )?;
+    println!("Now, imu acc range is {:?}", acc_range);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_imu_acc_odr() -> Result<i64> {
+    let acc_odr: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_ACC_ODR)?;
+    println!("Now, imu acc odr is {}", acc_odr);
+    Ok(acc_odr)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_imu_acc_odr(acc_odr: GX_IMU_CONFIG_ACC_ODR_ENTRY) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_ACC_ODR, &(acc_odr as i64))?;
+    println!("Now, imu acc odr is {:?}", acc_odr);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_imu_acc_low_pass_filter_switch() -> Result<i64> {
+    let acc_low_pass_filter_switch: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_SWITCH)?;
+    println!(
+        "Now, imu acc low pass filter switch is {}",
+        acc_low_pass_filter_switch
+    );
+    Ok(acc_low_pass_filter_switch)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_imu_acc_low_pass_filter_switch(
+    acc_low_pass_filter_switch: GX_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_SWITCH_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_SWITCH,
+        &(acc_low_pass_filter_switch as i64),
+    )?;
+    println!(
+        "Now, imu acc low pass filter switch is {:?}",
+        acc_low_pass_filter_switch
+    );
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_imu_acc_low_pass_filter_frequency() -> Result<i64> {
+    let acc_low_pass_filter_frequency: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_FREQUENCY)?;
+    println!(
+        "Now, imu acc low pass filter frequency is {}",
+        acc_low_pass_filter_frequency
+    );
+    Ok(acc_low_pass_filter_frequency)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_imu_acc_low_pass_filter_frequency(
+    acc_low_pass_filter_frequency: GX_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_FREQUENCY_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_FREQUENCY,
+        &(acc_low_pass_filter_frequency as i64),
+    )?;
+    println!(
+        "Now, imu acc low pass filter frequency is {:?}",
+        acc_low_pass_filter_frequency
+    );
+    Ok(())
+}
+
+/// The gyro ranges of the X, Y and Z axes.
+#[cfg(feature = "solo")]
+pub fn gxi_get_imu_gyro_range() -> Result<[i64; 3]> {
+    let gyro_range: [i64; 3] = [
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_XRANGE)?,
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_YRANGE)?,
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_ZRANGE)?,
+    ];
+    println!("Now, imu gyro range is {:?}", gyro_range);
+    Ok(gyro_range)
+}
+
+/// Set the same gyro range on the X, Y and Z axes.
+#[cfg(feature = "solo")]
+pub fn gxi_set_imu_gyro_range(gyro_range: GX_IMU_CONFIG_GYRO_RANGE_ENTRY) -> Result<()> {
+    for feature_id in [
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_XRANGE,
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_YRANGE,
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_ZRANGE,
+    ] {
+        gxi_set_feature_value(feature_id, &(gyro_range as i64))?;
+    }
+    println!("Now, imu gyro range is {:?}", gyro_range);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_imu_gyro_odr() -> Result<i64> {
+    let gyro_odr: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_ODR)?;
+    println!("Now, imu gyro odr is {}", gyro_odr);
+    Ok(gyro_odr)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_imu_gyro_odr(gyro_odr: GX_IMU_CONFIG_GYRO_ODR_ENTRY) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_ODR,
+        &(gyro_odr as i64),
+    )?;
+    println!("Now, imu gyro odr is {:?}", gyro_odr);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_imu_gyro_low_pass_filter_switch() -> Result<i64> {
+    let gyro_low_pass_filter_switch: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_ODR_LOW_PASS_FILTER_SWITCH)?;
+    println!(
+        "Now, imu gyro low pass filter switch is {}",
+        gyro_low_pass_filter_switch
+    );
+    Ok(gyro_low_pass_filter_switch)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_imu_gyro_low_pass_filter_switch(
+    gyro_low_pass_filter_switch: GX_IMU_CONFIG_GYRO_ODR_LOW_PASS_FILTER_SWITCH_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_ODR_LOW_PASS_FILTER_SWITCH,
+        &(gyro_low_pass_filter_switch as i64),
+    )?;
+    println!(
+        "Now, imu gyro low pass filter switch is {:?}",
+        gyro_low_pass_filter_switch
+    );
+    Ok(())
+}
+
+// 陀螺仪低通截止频率的枚举值就是以Hz为单位的频率本身，直接按整数读写
+/// The gyro low pass cutoff frequency in Hz.
+#[cfg(feature = "solo")]
+pub fn gxi_get_imu_gyro_low_pass_filter_frequency() -> Result<i64> {
+    let gyro_low_pass_filter_frequency: i64 = gxi_get_feature_value(
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_ODR_LOW_PASS_FILTER_FREQUENCY,
+    )?;
+    println!(
+        "Now, imu gyro low pass filter frequency is {}",
+        gyro_low_pass_filter_frequency
+    );
+    Ok(gyro_low_pass_filter_frequency)
+}
+
+/// Set the gyro low pass cutoff frequency in Hz, it must be one the device lists.
+#[cfg(feature = "solo")]
+pub fn gxi_set_imu_gyro_low_pass_filter_frequency(
+    gyro_low_pass_filter_frequency: i64,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_ODR_LOW_PASS_FILTER_FREQUENCY,
+        &gyro_low_pass_filter_frequency,
+    )?;
+    println!(
+        "Now, imu gyro low pass filter frequency is {}",
+        gyro_low_pass_filter_frequency
+    );
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_imu_temperature_odr() -> Result<i64> {
+    let temperature_odr: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_IMU_TEMPERATURE_ODR)?;
+    println!("Now, imu temperature odr is {}", temperature_odr);
+    Ok(temperature_odr)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_imu_temperature_odr(temperature_odr: GX_IMU_TEMPERATURE_ODR_ENTRY) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_IMU_TEMPERATURE_ODR,
+        &(temperature_odr as i64),
+    )?;
+    println!("Now, imu temperature odr is {:?}", temperature_odr);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_imu_room_temperature() -> Result<f64> {
+    let room_temperature: f64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_FLOAT_IMU_ROOM_TEMPERATURE)?;
+    println!("Now, imu room temperature is {}", room_temperature);
+    Ok(room_temperature)
+}
+
+//----------------------------------------------------------
+//---------------IMU Samples--------------------------------
+//----------------------------------------------------------
+
+// GxIAPI.h只说GX_BUFFER_IMU_DATA是"IMU data"，没有给出记录格式，所以格式由调用方按相机手册填，
+// 这里不猜默认值
+
+/// The record layout of GX_BUFFER_IMU_DATA, from the camera's manual since the SDK does not
+/// document it. Records are little endian: a u64 timestamp, three i16 acceleration counts,
+/// three i16 angular rate counts and an optional i16 temperature, at the given byte offsets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImuRecordLayout {
+    pub record_size: usize,
+    pub timestamp_offset: usize,
+    pub acceleration_offset: usize,
+    pub angular_rate_offset: usize,
+    pub temperature_offset: Option<usize>,
+    /// Counts at the full scale of the range, e.g. 32768 for a 16 bit sensor.
+    pub full_scale_counts: f64,
+    /// Counts per degree Celsius.
+    pub temperature_counts_per_degree: f64,
+}
+
+impl ImuRecordLayout {
+    /// Check that every field fits in the record.
+    pub fn validate(&self) -> Result<()> {
+        let fields = [
+            Some((self.timestamp_offset, 8)),
+            Some((self.acceleration_offset, 6)),
+            Some((self.angular_rate_offset, 6)),
+            self.temperature_offset.map(|offset| (offset, 2)),
+        ];
+        // 偏移来自调用方，越界的偏移相加会溢出，溢出同样算作放不下
+        let fits = |(offset, len): (usize, usize)| {
+            offset
+                .checked_add(len)
+                .is_some_and(|end| end <= self.record_size)
+        };
+        let positive = |scale: f64| scale.is_finite() && scale > 0.0;
+        if !fields.into_iter().flatten().all(fits)
+            || !positive(self.full_scale_counts)
+            || !positive(self.temperature_counts_per_degree)
+        {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "invalid imu record layout {:?}",
+                self
+            ))));
+        }
+        Ok(())
+    }
+}
+
+/// Full scale of an accelerometer range entry value, in g.
+pub fn imu_acc_range_g(acc_range: i64) -> Result<f64> {
+    match acc_range {
+        2 => Ok(16.0),
+        3 => Ok(8.0),
+        4 => Ok(4.0),
+        5 => Ok(2.0),
+        _ => Err(Error::new(ErrorKind::InvalidParameter(format!(
+            "unknown imu acc range {}",
+            acc_range
+        )))),
+    }
+}
+
+/// Full scale of a gyro range entry value, in degrees per second.
+pub fn imu_gyro_range_dps(gyro_range: i64) -> Result<f64> {
+    match gyro_range {
+        2 => Ok(125.0),
+        3 => Ok(250.0),
+        4 => Ok(500.0),
+        5 => Ok(1000.0),
+        6 => Ok(2000.0),
+        _ => Err(Error::new(ErrorKind::InvalidParameter(format!(
+            "unknown imu gyro range {}",
+            gyro_range
+        )))),
+    }
+}
+
+/// Full scales the raw IMU counts are converted with, the accelerometer in g and the gyro
+/// axes in degrees per second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImuScale {
+    pub acc_range_g: f64,
+    pub gyro_range_dps: [f64; 3],
+}
+
+impl ImuScale {
+    /// Build from the accelerometer and X/Y/Z gyro range entry values.
+    pub fn from_ranges(acc_range: i64, gyro_range: [i64; 3]) -> Result<Self> {
+        Ok(ImuScale {
+            acc_range_g: imu_acc_range_g(acc_range)?,
+            gyro_range_dps: [
+                imu_gyro_range_dps(gyro_range[0])?,
+                imu_gyro_range_dps(gyro_range[1])?,
+                imu_gyro_range_dps(gyro_range[2])?,
+            ],
+        })
+    }
+}
+
+/// One IMU reading, the acceleration in g, angular rate in degrees per second and
+/// temperature in Celsius.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImuSample {
+    pub timestamp: u64,
+    pub acceleration: [f64; 3],
+    pub angular_rate: [f64; 3],
+    /// `None` when the record layout has no temperature.
+    pub temperature: Option<f64>,
+}
+
+impl ImuSample {
+    fn from_record(record: &[u8], layout: &ImuRecordLayout, scale: &ImuScale) -> Self {
+        let read_i16 = |offset: usize| i16::from_le_bytes([record[offset], record[offset + 1]]);
+        let mut timestamp_bytes = [0u8; 8];
+        timestamp_bytes
+            .copy_from_slice(&record[layout.timestamp_offset..layout.timestamp_offset + 8]);
+
+        let acc = layout.acceleration_offset;
+        let gyro = layout.angular_rate_offset;
+        let acc_scale = scale.acc_range_g / layout.full_scale_counts;
+        ImuSample {
+            timestamp: u64::from_le_bytes(timestamp_bytes),
+            acceleration: [
+                read_i16(acc) as f64 * acc_scale,
+                read_i16(acc + 2) as f64 * acc_scale,
+                read_i16(acc + 4) as f64 * acc_scale,
+            ],
+            angular_rate: [
+                read_i16(gyro) as f64 * scale.gyro_range_dps[0] / layout.full_scale_counts,
+                read_i16(gyro + 2) as f64 * scale.gyro_range_dps[1] / layout.full_scale_counts,
+                read_i16(gyro + 4) as f64 * scale.gyro_range_dps[2] / layout.full_scale_counts,
+            ],
+            temperature: layout
+                .temperature_offset
+                .map(|offset| read_i16(offset) as f64 / layout.temperature_counts_per_degree),
+        }
+    }
+}
+
+/// Decode a GX_BUFFER_IMU_DATA buffer into samples. Records with a zero timestamp are
+/// unused slots and skipped. A buffer that is not a whole number of records means the
+/// layout does not match the device and is an error.
+pub fn decode_imu_data(
+    buffer: &[u8],
+    layout: &ImuRecordLayout,
+    scale: &ImuScale,
+) -> Result<Vec<ImuSample>> {
+    layout.validate()?;
+    if buffer.len() % layout.record_size != 0 {
+        return Err(Error::new(ErrorKind::InvalidParameter(format!(
+            "imu buffer length {} is not a multiple of the {} byte record, check the layout",
+            buffer.len(),
+            layout.record_size
+        ))));
+    }
+    Ok(buffer
+        .chunks_exact(layout.record_size)
+        .map(|record| ImuSample::from_record(record, layout, scale))
+        .filter(|sample| sample.timestamp != 0)
+        .collect())
+}
+
+/// Read the configured ranges, to decode the IMU buffer with.
+#[cfg(feature = "solo")]
+pub fn gxi_get_imu_scale() -> Result<ImuScale> {
+    ImuScale::from_ranges(gxi_get_imu_acc_range()?, gxi_get_imu_gyro_range()?)
+}
+
+/// Read and decode the samples currently in the IMU buffer. The buffer length the device
+/// reports is checked against `layout` before reading.
+#[cfg(feature = "solo")]
+pub fn gxi_get_imu_samples(layout: &ImuRecordLayout, scale: &ImuScale) -> Result<Vec<ImuSample>> {
+    layout.validate()?;
+    let buffer_length = gxi_get_buffer_length(GX_FEATURE_ID::GX_BUFFER_IMU_DATA)?;
+    if buffer_length % layout.record_size != 0 {
+        return Err(Error::new(ErrorKind::InvalidParameter(format!(
+            "device imu buffer of {} bytes does not hold whole {} byte records",
+            buffer_length, layout.record_size
+        ))));
+    }
+    let buffer = gxi_get_buffer(GX_FEATURE_ID::GX_BUFFER_IMU_DATA)?;
+    let samples = decode_imu_data(&buffer, layout, scale)?;
+    println!("Successfully got {} imu samples", samples.len());
+    Ok(samples)
+}
+
+//----------------------------------------------------------
+//---------------Frame Pairing------------------------------
+//----------------------------------------------------------
+
+/// A frame with the IMU samples nearest to its timestamp, in time order.
+#[derive(Debug, Clone)]
+pub struct ImuFrame {
+    pub frame: GxiFrame,
+    pub samples: Vec<ImuSample>,
+}
+
+/// Buffers IMU samples and hands each frame the `nearest` samples closest to its timestamp.
+///
+/// Frames are expected in time order, samples older than the ones given to a frame are
+/// dropped after pairing. At most `capacity` samples are kept, the oldest go first.
+#[derive(Debug, Clone)]
+pub struct ImuFramePairer {
+    nearest: usize,
+    capacity: usize,
+    samples: VecDeque<ImuSample>,
+}
+
+impl ImuFramePairer {
+    pub fn new(nearest: usize, capacity: usize) -> Result<Self> {
+        if nearest == 0 || capacity < nearest {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "cannot pair {} imu samples out of a buffer of {}",
+                nearest, capacity
+            ))));
+        }
+        Ok(ImuFramePairer {
+            nearest,
+            capacity,
+            samples: VecDeque::with_capacity(capacity),
+        })
+    }
+
+    /// Add samples, the ones not newer than the last buffered sample are ignored since the
+    /// device buffer may hand the same sample out twice.
+    pub fn push(&mut self, samples: impl IntoIterator<Item = ImuSample>) {
+        for sample in samples {
+            if self
+                .samples
+                .back()
+                .is_some_and(|last| sample.timestamp <= last.timestamp)
+            {
+                continue;
+            }
+            if self.samples.len() == self.capacity {
+                self.samples.pop_front();
+            }
+            self.samples.push_back(sample);
+        }
+    }
+
+    pub fn buffered(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// The `nearest` buffered samples closest to `timestamp`, in time order.
+    pub fn nearest_samples(&self, timestamp: u64) -> Vec<ImuSample> {
+        if self.samples.is_empty() {
+            return Vec::new();
+        }
+        // 样本按时间有序，从最接近的位置向两边扩展窗口
+        let center = self
+            .samples
+            .partition_point(|sample| sample.timestamp < timestamp);
+        let mut start = center;
+        let mut end = center;
+        while end - start < self.nearest && (start > 0 || end < self.samples.len()) {
+            let take_before = match (start.checked_sub(1), self.samples.get(end)) {
+                (Some(before), Some(after)) => {
+                    timestamp.abs_diff(self.samples[before].timestamp)
+                        <= timestamp.abs_diff(after.timestamp)
+                }
+                (Some(_), None) => true,
+                _ => false,
+            };
+            if take_before {
+                start -= 1;
+            } else {
+                end += 1;
+            }
+        }
+        self.samples.range(start..end).copied().collect()
+    }
+
+    pub fn pair(&mut self, frame: GxiFrame) -> ImuFrame {
+        let samples = self.nearest_samples(frame.timestamp);
+        if let Some(first) = samples.first() {
+            while self
+                .samples
+                .front()
+                .is_some_and(|sample| sample.timestamp < first.timestamp)
+            {
+                self.samples.pop_front();
+            }
+        }
+        ImuFrame { frame, samples }
+    }
+}
+
+/// Frames of a running acquisition, each paired with its nearest IMU samples.
+///
+/// Every item reads one frame, then drains the IMU buffer into the pairer. Acquisition has
+/// to be started before iterating, the stream does not start or stop it.
+#[cfg(feature = "solo")]
+pub struct ImuFrameStream {
+    timeout: u32,
+    layout: ImuRecordLayout,
+    scale: ImuScale,
+    pairer: ImuFramePairer,
+}
+
+#[cfg(feature = "solo")]
+impl ImuFrameStream {
+    pub fn pairer(&self) -> &ImuFramePairer {
+        &self.pairer
+    }
+}
+
+#[cfg(feature = "solo")]
+impl Iterator for ImuFrameStream {
+    type Item = Result<ImuFrame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let frame = match gxi_get_frame(self.timeout) {
+            Ok(frame) => frame,
+            Err(e) => return Some(Err(e)),
+        };
+        match gxi_get_imu_samples(&self.layout, &self.scale) {
+            Ok(samples) => self.pairer.push(samples),
+            Err(e) => return Some(Err(e)),
+        }
+        Some(Ok(self.pairer.pair(frame)))
+    }
+}
+
+/// Pair each frame with its `nearest` IMU samples, reading the ranges once up front.
+#[cfg(feature = "solo")]
+pub fn gxi_imu_frame_stream(
+    timeout: u32,
+    nearest: usize,
+    layout: ImuRecordLayout,
+) -> Result<ImuFrameStream> {
+    layout.validate()?;
+    let scale = gxi_get_imu_scale()?;
+    // 留足几帧之间的样本，避免IMU输出率远高于帧率时最接近的样本被挤掉
+    let pairer = ImuFramePairer::new(nearest, nearest * 64)?;
+    Ok(ImuFrameStream {
+        timeout,
+        layout,
+        scale,
+        pairer,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout() -> ImuRecordLayout {
+        ImuRecordLayout {
+            record_size: 22,
+            timestamp_offset: 0,
+            acceleration_offset: 8,
+            angular_rate_offset: 14,
+            temperature_offset: Some(20),
+            full_scale_counts: 32768.0,
+            temperature_counts_per_degree: 256.0,
+        }
+    }
+
+    #[test]
+    fn validate_accepts_fields_that_fit() {
+        assert!(layout().validate().is_ok());
+        let without_temperature = ImuRecordLayout {
+            record_size: 20,
+            temperature_offset: None,
+            ..layout()
+        };
+        assert!(without_temperature.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_fields_past_the_record() {
+        let short = ImuRecordLayout {
+            record_size: 21,
+            ..layout()
+        };
+        assert!(short.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_overflowing_offsets() {
+        for layout in [
+            ImuRecordLayout {
+                timestamp_offset: usize::MAX,
+                ..layout()
+            },
+            ImuRecordLayout {
+                acceleration_offset: usize::MAX - 1,
+                ..layout()
+            },
+            ImuRecordLayout {
+                angular_rate_offset: usize::MAX,
+                ..layout()
+            },
+            ImuRecordLayout {
+                temperature_offset: Some(usize::MAX),
+                ..layout()
+            },
+        ] {
+            let error = layout.validate().unwrap_err();
+            assert!(matches!(*error.inner, ErrorKind::InvalidParameter(_)));
+        }
+    }
+
+    #[test]
+    fn validate_rejects_non_positive_scales() {
+        for scale in [0.0, -1.0, f64::NAN] {
+            assert!(
+                ImuRecordLayout {
+                    full_scale_counts: scale,
+                    ..layout()
+                }
+                .validate()
+                .is_err()
+            );
+            assert!(
+                ImuRecordLayout {
+                    temperature_counts_per_degree: scale,
+                    ..layout()
+                }
+                .validate()
+                .is_err()
+            );
+        }
+    }
+}
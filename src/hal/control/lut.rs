@@ -0,0 +1,448 @@
+//! Lookup table (LUT) features, and whole tables uploaded and downloaded through GX_BUFFER_LUT_VALUEALL.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::hal::config::*;
+use crate::hal::control::image_format::gxi_get_sensor_bit_depth;
+use crate::raw::gx_enum::{GX_FEATURE_ID, GX_LUT_SELECTOR_ENTRY};
+
+use std::fs;
+use std::path::Path;
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_lut_selector() -> Result<i64> {
+    let lut_selector: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_LUT_SELECTOR)?;
+    println!("Now, lut selector is {}", lut_selector);
+    Ok(lut_selector)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_lut_selector(lut_selector: GX_LUT_SELECTOR_ENTRY) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_LUT_SELECTOR, &(lut_selector as i64))?;
+    println!("Now, lut selector is {:?}", lut_selector);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_lut_selector_luminance() -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_LUT_SELECTOR, &0i64)?;
+    println!("Now, lut selector is 0");
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_lut_enable() -> Result<bool> {
+    let lut_enable: bool = gxi_get_feature_value(GX_FEATURE_ID::GX_BOOL_LUT_ENABLE)?;
+    println!("Now, lut enable is {}", lut_enable);
+    Ok(lut_enable)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_lut_enable(lut_enable: bool) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_BOOL_LUT_ENABLE, &lut_enable)?;
+    println!("Now, lut enable is {}", lut_enable);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_lut_index() -> Result<i64> {
+    let lut_index: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_INT_LUT_INDEX)?;
+    println!("Now, lut index is {}", lut_index);
+    Ok(lut_index)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_lut_index(lut_index: i64) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_INT_LUT_INDEX, &lut_index)?;
+    println!("Now, lut index is {}", lut_index);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_lut_value() -> Result<i64> {
+    let lut_value: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_INT_LUT_VALUE)?;
+    println!("Now, lut value is {}", lut_value);
+    Ok(lut_value)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_lut_value(lut_value: i64) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_INT_LUT_VALUE, &lut_value)?;
+    println!("Now, lut value is {}", lut_value);
+    Ok(())
+}
+
+//----------------------------------------------------------
+//---------------LUT Tables---------------------------------
+//----------------------------------------------------------
+
+// GX_BUFFER_LUT_VALUEALL里每个表项是一个小端的32位整数
+const LUT_ENTRY_SIZE: usize = 4;
+
+/// A whole lookup table, one output value for every input value of a `bit_depth` bit sensor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LutTable {
+    bit_depth: u32,
+    values: Vec<u32>,
+}
+
+impl LutTable {
+    /// Check that there are exactly `2^bit_depth` values, each below `2^bit_depth`.
+    pub fn new(bit_depth: u32, values: Vec<u32>) -> Result<Self> {
+        if !(1..=16).contains(&bit_depth) {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "unsupported LUT bit depth {}",
+                bit_depth
+            ))));
+        }
+        let table = LutTable { bit_depth, values };
+        if table.values.len() != table.entry_count() {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "a {} bit LUT needs {} entries, got {}",
+                bit_depth,
+                table.entry_count(),
+                table.values.len()
+            ))));
+        }
+        if let Some(index) = table.values.iter().position(|v| *v > table.max_value()) {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "LUT entry {} is {}, above the {} bit maximum {}",
+                index,
+                table.values[index],
+                bit_depth,
+                table.max_value()
+            ))));
+        }
+        Ok(table)
+    }
+
+    fn from_fn(bit_depth: u32, f: impl Fn(f64, f64) -> f64) -> Result<Self> {
+        let max_value = ((1u64 << bit_depth) - 1) as f64;
+        let values = (0..1u64 << bit_depth)
+            .map(|input| f(input as f64, max_value).round().clamp(0.0, max_value) as u32)
+            .collect();
+        LutTable::new(bit_depth, values)
+    }
+
+    pub fn identity(bit_depth: u32) -> Result<Self> {
+        LutTable::from_fn(bit_depth, |input, _| input)
+    }
+
+    /// `out = max * (in / max) ^ (1 / gamma)`, a gamma above 1 brightens the shadows.
+    pub fn gamma(bit_depth: u32, gamma: f64) -> Result<Self> {
+        if !gamma.is_finite() || gamma <= 0.0 {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "gamma must be positive, got {}",
+                gamma
+            ))));
+        }
+        LutTable::from_fn(bit_depth, |input, max| {
+            max * (input / max).powf(1.0 / gamma)
+        })
+    }
+
+    /// Stretch around the mid gray by `contrast`, 1.0 keeps the image, 0.0 gives flat gray.
+    pub fn contrast(bit_depth: u32, contrast: f64) -> Result<Self> {
+        if !contrast.is_finite() || contrast < 0.0 {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "contrast must not be negative, got {}",
+                contrast
+            ))));
+        }
+        LutTable::from_fn(bit_depth, |input, max| {
+            (input - max / 2.0) * contrast + max / 2.0
+        })
+    }
+
+    /// Binarize: inputs at or above `threshold` become the maximum, the rest 0.
+    pub fn threshold(bit_depth: u32, threshold: u32) -> Result<Self> {
+        LutTable::from_fn(
+            bit_depth,
+            |input, max| {
+                if input >= threshold as f64 { max } else { 0.0 }
+            },
+        )
+    }
+
+    pub fn bit_depth(&self) -> u32 {
+        self.bit_depth
+    }
+
+    pub fn entry_count(&self) -> usize {
+        1 << self.bit_depth
+    }
+
+    pub fn max_value(&self) -> u32 {
+        ((1u64 << self.bit_depth) - 1) as u32
+    }
+
+    pub fn values(&self) -> &[u32] {
+        &self.values
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    pub fn from_bytes(bit_depth: u32, bytes: &[u8]) -> Result<Self> {
+        if bytes.len() % LUT_ENTRY_SIZE != 0 {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "LUT buffer length {} is not a multiple of {}",
+                bytes.len(),
+                LUT_ENTRY_SIZE
+            ))));
+        }
+        let values = bytes
+            .chunks_exact(LUT_ENTRY_SIZE)
+            .map(|entry| u32::from_le_bytes([entry[0], entry[1], entry[2], entry[3]]))
+            .collect();
+        LutTable::new(bit_depth, values)
+    }
+
+    /// Save as `index,value` lines under an `index,value` header.
+    pub fn save_csv<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut csv = String::from("index,value\n");
+        for (index, value) in self.values.iter().enumerate() {
+            csv.push_str(&format!("{},{}\n", index, value));
+        }
+        fs::write(path.as_ref(), csv)?;
+        println!("Successfully saved LUT to {}", path.as_ref().display());
+        Ok(())
+    }
+
+    /// Load a table written by `save_csv`. A file with only one column is read as the values
+    /// in index order; with two columns the indices must run from 0 without gaps.
+    pub fn load_csv<P: AsRef<Path>>(path: P, bit_depth: u32) -> Result<Self> {
+        let csv = fs::read_to_string(path.as_ref())?;
+        let mut values = Vec::new();
+        for (line_number, line) in csv.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || (line_number == 0 && line.starts_with("index")) {
+                continue;
+            }
+            let invalid_line = || {
+                Error::new(ErrorKind::InvalidParameter(format!(
+                    "invalid LUT line {}: {:?}",
+                    line_number + 1,
+                    line
+                )))
+            };
+            let fields = line.split(',').map(str::trim).collect::<Vec<&str>>();
+            let value = match fields.as_slice() {
+                [value] => value,
+                [index, value] => {
+                    if index.parse::<usize>().map_err(|_| invalid_line())? != values.len() {
+                        return Err(invalid_line());
+                    }
+                    value
+                }
+                _ => return Err(invalid_line()),
+            };
+            values.push(value.parse::<u32>().map_err(|_| invalid_line())?);
+        }
+        LutTable::new(bit_depth, values)
+    }
+}
+
+// 先用传感器位深，不支持这个feature的设备就从LUT缓冲区长度反推
+#[cfg(feature = "solo")]
+fn gxi_get_lut_bit_depth(buffer_length: usize) -> Result<u32> {
+    if gxi_is_implemented(GX_FEATURE_ID::GX_ENUM_SENSOR_BIT_DEPTH)? {
+        return Ok(gxi_get_sensor_bit_depth()? as u32);
+    }
+    let entry_count = buffer_length / LUT_ENTRY_SIZE;
+    if entry_count.is_power_of_two() {
+        Ok(entry_count.trailing_zeros())
+    } else {
+        Err(Error::new(ErrorKind::InvalidParameter(format!(
+            "cannot derive the LUT bit depth from a {} byte buffer",
+            buffer_length
+        ))))
+    }
+}
+
+/// Download the selected LUT.
+#[cfg(feature = "solo")]
+pub fn gxi_get_lut() -> Result<LutTable> {
+    let buffer = gxi_get_buffer(GX_FEATURE_ID::GX_BUFFER_LUT_VALUEALL)?;
+    let bit_depth = gxi_get_lut_bit_depth(buffer.len())?;
+    let table = LutTable::from_bytes(bit_depth, &buffer)?;
+    println!("Successfully got {} bit LUT", bit_depth);
+    Ok(table)
+}
+
+/// Upload `table` to the selected LUT, after checking it matches the device bit depth and
+/// LUT buffer length. The LUT still has to be switched on with `gxi_set_lut_enable`.
+#[cfg(feature = "solo")]
+pub fn gxi_set_lut(table: &LutTable) -> Result<()> {
+    let buffer_length = gxi_get_buffer_length(GX_FEATURE_ID::GX_BUFFER_LUT_VALUEALL)?;
+    let bit_depth = gxi_get_lut_bit_depth(buffer_length)?;
+    let buffer = table.to_bytes();
+    if table.bit_depth() != bit_depth || buffer.len() != buffer_length {
+        return Err(Error::new(ErrorKind::InvalidParameter(format!(
+            "device expects a {} bit LUT of {} bytes, got a {} bit LUT of {} bytes",
+            bit_depth,
+            buffer_length,
+            table.bit_depth(),
+            buffer.len()
+        ))));
+    }
+
+    gxi_set_buffer(GX_FEATURE_ID::GX_BUFFER_LUT_VALUEALL, &buffer)?;
+    println!("Successfully set {} bit LUT", bit_depth);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("gxci-lut-{}-{}", std::process::id(), name))
+    }
+
+    fn is_non_decreasing(values: &[u32]) -> bool {
+        values.windows(2).all(|pair| pair[0] <= pair[1])
+    }
+
+    #[test]
+    fn bit_depth_must_be_1_to_16() {
+        assert!(LutTable::identity(0).is_err());
+        assert!(LutTable::identity(17).is_err());
+
+        let one_bit = LutTable::identity(1).unwrap();
+        assert_eq!(one_bit.values(), &[0, 1]);
+
+        let sixteen_bit = LutTable::identity(16).unwrap();
+        assert_eq!(sixteen_bit.entry_count(), 65536);
+        assert_eq!(sixteen_bit.max_value(), 65535);
+        assert_eq!(sixteen_bit.values()[65535], 65535);
+    }
+
+    #[test]
+    fn new_checks_length_and_range() {
+        assert!(LutTable::new(2, vec![0, 1, 2]).is_err());
+        assert!(LutTable::new(2, vec![0, 1, 2, 4]).is_err());
+        assert!(LutTable::new(2, vec![3, 2, 1, 0]).is_ok());
+    }
+
+    #[test]
+    fn gamma_keeps_the_ends_and_brightens_the_shadows() {
+        assert_eq!(
+            LutTable::gamma(8, 1.0).unwrap(),
+            LutTable::identity(8).unwrap()
+        );
+
+        let table = LutTable::gamma(8, 2.2).unwrap();
+        let values = table.values();
+        assert_eq!((values[0], values[255]), (0, 255));
+        assert!(values[64] > 64 && values[128] > 128);
+        assert!(is_non_decreasing(values));
+
+        for gamma in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(LutTable::gamma(8, gamma).is_err(), "gamma {}", gamma);
+        }
+    }
+
+    #[test]
+    fn contrast_stretches_around_mid_gray() {
+        assert_eq!(
+            LutTable::contrast(10, 1.0).unwrap(),
+            LutTable::identity(10).unwrap()
+        );
+        assert!(
+            LutTable::contrast(8, 0.0)
+                .unwrap()
+                .values()
+                .iter()
+                .all(|v| *v == 128)
+        );
+
+        let table = LutTable::contrast(8, 2.0).unwrap();
+        let values = table.values();
+        assert_eq!(
+            (values[0], values[32], values[223], values[255]),
+            (0, 0, 255, 255)
+        );
+        assert_eq!(values[100], 73);
+        assert!(is_non_decreasing(values));
+
+        for contrast in [-0.5, f64::NAN] {
+            assert!(
+                LutTable::contrast(8, contrast).is_err(),
+                "contrast {}",
+                contrast
+            );
+        }
+    }
+
+    #[test]
+    fn threshold_binarizes_at_the_threshold() {
+        let table = LutTable::threshold(8, 128).unwrap();
+        assert_eq!((table.values()[127], table.values()[128]), (0, 255));
+        assert!(is_non_decreasing(table.values()));
+
+        assert!(
+            LutTable::threshold(8, 0)
+                .unwrap()
+                .values()
+                .iter()
+                .all(|v| *v == 255)
+        );
+        assert!(
+            LutTable::threshold(8, 256)
+                .unwrap()
+                .values()
+                .iter()
+                .all(|v| *v == 0)
+        );
+    }
+
+    #[test]
+    fn bytes_are_32_bit_little_endian() {
+        let table = LutTable::new(12, (0..4096).rev().collect()).unwrap();
+        let bytes = table.to_bytes();
+        assert_eq!(bytes.len(), 4096 * LUT_ENTRY_SIZE);
+        assert_eq!(&bytes[..8], &[0xFF, 0x0F, 0, 0, 0xFE, 0x0F, 0, 0]);
+        assert_eq!(LutTable::from_bytes(12, &bytes).unwrap(), table);
+
+        assert!(LutTable::from_bytes(12, &bytes[..bytes.len() - 1]).is_err());
+        assert!(LutTable::from_bytes(12, &bytes[..bytes.len() - 4]).is_err());
+        assert!(LutTable::from_bytes(1, &[2, 0, 0, 0, 1, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn csv_round_trips() {
+        let path = temp_path("round-trip.csv");
+        let table = LutTable::gamma(8, 0.45).unwrap();
+        table.save_csv(&path).unwrap();
+        let loaded = LutTable::load_csv(&path, 8);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), table);
+    }
+
+    #[test]
+    fn csv_reads_a_value_column_and_rejects_bad_lines() {
+        let load = |name: &str, csv: &str| {
+            let path = temp_path(name);
+            std::fs::write(&path, csv).unwrap();
+            let table = LutTable::load_csv(&path, 2);
+            std::fs::remove_file(&path).unwrap();
+            table
+        };
+        assert_eq!(
+            load("values.csv", "3\n2\n\n1\n0\n").unwrap().values(),
+            &[3, 2, 1, 0]
+        );
+        assert_eq!(
+            load("indexed.csv", "index,value\n0, 0\n1, 1\n2, 3\n3, 3\n")
+                .unwrap()
+                .values(),
+            &[0, 1, 3, 3]
+        );
+        assert!(load("gap.csv", "0,0\n1,1\n3,3\n4,3\n").is_err());
+        assert!(load("columns.csv", "0,0,0\n1,1,1\n2,2,2\n3,3,3\n").is_err());
+        assert!(load("text.csv", "0\n1\ntwo\n3\n").is_err());
+        assert!(load("short.csv", "0\n1\n2\n").is_err());
+        assert!(load("range.csv", "0\n1\n2\n4\n").is_err());
+    }
+}
@@ -2,8 +2,17 @@
 pub mod acquisition;
 pub mod analog;
 pub mod chunk_data;
+pub mod clock_sync;
+pub mod counter_timer;
+pub mod data_stream;
 pub mod device;
 pub mod digital_io;
+pub mod encoder;
+pub mod hdr;
+pub mod health;
 pub mod image_format;
+pub mod imu;
+pub mod lut;
+pub mod serial_port;
 pub mod transport_layer;
 pub mod user_set;
@@ -0,0 +1,448 @@
+//! The camera UART, configured through the SerialPortControl features and read or written as a `std::io` stream through GX_BUFFER_SERIALPORT_DATA.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::hal::config::*;
+use crate::hal::device::gxi_send_command;
+use crate::raw::gx_enum::{
+    GX_FEATURE_ID, GX_SERIALPORT_BAUNDRATE_ENTRY, GX_SERIALPORT_PARITY_ENTRY,
+    GX_SERIALPORT_SELECTOR_ENTRY, GX_SERIALPORT_SOURCE_ENTRY, GX_SERIALPORT_STOP_BITS_ENTRY,
+};
+
+use std::io;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_serial_port_selector() -> Result<i64> {
+    let serial_port_selector: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_SERIALPORT_SELECTOR)?;
+    println!("Now, serial port selector is {}", serial_port_selector);
+    Ok(serial_port_selector)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_serial_port_selector(
+    serial_port_selector: GX_SERIALPORT_SELECTOR_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_SERIALPORT_SELECTOR,
+        &(serial_port_selector as i64),
+    )?;
+    println!("Now, serial port selector is {:?}", serial_port_selector);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_serial_port_source() -> Result<i64> {
+    let serial_port_source: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_SERIALPORT_SOURCE)?;
+    println!("Now, serial port source is {}", serial_port_source);
+    Ok(serial_port_source)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_serial_port_source(serial_port_source: GX_SERIALPORT_SOURCE_ENTRY) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_SERIALPORT_SOURCE,
+        &(serial_port_source as i64),
+    )?;
+    println!("Now, serial port source is {:?}", serial_port_source);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_serial_port_baud_rate() -> Result<i64> {
+    let serial_port_baud_rate: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_SERIALPORT_BAUDRATE)?;
+    println!("Now, serial port baud rate is {}", serial_port_baud_rate);
+    Ok(serial_port_baud_rate)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_serial_port_baud_rate(
+    serial_port_baud_rate: GX_SERIALPORT_BAUNDRATE_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_SERIALPORT_BAUDRATE,
+        &(serial_port_baud_rate as i64),
+    )?;
+    println!("Now, serial port baud rate is {:?}", serial_port_baud_rate);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_serial_port_data_bits() -> Result<i64> {
+    let serial_port_data_bits: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_INT_SERIALPORT_DATA_BITS)?;
+    println!("Now, serial port data bits is {}", serial_port_data_bits);
+    Ok(serial_port_data_bits)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_serial_port_data_bits(serial_port_data_bits: i64) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_INT_SERIALPORT_DATA_BITS,
+        &serial_port_data_bits,
+    )?;
+    println!("Now, serial port data bits is {}", serial_port_data_bits);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_serial_port_stop_bits() -> Result<i64> {
+    let serial_port_stop_bits: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_SERIALPORT_STOP_BITS)?;
+    println!("Now, serial port stop bits is {}", serial_port_stop_bits);
+    Ok(serial_port_stop_bits)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_serial_port_stop_bits(
+    serial_port_stop_bits: GX_SERIALPORT_STOP_BITS_ENTRY,
+) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_SERIALPORT_STOP_BITS,
+        &(serial_port_stop_bits as i64),
+    )?;
+    println!("Now, serial port stop bits is {:?}", serial_port_stop_bits);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_serial_port_parity() -> Result<i64> {
+    let serial_port_parity: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_SERIALPORT_PARITY)?;
+    println!("Now, serial port parity is {}", serial_port_parity);
+    Ok(serial_port_parity)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_serial_port_parity(serial_port_parity: GX_SERIALPORT_PARITY_ENTRY) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_SERIALPORT_PARITY,
+        &(serial_port_parity as i64),
+    )?;
+    println!("Now, serial port parity is {:?}", serial_port_parity);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_transmit_queue_max_character_count() -> Result<i64> {
+    let max_character_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_INT_TRANSMIT_QUEUE_MAX_CHARACTER_COUNT)?;
+    println!(
+        "Now, transmit queue max character count is {}",
+        max_character_count
+    );
+    Ok(max_character_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_transmit_queue_current_character_count() -> Result<i64> {
+    let current_character_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_INT_TRANSMIT_QUEUE_CURRENT_CHARACTER_COUNT)?;
+    println!(
+        "Now, transmit queue current character count is {}",
+        current_character_count
+    );
+    Ok(current_character_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_receive_queue_max_character_count() -> Result<i64> {
+    let max_character_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_INT_RECEIVE_QUEUE_MAX_CHARACTER_COUNT)?;
+    println!(
+        "Now, receive queue max character count is {}",
+        max_character_count
+    );
+    Ok(max_character_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_receive_queue_current_character_count() -> Result<i64> {
+    let current_character_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_INT_RECEIVE_QUEUE_CURRENT_CHARACTER_COUNT)?;
+    println!(
+        "Now, receive queue current character count is {}",
+        current_character_count
+    );
+    Ok(current_character_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_receive_framing_error_count() -> Result<i64> {
+    let framing_error_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_INT_RECEIVE_FRAMING_ERROR_COUNT)?;
+    println!(
+        "Now, receive framing error count is {}",
+        framing_error_count
+    );
+    Ok(framing_error_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_receive_parity_error_count() -> Result<i64> {
+    let parity_error_count: i64 =
+        gxi_get_feature_value(GX_FEATURE_ID::GX_INT_RECEIVE_PARITY_ERROR_COUNT)?;
+    println!("Now, receive parity error count is {}", parity_error_count);
+    Ok(parity_error_count)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_clear_receive_queue() -> Result<()> {
+    gxi_send_command(GX_FEATURE_ID::GX_COMMAND_RECEIVE_QUEUE_CLEAR)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_serial_port_data_length() -> Result<i64> {
+    let data_length: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_INT_SERIALPORT_DATA_LENGTH)?;
+    println!("Now, serial port data length is {}", data_length);
+    Ok(data_length)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_serial_port_data_length(data_length: i64) -> Result<()> {
+    gxi_set_feature_value(GX_FEATURE_ID::GX_INT_SERIALPORT_DATA_LENGTH, &data_length)?;
+    println!("Now, serial port data length is {}", data_length);
+    Ok(())
+}
+
+//----------------------------------------------------------
+//---------------Camera Serial Port-------------------------
+//----------------------------------------------------------
+
+/// Line settings of the camera UART, `new` gives 8 data bits, no parity and 1 stop bit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SerialPortConfig {
+    pub source: GX_SERIALPORT_SOURCE_ENTRY,
+    pub baud_rate: GX_SERIALPORT_BAUNDRATE_ENTRY,
+    pub data_bits: i64,
+    pub parity: GX_SERIALPORT_PARITY_ENTRY,
+    pub stop_bits: GX_SERIALPORT_STOP_BITS_ENTRY,
+}
+
+impl SerialPortConfig {
+    pub fn new(
+        source: GX_SERIALPORT_SOURCE_ENTRY,
+        baud_rate: GX_SERIALPORT_BAUNDRATE_ENTRY,
+    ) -> Self {
+        SerialPortConfig {
+            source,
+            baud_rate,
+            data_bits: 8,
+            parity: GX_SERIALPORT_PARITY_ENTRY::GX_SERIALPORT_PARITY_NONE,
+            stop_bits: GX_SERIALPORT_STOP_BITS_ENTRY::GX_SERIALPORT_STOP_BITS_ONE,
+        }
+    }
+
+    pub fn data_bits(mut self, data_bits: i64) -> Self {
+        self.data_bits = data_bits;
+        self
+    }
+
+    pub fn parity(mut self, parity: GX_SERIALPORT_PARITY_ENTRY) -> Self {
+        self.parity = parity;
+        self
+    }
+
+    pub fn stop_bits(mut self, stop_bits: GX_SERIALPORT_STOP_BITS_ENTRY) -> Self {
+        self.stop_bits = stop_bits;
+        self
+    }
+}
+
+/// Receive errors counted by the camera since the port was configured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SerialPortErrorCounters {
+    pub framing_errors: i64,
+    pub parity_errors: i64,
+}
+
+// SDK没有串口收发的事件通知，只能轮询队列计数
+// 每次轮询都是一次设备读，间隔从1ms起翻倍退避，避免长时间等待时占满控制通道
+const SERIAL_PORT_POLL_INTERVAL_MIN: Duration = Duration::from_millis(1);
+const SERIAL_PORT_POLL_INTERVAL_MAX: Duration = Duration::from_millis(20);
+
+fn next_poll_interval(interval: Duration) -> Duration {
+    (interval * 2).min(SERIAL_PORT_POLL_INTERVAL_MAX)
+}
+
+/// The camera UART as a `std::io` stream.
+///
+/// `read` waits up to the read timeout for the first byte, then returns what is queued.
+/// `write` queues as much as fits in the transmit queue, waiting up to the write timeout for
+/// room; `flush` waits for the transmit queue to drain. Running out of time gives
+/// `io::ErrorKind::TimedOut`.
+#[cfg(feature = "solo")]
+#[derive(Debug)]
+pub struct CameraSerialPort {
+    config: SerialPortConfig,
+    read_timeout: Duration,
+    write_timeout: Duration,
+}
+
+#[cfg(feature = "solo")]
+impl CameraSerialPort {
+    /// Select serial port 0, apply `config` and clear anything left in the receive queue.
+    pub fn open(config: SerialPortConfig) -> Result<Self> {
+        if !(5..=8).contains(&config.data_bits) {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "a serial port has 5 to 8 data bits, got {}",
+                config.data_bits
+            ))));
+        }
+        gxi_set_serial_port_selector(GX_SERIALPORT_SELECTOR_ENTRY::GX_SERIALPORT_SERIALPORT_0)?;
+        gxi_set_serial_port_source(config.source)?;
+        gxi_set_serial_port_baud_rate(config.baud_rate)?;
+        gxi_set_serial_port_data_bits(config.data_bits)?;
+        gxi_set_serial_port_parity(config.parity)?;
+        gxi_set_serial_port_stop_bits(config.stop_bits)?;
+        gxi_clear_receive_queue()?;
+        println!("Successfully opened camera serial port with {:?}", config);
+        Ok(CameraSerialPort {
+            config,
+            read_timeout: Duration::from_millis(1000),
+            write_timeout: Duration::from_millis(1000),
+        })
+    }
+
+    pub fn config(&self) -> &SerialPortConfig {
+        &self.config
+    }
+
+    pub fn set_read_timeout(&mut self, read_timeout: Duration) {
+        self.read_timeout = read_timeout;
+    }
+
+    pub fn set_write_timeout(&mut self, write_timeout: Duration) {
+        self.write_timeout = write_timeout;
+    }
+
+    /// Bytes waiting in the receive queue.
+    pub fn bytes_to_read(&self) -> Result<usize> {
+        Ok(gxi_get_receive_queue_current_character_count()?.max(0) as usize)
+    }
+
+    // 轮询时不走会打印的gxi_get_*，否则等待期间每毫秒刷一行日志
+    fn queued_count(feature_id: GX_FEATURE_ID) -> Result<usize> {
+        let count: i64 = gxi_get_feature_value(feature_id)?;
+        Ok(count.max(0) as usize)
+    }
+
+    pub fn clear_input(&mut self) -> Result<()> {
+        gxi_clear_receive_queue()
+    }
+
+    pub fn error_counters(&self) -> Result<SerialPortErrorCounters> {
+        Ok(SerialPortErrorCounters {
+            framing_errors: gxi_get_receive_framing_error_count()?,
+            parity_errors: gxi_get_receive_parity_error_count()?,
+        })
+    }
+
+    fn read_queued(&mut self, buf: &mut [u8], queued: usize) -> Result<usize> {
+        let length = queued.min(buf.len());
+        gxi_set_serial_port_data_length(length as i64)?;
+        let data = gxi_get_buffer(GX_FEATURE_ID::GX_BUFFER_SERIALPORT_DATA)?;
+        let length = length.min(data.len());
+        buf[..length].copy_from_slice(&data[..length]);
+        Ok(length)
+    }
+
+    fn write_queued(&mut self, buf: &[u8], room: usize) -> Result<usize> {
+        let length = room.min(buf.len());
+        gxi_set_serial_port_data_length(length as i64)?;
+        gxi_set_buffer(GX_FEATURE_ID::GX_BUFFER_SERIALPORT_DATA, &buf[..length])?;
+        Ok(length)
+    }
+
+    fn transmit_room(&self) -> Result<usize> {
+        let max_character_count: i64 =
+            gxi_get_feature_value(GX_FEATURE_ID::GX_INT_TRANSMIT_QUEUE_MAX_CHARACTER_COUNT)?;
+        let current_character_count =
+            Self::queued_count(GX_FEATURE_ID::GX_INT_TRANSMIT_QUEUE_CURRENT_CHARACTER_COUNT)?;
+        Ok((max_character_count.max(0) as usize).saturating_sub(current_character_count))
+    }
+}
+
+/// Poll `probe` until it gives a value, backing off between polls, or time out.
+#[cfg(feature = "solo")]
+fn poll_until<T>(
+    what: &str,
+    timeout: Duration,
+    mut probe: impl FnMut() -> Result<Option<T>>,
+) -> io::Result<T> {
+    let start = Instant::now();
+    let mut interval = SERIAL_PORT_POLL_INTERVAL_MIN;
+    loop {
+        if let Some(value) = probe().map_err(io::Error::other)? {
+            return Ok(value);
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            return Err(timed_out(what, timeout));
+        }
+        thread::sleep(interval.min(timeout - elapsed));
+        interval = next_poll_interval(interval);
+    }
+}
+
+#[cfg(feature = "solo")]
+fn timed_out(what: &str, timeout: Duration) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::TimedOut,
+        format!("camera serial port {} timed out after {:?}", what, timeout),
+    )
+}
+
+#[cfg(feature = "solo")]
+impl io::Read for CameraSerialPort {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let queued = poll_until("read", self.read_timeout, || {
+            let queued =
+                Self::queued_count(GX_FEATURE_ID::GX_INT_RECEIVE_QUEUE_CURRENT_CHARACTER_COUNT)?;
+            Ok((queued > 0).then_some(queued))
+        })?;
+        self.read_queued(buf, queued).map_err(io::Error::other)
+    }
+}
+
+#[cfg(feature = "solo")]
+impl io::Write for CameraSerialPort {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let room = poll_until("write", self.write_timeout, || {
+            let room = self.transmit_room()?;
+            Ok((room > 0).then_some(room))
+        })?;
+        self.write_queued(buf, room).map_err(io::Error::other)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        poll_until("flush", self.write_timeout, || {
+            let queued =
+                Self::queued_count(GX_FEATURE_ID::GX_INT_TRANSMIT_QUEUE_CURRENT_CHARACTER_COUNT)?;
+            Ok((queued == 0).then_some(()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poll_interval_backs_off_to_the_cap() {
+        let mut interval = SERIAL_PORT_POLL_INTERVAL_MIN;
+        let mut intervals = Vec::new();
+        for _ in 0..7 {
+            intervals.push(interval.as_millis());
+            interval = next_poll_interval(interval);
+        }
+        assert_eq!(intervals, vec![1, 2, 4, 8, 16, 20, 20]);
+    }
+}
@@ -1,8 +1,12 @@
-//! Placeholder
+//! User sets: the settings the device keeps in its own flash, saved and loaded by slot, and
+//! the one it loads at power on.
 
-use crate::error::Result;
+use crate::error::{Error, ErrorKind, Result};
 use crate::hal::config::*;
-use crate::hal::device::gxi_send_command;
+use crate::hal::config_file::{gxi_export_config, gxi_import_config};
+use crate::hal::control::acquisition::{gxi_acquisition_start, gxi_acquisition_stop};
+use crate::hal::device::{gxi_is_acquiring, gxi_send_command};
+use crate::hal::snapshot::{FeatureDifference, gxi_take_snapshot};
 use crate::raw::gx_enum::GX_FEATURE_ID;
 
 #[cfg(feature = "solo")]
@@ -54,6 +58,7 @@ pub fn gxi_get_user_set_default() -> Result<i64> {
     Ok(user_set_default)
 }
 
+/// Choose the user set the device loads at power on, one of `gxi_get_power_on_user_sets`.
 #[cfg(feature = "solo")]
 pub fn gxi_set_user_set_default(user_set_default: i64) -> Result<()> {
     gxi_set_feature_value(GX_FEATURE_ID::GX_ENUM_USER_SET_DEFAULT, &user_set_default)?;
@@ -74,3 +79,149 @@ pub fn gxi_set_user_set_default_user_set0() -> Result<()> {
     println!("Now, user set default is user set 0");
     Ok(())
 }
+
+//----------------------------------------------------------
+//---------------User Set Management------------------------
+//----------------------------------------------------------
+
+/// A user set slot of the device, e.g. `Default` or `UserSet0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GxiUserSet {
+    pub value: i64,
+    pub name: String,
+}
+
+#[cfg(feature = "solo")]
+fn gxi_get_user_sets_of(feature_id: GX_FEATURE_ID) -> Result<Vec<GxiUserSet>> {
+    Ok(gxi_get_enum_description(feature_id)?
+        .into_iter()
+        .map(|description| GxiUserSet {
+            value: description.n_value,
+            name: description.sz_symbolic,
+        })
+        .collect())
+}
+
+/// The user sets the device has, the read only factory `Default` included.
+#[cfg(feature = "solo")]
+pub fn gxi_get_user_sets() -> Result<Vec<GxiUserSet>> {
+    gxi_get_user_sets_of(GX_FEATURE_ID::GX_ENUM_USER_SET_SELECTOR)
+}
+
+/// The user sets the device can load at power on.
+#[cfg(feature = "solo")]
+pub fn gxi_get_power_on_user_sets() -> Result<Vec<GxiUserSet>> {
+    gxi_get_user_sets_of(GX_FEATURE_ID::GX_ENUM_USER_SET_DEFAULT)
+}
+
+/// Look up a user set by name, ignoring case.
+#[cfg(feature = "solo")]
+pub fn gxi_find_user_set(name: &str) -> Result<GxiUserSet> {
+    gxi_get_user_sets()?
+        .into_iter()
+        .find(|user_set| user_set.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            Error::new(ErrorKind::InvalidParameter(format!(
+                "the device has no user set named {}",
+                name
+            )))
+        })
+}
+
+/// Stops acquisition while it is held and starts it again when dropped, since the device
+/// rejects saving and loading user sets while it acquires. Does nothing when acquisition is
+/// not running.
+#[cfg(feature = "solo")]
+pub struct AcquisitionPause {
+    paused: bool,
+}
+
+#[cfg(feature = "solo")]
+impl AcquisitionPause {
+    pub fn new() -> Result<Self> {
+        let paused = gxi_is_acquiring()?;
+        if paused {
+            gxi_acquisition_stop()?;
+        }
+        Ok(Self { paused })
+    }
+
+    /// Start acquisition again now if it was running, with the error that dropping would
+    /// only print.
+    pub fn resume(mut self) -> Result<()> {
+        if !self.paused {
+            return Ok(());
+        }
+        self.paused = false;
+        gxi_acquisition_start()
+    }
+}
+
+#[cfg(feature = "solo")]
+impl Drop for AcquisitionPause {
+    fn drop(&mut self) {
+        if self.paused {
+            if let Err(e) = gxi_acquisition_start() {
+                println!("Failed to resume acquisition: {}", e);
+            }
+        }
+    }
+}
+
+/// Load the settings stored in `user_set` into the device, pausing acquisition meanwhile.
+#[cfg(feature = "solo")]
+pub fn gxi_user_set_load_from(user_set: i64) -> Result<()> {
+    let pause = AcquisitionPause::new()?;
+    gxi_set_user_set_selector(user_set)?;
+    gxi_user_set_load()?;
+    pause.resume()
+}
+
+/// Store the current settings of the device in `user_set`, pausing acquisition meanwhile.
+/// The factory `Default` set is read only.
+#[cfg(feature = "solo")]
+pub fn gxi_user_set_save_to(user_set: i64) -> Result<()> {
+    // 和上面的helper一样，Default固定是0
+    if user_set == 0 {
+        return Err(Error::new(ErrorKind::InvalidParameter(
+            "the Default user set is read only, save to UserSet0 or later".to_string(),
+        )));
+    }
+    let pause = AcquisitionPause::new()?;
+    gxi_set_user_set_selector(user_set)?;
+    gxi_user_set_save()?;
+    pause.resume()
+}
+
+/// The settings that differ between two user sets. Both are loaded into the device to read
+/// them, so this takes a few seconds. The settings the device had before are exported to a
+/// config file in the temp directory and imported again afterwards with verification.
+/// Acquisition is paused meanwhile. Fails if the settings could not be restored, the file is
+/// then kept and named in the error.
+#[cfg(feature = "solo")]
+pub fn gxi_user_set_diff(user_set_a: i64, user_set_b: i64) -> Result<Vec<FeatureDifference>> {
+    let pause = AcquisitionPause::new()?;
+    // 快照只存得下它认识的feature，加载用户集会改掉所有设置，用SDK的配置文件整份存下来再导回去
+    let saved = std::env::temp_dir().join(format!("gxci_user_set_diff_{}.txt", std::process::id()));
+    gxi_export_config(&saved)?;
+    let snapshots = (|| {
+        gxi_user_set_load_from(user_set_a)?;
+        let a = gxi_take_snapshot()?;
+        gxi_user_set_load_from(user_set_b)?;
+        let b = gxi_take_snapshot()?;
+        Ok::<_, Error>((a, b))
+    })();
+
+    // 无论读取是否成功都要把原来的设置写回去，写不回去时设备已经不是调用前的状态，不能只打印
+    if let Err(e) = gxi_import_config(&saved, true) {
+        return Err(Error::new(ErrorKind::GxiError(format!(
+            "failed to restore the settings after the user set diff, they are kept in {:?}: {}",
+            saved, e
+        ))));
+    }
+    let _ = std::fs::remove_file(&saved);
+    pause.resume()?;
+
+    let (a, b) = snapshots?;
+    Ok(a.diff(&b))
+}
@@ -3,21 +3,22 @@
 use crate::error::{Error, ErrorKind, MutexExt, MutexType, Result};
 use crate::hal::base::{GXI, gxi_check};
 use crate::hal::check::{check_gx_status, check_gx_status_with_ok_fn};
+use crate::hal::control::acquisition::GXI_LAST_TRIGGERED_FRAME_ID;
+use crate::hal::frame::{SaveFormat, save_frame};
+use crate::raw::gx_pixel_format::PixelFormatEntry;
 use crate::raw::{gx_callback::*, gx_enum::*, gx_handle::*, gx_interface::*, gx_struct::*};
 use crate::utils::builder::GXDeviceBaseInfoBuilder;
 use crate::utils::extract::*;
 use crate::utils::facade::*;
-use crate::utils::imgproc::*;
 
 use std::ffi::c_void;
+use std::path::Path;
 use std::sync::{Arc, LazyLock, Mutex};
 use std::thread::sleep;
 use std::time::Duration;
 
-#[cfg(feature = "use-imageproc")]
-use imageproc::image::{ImageBuffer, Luma};
 #[cfg(feature = "use-opencv")]
-use opencv::{core, highgui, imgcodecs};
+use opencv::{core, highgui};
 
 //----------------------------------------------------------
 //---------------Common Functions---------------------------
@@ -94,6 +95,11 @@ unsafe impl Send for GxiDevice {}
 pub static GXI_DEVICE: LazyLock<Arc<Mutex<Option<GxiDevice>>>> =
     LazyLock::new(|| Arc::new(Mutex::new(None)));
 
+// 所有AcquisitionStart/Stop都经过gxi_send_command，在那里记下是否在采集；
+// GX_BOOL_ACQUISITION_STATUS只能查触发等待状态，查不到是否在采集
+pub static GXI_ACQUIRING: LazyLock<Arc<Mutex<bool>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(false)));
+
 pub struct GxiFrameData {
     pub frame_data: GX_FRAME_DATA,
     pub image_buffer: Vec<u8>,
@@ -104,13 +110,63 @@ unsafe impl Send for GxiFrameData {}
 pub static GXI_FRAME_DATA: LazyLock<Arc<Mutex<Option<GxiFrameData>>>> =
     LazyLock::new(|| Arc::new(Mutex::new(None)));
 
+/// An owned copy of one captured frame, it stays valid after the next capture.
+#[derive(Debug, Clone)]
+pub struct GxiFrame {
+    pub status: i32,
+    pub width: i32,
+    pub height: i32,
+    pub pixel_format: i32,
+    pub frame_id: u64,
+    pub timestamp: u64,
+    pub data: Vec<u8>,
+}
+
+impl GxiFrame {
+    pub fn from_frame_data(frame_data: &GX_FRAME_DATA) -> Self {
+        Self {
+            status: frame_data.nStatus,
+            width: frame_data.nWidth,
+            height: frame_data.nHeight,
+            pixel_format: frame_data.nPixelFormat,
+            frame_id: frame_data.nFrameID,
+            timestamp: frame_data.nTimestamp,
+            data: copy_img_buf(frame_data.pImgBuf as *const c_void, frame_data.nImgSize),
+        }
+    }
+
+    pub fn from_frame_callback_param(frame_callback_data: &GX_FRAME_CALLBACK_PARAM) -> Self {
+        Self {
+            status: frame_callback_data.status,
+            width: frame_callback_data.nWidth,
+            height: frame_callback_data.nHeight,
+            pixel_format: frame_callback_data.nPixelFormat,
+            frame_id: frame_callback_data.nFrameID,
+            timestamp: frame_callback_data.nTimestamp,
+            data: copy_img_buf(frame_callback_data.pImgBuf, frame_callback_data.nImgSize),
+        }
+    }
+
+    /// The typed pixel format, for its bit depth, channels and Bayer pattern.
+    pub fn pixel_format_entry(&self) -> Result<PixelFormatEntry> {
+        PixelFormatEntry::try_from(self.pixel_format)
+    }
+}
+
+fn copy_img_buf(p_img_buf: *const c_void, img_size: i32) -> Vec<u8> {
+    if p_img_buf.is_null() || img_size <= 0 {
+        return Vec::new();
+    }
+    unsafe { std::slice::from_raw_parts(p_img_buf as *const u8, img_size as usize).to_vec() }
+}
+
 pub static GXI_IMAGE_BUFFER: LazyLock<Arc<Mutex<Option<Vec<u8>>>>> =
     LazyLock::new(|| Arc::new(Mutex::new(None)));
 
 #[cfg(feature = "solo")]
 pub fn gxi_open_device() -> Result<()> {
     let mut device_num = 0;
-    gxi_check(|gxi: &GXInstance| {
+    gxi_check(|gxi: &dyn GXInterface| {
         gxi.gx_update_device_list(&mut device_num, 1000)?;
         Ok(())
     })?;
@@ -120,6 +176,7 @@ pub fn gxi_open_device() -> Result<()> {
 
     check_gx_status_with_ok_fn(status, || {
         *GXI_DEVICE.lock_safe(MutexType::Device)? = Some(GxiDevice { device });
+        *GXI_ACQUIRING.lock_safe(MutexType::Device)? = false;
         Ok(())
     })?;
     println!("Successfully opened device");
@@ -133,6 +190,7 @@ pub fn gxi_close_device() -> Result<()> {
 
     check_gx_status_with_ok_fn(status, || {
         *GXI_DEVICE.lock_safe(MutexType::Device)? = None;
+        *GXI_ACQUIRING.lock_safe(MutexType::Device)? = false;
         Ok(())
     })?;
     println!("Successfully closed device");
@@ -145,10 +203,54 @@ pub fn gxi_send_command(command: GX_FEATURE_ID) -> Result<()> {
     let status = gxi_check(|gxi| gxi.gx_send_command(gxi_device, command))?;
 
     check_gx_status(status)?;
+    match command {
+        GX_FEATURE_ID::GX_COMMAND_ACQUISITION_START => {
+            *GXI_ACQUIRING.lock_safe(MutexType::Device)? = true;
+            // 重新开始采集后帧号从0算起，旧的帧号会让gxi_trigger_and_wait把新帧都当成过期的
+            *GXI_LAST_TRIGGERED_FRAME_ID.lock_safe(MutexType::Event)? = None;
+        }
+        GX_FEATURE_ID::GX_COMMAND_ACQUISITION_STOP => {
+            *GXI_ACQUIRING.lock_safe(MutexType::Device)? = false
+        }
+        _ => {}
+    }
     println!("Successfully sent command");
     Ok(())
 }
 
+/// Whether acquisition is running, i.e. an acquisition start command was sent to the open
+/// device and no stop after it.
+#[cfg(feature = "solo")]
+pub fn gxi_is_acquiring() -> Result<bool> {
+    Ok(*GXI_ACQUIRING.lock_safe(MutexType::Device)?)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_flush_queue() -> Result<()> {
+    let gxi_device = gxi_get_device_handle()?;
+    let status = gxi_check(|gxi| gxi.gx_flush_queue(gxi_device))?;
+
+    check_gx_status(status)?;
+    println!("Successfully flushed queue");
+    Ok(())
+}
+
+/// Get one frame from a running acquisition, without starting or stopping it.
+#[cfg(feature = "solo")]
+pub fn gxi_get_frame(timeout: u32) -> Result<GxiFrame> {
+    let gxi_device = gxi_get_device_handle()?;
+    let (frame_data_facade, image_buffer) = gxi_check(|gxi| fetch_frame_data(gxi, gxi_device))?;
+    let mut frame_data = convert_to_frame_data(&frame_data_facade);
+
+    let status = gxi_check(|gxi| gxi.gx_get_image(gxi_device, &mut frame_data, timeout as i32))?;
+    check_gx_status(status)?;
+
+    let frame = GxiFrame::from_frame_data(&frame_data);
+    drop(image_buffer);
+    println!("Successfully got frame {}", frame.frame_id);
+    Ok(frame)
+}
+
 #[cfg(feature = "solo")]
 pub fn gxi_get_image() -> Result<()> {
     let gxi_device = gxi_get_device_handle()?;
@@ -157,7 +259,7 @@ pub fn gxi_get_image() -> Result<()> {
     gxi_send_command(GX_FEATURE_ID::GX_COMMAND_ACQUISITION_START)?;
 
     let byd = GXI.lock_safe(MutexType::Gxi)?;
-    let gxi = byd.as_ref().ok_or(Error::new(ErrorKind::GxiError(
+    let gxi = byd.as_deref().ok_or(Error::new(ErrorKind::GxiError(
         "GXI is None. Please check your gxci_init situation.".to_string(),
     )))?;
     let device = GXI_DEVICE
@@ -189,7 +291,7 @@ pub fn gxi_get_image_as_frame_data() -> Result<GX_FRAME_DATA> {
     println!("gxi_device: {:?}", gxi_device);
 
     let byd = GXI.lock_safe(MutexType::Gxi)?;
-    let gxi = byd.as_ref().ok_or(Error::new(ErrorKind::GxiError(
+    let gxi = byd.as_deref().ok_or(Error::new(ErrorKind::GxiError(
         "GXI is None. Please check your gxci_init situation.".to_string(),
     )))?;
     let device = GXI_DEVICE
@@ -225,7 +327,7 @@ pub fn gxi_get_image_as_raw() -> Result<&'static [u8]> {
     gxi_send_command(GX_FEATURE_ID::GX_COMMAND_ACQUISITION_START)?;
 
     let byd = GXI.lock_safe(MutexType::Gxi)?;
-    let gxi = byd.as_ref().ok_or(Error::new(ErrorKind::GxiError(
+    let gxi = byd.as_deref().ok_or(Error::new(ErrorKind::GxiError(
         "GXI is None. Please check your gxci_init situation.".to_string(),
     )))?;
     let device = GXI_DEVICE
@@ -274,7 +376,7 @@ pub fn gxi_get_image_as_bytes() -> Result<Vec<u8>> {
 
     let byd = GXI.lock_safe(MutexType::Gxi)?;
 
-    let gxi = byd.as_ref().ok_or(Error::new(ErrorKind::GxiError(
+    let gxi = byd.as_deref().ok_or(Error::new(ErrorKind::GxiError(
         "GXI is None. Please check your gxci_init situation.".to_string(),
     )))?;
     let device = GXI_DEVICE
@@ -314,61 +416,25 @@ pub fn gxi_get_image_as_bytes() -> Result<Vec<u8>> {
     Ok(raw.to_vec())
 }
 
-#[cfg(all(feature = "solo", feature = "use-opencv"))]
-pub fn gxi_save_image_as_png(filename: &str) -> Result<()> {
-    // let frame_data = GXI_FRAME_DATA.lock().map_err(|e| Error::new(ErrorKind::MutexPoisonOptionFrameDataError(e)))?.as_ref().unwrap().frame_data;
-    let frame_data = GXI_FRAME_DATA
-        .lock_safe(MutexType::FrameData)?
-        .as_ref()
-        .ok_or(Error::new(ErrorKind::FrameDataError(
-            "Frame data is None. Please check your get image situation.".to_string(),
-        )))?
-        .frame_data;
-    if frame_data.nStatus == 0 {
-        if let Some(data) = extract_image_data(&frame_data) {
-            let mat =
-                core::Mat::new_rows_cols_with_data(frame_data.nHeight, frame_data.nWidth, &data)
-                    .unwrap();
-            let vec = core::Vector::<i32>::new();
-            if imgcodecs::imwrite(filename, &mat, &vec).unwrap() {
-                println!("Image saved successfully.");
-            } else {
-                println!("Failed to save the image.");
-            }
-        } else {
-            println!("Failed to extract image data.");
-        }
-    }
-    Ok(())
-}
-
-#[cfg(all(feature = "solo", feature = "use-imageproc"))]
-pub fn gxi_save_image_as_png(filename: &str) -> Result<()> {
-    let frame_data = GXI_FRAME_DATA.lock_safe(MutexType::FrameData)?.as_ref()
-        ..ok_or(Error::new(ErrorKind::FrameDataError(
-            "Frame data is None. Please check your get image situation.".to_string(),
-        )))?
-        .frame_data;
-
-    if frame_data.nStatus == 0 {
-        if let Some(data) = extract_image_data(&frame_data) {
-            let img = ImageBuffer::<Luma<u8>, _>::from_raw(
-                frame_data.nWidth as u32,
-                frame_data.nHeight as u32,
-                data,
-            )
-            .expect("Failed to create image buffer");
-
-            if img.save(filename).is_ok() {
-                println!("Image saved successfully.");
-            } else {
-                println!("Failed to save the image.");
-            }
-        } else {
-            println!("Failed to extract image data.");
-        }
+/// Save the last image taken with `gxi_get_image` as PNG, see `save_frame`.
+#[cfg(feature = "solo")]
+pub fn gxi_save_image_as_png<P: AsRef<Path>>(filename: P) -> Result<()> {
+    let frame = GxiFrame::from_frame_data(
+        &GXI_FRAME_DATA
+            .lock_safe(MutexType::FrameData)?
+            .as_ref()
+            .ok_or(Error::new(ErrorKind::FrameDataError(
+                "Frame data is None. Please check your get image situation.".to_string(),
+            )))?
+            .frame_data,
+    );
+    if frame.status != 0 {
+        return Err(Error::new(ErrorKind::FrameDataError(format!(
+            "Frame status is {}, the image is incomplete.",
+            frame.status
+        ))));
     }
-    Ok(())
+    save_frame(&frame, filename, SaveFormat::Png)
 }
 
 //---------------Callback Fn-------------------------------
@@ -1 +1,122 @@
-//! Placeholder for future development in ^0.4.0 or later.
+//! Device events (exposure end, over trigger ...) and the feature callbacks they are delivered through.
+
+use crate::error::Result;
+use crate::hal::base::gxi_check;
+use crate::hal::check::check_gx_status;
+use crate::hal::config::*;
+use crate::hal::device::gxi_get_device_handle;
+use crate::raw::gx_callback::GXFeatureCallBack;
+use crate::raw::gx_enum::{GX_EVENT_NOTIFICATION_ENTRY, GX_EVENT_SELECTOR_ENTRY, GX_FEATURE_ID};
+use crate::raw::gx_handle::GX_FEATURE_CALLBACK_HANDLE;
+
+use std::ptr::null_mut;
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_event_selector() -> Result<i64> {
+    let event_selector: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_EVENT_SELECTOR)?;
+    println!("Now, event selector is {}", event_selector);
+    Ok(event_selector)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_event_selector(event_selector: GX_EVENT_SELECTOR_ENTRY) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_EVENT_SELECTOR,
+        &(event_selector as i64),
+    )?;
+    println!("Now, event selector is {:?}", event_selector);
+    Ok(())
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_get_event_notification() -> Result<i64> {
+    let event_notification: i64 = gxi_get_feature_value(GX_FEATURE_ID::GX_ENUM_EVENT_NOTIFICATION)?;
+    println!("Now, event notification is {}", event_notification);
+    Ok(event_notification)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_set_event_notification(event_notification: GX_EVENT_NOTIFICATION_ENTRY) -> Result<()> {
+    gxi_set_feature_value(
+        GX_FEATURE_ID::GX_ENUM_EVENT_NOTIFICATION,
+        &(event_notification as i64),
+    )?;
+    println!("Now, event notification is {:?}", event_notification);
+    Ok(())
+}
+
+/// Select the event and switch its notification on.
+#[cfg(feature = "solo")]
+pub fn gxi_enable_event(event_selector: GX_EVENT_SELECTOR_ENTRY) -> Result<()> {
+    gxi_set_event_selector(event_selector)?;
+    gxi_set_event_notification(GX_EVENT_NOTIFICATION_ENTRY::GX_ENUM_EVENT_NOTIFICATION_ON)
+}
+
+/// Select the event and switch its notification off.
+#[cfg(feature = "solo")]
+pub fn gxi_disable_event(event_selector: GX_EVENT_SELECTOR_ENTRY) -> Result<()> {
+    gxi_set_event_selector(event_selector)?;
+    gxi_set_event_notification(GX_EVENT_NOTIFICATION_ENTRY::GX_ENUM_EVENT_NOTIFICATION_OFF)
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_flush_event() -> Result<()> {
+    let gxi_device = gxi_get_device_handle()?;
+    let status = gxi_check(|gxi| gxi.gx_flush_event(gxi_device))?;
+
+    check_gx_status(status)?;
+    println!("Successfully flushed event queue");
+    Ok(())
+}
+
+/// A registered feature callback, give it back to `gxi_unregister_feature_callback` to remove it.
+#[derive(Debug)]
+pub struct GxiFeatureCallback {
+    pub feature_id: GX_FEATURE_ID,
+    handle: GX_FEATURE_CALLBACK_HANDLE,
+}
+
+// 句柄只是SDK内部的标识，不会在Rust这边解引用
+unsafe impl Send for GxiFeatureCallback {}
+
+/// Register a callback that the SDK calls when the event data feature `feature_id` is updated.
+///
+/// The callback runs on the SDK event thread. It should not call back into the `gxi_*`
+/// functions, since unregistering waits for it while holding the instance lock.
+#[cfg(feature = "solo")]
+pub fn gxi_register_feature_callback(
+    feature_id: GX_FEATURE_ID,
+    callback: GXFeatureCallBack,
+) -> Result<GxiFeatureCallback> {
+    let gxi_device = gxi_get_device_handle()?;
+    let mut handle: GX_FEATURE_CALLBACK_HANDLE = null_mut();
+    let status = gxi_check(|gxi| {
+        gxi.gx_register_feature_callback(gxi_device, null_mut(), callback, feature_id, &mut handle)
+    })?;
+
+    check_gx_status(status)?;
+    println!(
+        "Successfully registered feature callback for {:?}",
+        feature_id
+    );
+    Ok(GxiFeatureCallback { feature_id, handle })
+}
+
+#[cfg(feature = "solo")]
+pub fn gxi_unregister_feature_callback(feature_callback: GxiFeatureCallback) -> Result<()> {
+    let gxi_device = gxi_get_device_handle()?;
+    let status = gxi_check(|gxi| {
+        gxi.gx_unregister_feature_callback(
+            gxi_device,
+            feature_callback.feature_id,
+            feature_callback.handle,
+        )
+    })?;
+
+    check_gx_status(status)?;
+    println!(
+        "Successfully unregistered feature callback for {:?}",
+        feature_callback.feature_id
+    );
+    Ok(())
+}
@@ -0,0 +1,769 @@
+//! Frame conversion to `image`, `ndarray` and OpenCV types, and encoding with the `image` crate so it works without OpenCV.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::hal::device::GxiFrame;
+use crate::raw::gx_pixel_format::PixelFormatEntry;
+
+use image::{DynamicImage, ImageBuffer, ImageFormat, Luma, Rgb, Rgba, codecs::jpeg::JpegEncoder};
+#[cfg(feature = "use-ndarray")]
+use ndarray::{Array3, ArrayView3};
+#[cfg(feature = "use-opencv")]
+use opencv::{
+    boxed_ref::BoxedRef,
+    core::{Mat, MatTraitConst, Vec3b, Vec3w, Vec4b},
+};
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+/// File formats `save_frame` writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveFormat {
+    /// 8 and 16 bit, lossless.
+    Png,
+    /// 8 and 16 bit, lossless.
+    Tiff,
+    /// 8 bit only, lossy with the given quality from 1 to 100.
+    Jpeg(u8),
+}
+
+impl SaveFormat {
+    /// Pick the format from the file extension, JPEG at quality 90.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        match ImageFormat::from_path(path) {
+            Ok(ImageFormat::Png) => Ok(SaveFormat::Png),
+            Ok(ImageFormat::Tiff) => Ok(SaveFormat::Tiff),
+            Ok(ImageFormat::Jpeg) => Ok(SaveFormat::Jpeg(90)),
+            _ => Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "cannot tell PNG, TIFF or JPEG from the path {:?}",
+                path
+            )))),
+        }
+    }
+}
+
+//----------------------------------------------------------
+//---------------Sample Layout------------------------------
+//----------------------------------------------------------
+
+/// How the samples of a pixel format sit in the frame buffer, for the conversions below.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleLayout {
+    /// 1 for 8 bit formats, 2 for deeper ones, which are little endian `u16`s.
+    pub sample_bytes: usize,
+    /// Samples per pixel, raw Bayer formats have 1.
+    pub channels: usize,
+    /// The colour channels are in blue, green, red order.
+    pub bgr: bool,
+    /// Two pixels share 3 bytes, see `PixelFormatEntry::is_packed`.
+    pub packed: bool,
+}
+
+impl SampleLayout {
+    /// The layout of `format`, interleaved mono, Bayer, RGB, BGR, RGBA and BGRA only.
+    pub fn of(format: PixelFormatEntry) -> Result<Self> {
+        use PixelFormatEntry::*;
+
+        let (sample_bytes, channels) = match format {
+            Mono8 | BayerGr8 | BayerRg8 | BayerGb8 | BayerBg8 => (1, 1),
+            Mono10 | Mono12 | Mono14 | Mono16 | BayerGr10 | BayerRg10 | BayerGb10 | BayerBg10
+            | BayerGr12 | BayerRg12 | BayerGb12 | BayerBg12 | BayerGr16 | BayerRg16 | BayerGb16
+            | BayerBg16 | Mono10Packed | Mono12Packed => (2, 1),
+            Rgb8 | Bgr8 => (1, 3),
+            Rgb10 | Rgb12 | Rgb14 | Rgb16 | Bgr10 | Bgr12 | Bgr14 | Bgr16 => (2, 3),
+            Rgba8 | Bgra8 => (1, 4),
+            _ => {
+                return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                    "converting {} frames is not supported",
+                    format
+                ))));
+            }
+        };
+        Ok(SampleLayout {
+            sample_bytes,
+            channels,
+            bgr: matches!(format, Bgr8 | Bgr10 | Bgr12 | Bgr14 | Bgr16 | Bgra8),
+            packed: format.is_packed(),
+        })
+    }
+}
+
+fn u16_samples(data: &[u8]) -> Vec<u16> {
+    data.chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect()
+}
+
+// GigE的Mono10Packed/Mono12Packed：两个像素占3个字节，中间字节的低4位和高4位分别是两个像素的低位
+fn unpack_mono_packed(data: &[u8], pixels: usize, bits: u32) -> Vec<u16> {
+    let low_bits = bits - 8;
+    let low_mask = (1u16 << low_bits) - 1;
+    let mut samples = Vec::with_capacity(pixels + 1);
+    for group in data.chunks(3) {
+        let byte = |index: usize| group.get(index).copied().unwrap_or(0) as u16;
+        let (high0, middle, high1) = (byte(0), byte(1), byte(2));
+        samples.push((high0 << low_bits) | (middle & low_mask));
+        samples.push((high1 << low_bits) | ((middle >> 4) & low_mask));
+    }
+    samples.truncate(pixels);
+    samples
+}
+
+fn swap_red_blue<T: Copy>(samples: &mut [T], channels: usize) {
+    for pixel in samples.chunks_exact_mut(channels) {
+        pixel.swap(0, 2);
+    }
+}
+
+/// The format a frame of `format` has once its samples are unpacked, Mono10Packed and
+/// Mono12Packed become Mono10 and Mono12, the rest stay as they are.
+pub(crate) fn unpacked_format(format: PixelFormatEntry) -> PixelFormatEntry {
+    match format {
+        PixelFormatEntry::Mono10Packed => PixelFormatEntry::Mono10,
+        PixelFormatEntry::Mono12Packed => PixelFormatEntry::Mono12,
+        format => format,
+    }
+}
+
+fn sample_type_error(format: PixelFormatEntry, bits: usize) -> Error {
+    Error::new(ErrorKind::InvalidParameter(format!(
+        "{} frames do not have {} bit samples",
+        format, bits
+    )))
+}
+
+impl GxiFrame {
+    fn width_height(&self) -> (usize, usize) {
+        (self.width.max(0) as usize, self.height.max(0) as usize)
+    }
+
+    /// Format, layout and the image part of the buffer, which is followed by the chunk
+    /// data when chunks are on.
+    fn image_bytes(&self) -> Result<(PixelFormatEntry, SampleLayout, &[u8])> {
+        let format = self.pixel_format_entry()?;
+        let layout = SampleLayout::of(format)?;
+        let (width, height) = self.width_height();
+        let data = self
+            .data
+            .get(..format.image_size(width, height))
+            .ok_or_else(|| {
+                Error::new(ErrorKind::FrameDataError(format!(
+                    "{} byte buffer is too small for a {}x{} {} frame",
+                    self.data.len(),
+                    self.width,
+                    self.height,
+                    format
+                )))
+            })?;
+        Ok((format, layout, data))
+    }
+
+    /// The samples of an 8 bit frame, borrowed from the buffer in buffer channel order.
+    pub fn samples_u8(&self) -> Result<&[u8]> {
+        let (format, layout, data) = self.image_bytes()?;
+        if layout.sample_bytes != 1 {
+            return Err(sample_type_error(format, 8));
+        }
+        Ok(data)
+    }
+
+    /// The samples of a 10 to 16 bit frame in buffer channel order, packed formats unpacked.
+    /// Values are the sensor values, a 12 bit frame uses 0 to 4095.
+    pub fn samples_u16(&self) -> Result<Vec<u16>> {
+        let (format, layout, data) = self.image_bytes()?;
+        if layout.sample_bytes != 2 {
+            return Err(sample_type_error(format, 16));
+        }
+        if layout.packed {
+            let (width, height) = self.width_height();
+            Ok(unpack_mono_packed(data, width * height, format.bit_depth()))
+        } else {
+            Ok(u16_samples(data))
+        }
+    }
+}
+
+//----------------------------------------------------------
+//---------------Image Conversion---------------------------
+//----------------------------------------------------------
+
+fn dynamic_image_from_u8(
+    layout: SampleLayout,
+    width: u32,
+    height: u32,
+    mut samples: Vec<u8>,
+) -> Option<DynamicImage> {
+    if layout.bgr {
+        swap_red_blue(&mut samples, layout.channels);
+    }
+    match layout.channels {
+        1 => ImageBuffer::<Luma<u8>, _>::from_raw(width, height, samples)
+            .map(DynamicImage::ImageLuma8),
+        3 => {
+            ImageBuffer::<Rgb<u8>, _>::from_raw(width, height, samples).map(DynamicImage::ImageRgb8)
+        }
+        _ => ImageBuffer::<Rgba<u8>, _>::from_raw(width, height, samples)
+            .map(DynamicImage::ImageRgba8),
+    }
+}
+
+fn dynamic_image_from_u16(
+    layout: SampleLayout,
+    width: u32,
+    height: u32,
+    mut samples: Vec<u16>,
+) -> Option<DynamicImage> {
+    if layout.bgr {
+        swap_red_blue(&mut samples, layout.channels);
+    }
+    match layout.channels {
+        1 => ImageBuffer::<Luma<u16>, _>::from_raw(width, height, samples)
+            .map(DynamicImage::ImageLuma16),
+        _ => ImageBuffer::<Rgb<u16>, _>::from_raw(width, height, samples)
+            .map(DynamicImage::ImageRgb16),
+    }
+}
+
+fn image_shape_error(frame: &GxiFrame) -> Error {
+    Error::new(ErrorKind::FrameDataError(format!(
+        "samples do not fill a {}x{} image",
+        frame.width, frame.height
+    )))
+}
+
+impl GxiFrame {
+    /// The frame as an `image` image, without losing bit depth.
+    ///
+    /// Mono and raw Bayer frames become `Luma8`/`Luma16`, RGB and BGR frames `Rgb8`/`Rgb16`
+    /// and RGBA/BGRA frames `Rgba8`. Formats with more than 8 bits keep the sensor values,
+    /// so a 12 bit frame uses 0 to 4095 of the 16 bit range. Bayer frames are not demosaiced.
+    pub fn to_dynamic_image(&self) -> Result<DynamicImage> {
+        DynamicImage::try_from(self)
+    }
+}
+
+impl TryFrom<&GxiFrame> for DynamicImage {
+    type Error = Error;
+
+    fn try_from(frame: &GxiFrame) -> Result<Self> {
+        let (_, layout, data) = frame.image_bytes()?;
+        let (width, height) = (frame.width as u32, frame.height as u32);
+        let image = if layout.sample_bytes == 1 {
+            dynamic_image_from_u8(layout, width, height, data.to_vec())
+        } else {
+            dynamic_image_from_u16(layout, width, height, frame.samples_u16()?)
+        };
+        image.ok_or_else(|| image_shape_error(frame))
+    }
+}
+
+/// Takes over the frame buffer for 8 bit formats instead of copying it.
+impl TryFrom<GxiFrame> for DynamicImage {
+    type Error = Error;
+
+    fn try_from(mut frame: GxiFrame) -> Result<Self> {
+        let (_, layout, data) = frame.image_bytes()?;
+        if layout.sample_bytes != 1 {
+            return DynamicImage::try_from(&frame);
+        }
+        let size = data.len();
+        let (width, height) = (frame.width as u32, frame.height as u32);
+        let mut samples = std::mem::take(&mut frame.data);
+        samples.truncate(size);
+        dynamic_image_from_u8(layout, width, height, samples)
+            .ok_or_else(|| image_shape_error(&frame))
+    }
+}
+
+fn u16_bytes(samples: &[u16]) -> Vec<u8> {
+    samples
+        .iter()
+        .flat_map(|sample| sample.to_le_bytes())
+        .collect()
+}
+
+impl GxiFrame {
+    /// The reverse of `to_dynamic_image`, for images written by `save_frame` or a `Recorder`.
+    ///
+    /// The frame gets `pixel_format` when the image has its sample size and channel count,
+    /// otherwise Mono8, Mono16, RGB8, RGB16 or RGBa8 after the image. Packed formats come
+    /// back unpacked as Mono10 or Mono12. Frame ID and timestamp are 0.
+    pub fn from_dynamic_image(
+        image: &DynamicImage,
+        pixel_format: Option<PixelFormatEntry>,
+    ) -> Result<Self> {
+        use PixelFormatEntry::*;
+
+        let fitting = |sample_bytes: usize, channels: usize, default: PixelFormatEntry| {
+            pixel_format
+                .and_then(|format| Some((format, SampleLayout::of(format).ok()?)))
+                .filter(|(_, layout)| {
+                    layout.sample_bytes == sample_bytes && layout.channels == channels
+                })
+                .map(|(format, layout)| (unpacked_format(format), layout.bgr))
+                .unwrap_or((default, false))
+        };
+
+        let (format, data) = match image {
+            DynamicImage::ImageLuma8(buffer) => (fitting(1, 1, Mono8).0, buffer.as_raw().clone()),
+            DynamicImage::ImageLuma16(buffer) => {
+                (fitting(2, 1, Mono16).0, u16_bytes(buffer.as_raw()))
+            }
+            DynamicImage::ImageRgb8(buffer) => {
+                let (format, bgr) = fitting(1, 3, Rgb8);
+                let mut samples = buffer.as_raw().clone();
+                if bgr {
+                    swap_red_blue(&mut samples, 3);
+                }
+                (format, samples)
+            }
+            DynamicImage::ImageRgb16(buffer) => {
+                let (format, bgr) = fitting(2, 3, Rgb16);
+                let mut samples = buffer.as_raw().clone();
+                if bgr {
+                    swap_red_blue(&mut samples, 3);
+                }
+                (format, u16_bytes(&samples))
+            }
+            DynamicImage::ImageRgba8(buffer) => {
+                let (format, bgr) = fitting(1, 4, Rgba8);
+                let mut samples = buffer.as_raw().clone();
+                if bgr {
+                    swap_red_blue(&mut samples, 4);
+                }
+                (format, samples)
+            }
+            // 其余的类型save_frame不会写出来，转成最接近的一种
+            DynamicImage::ImageLumaA8(_) => {
+                return Self::from_dynamic_image(
+                    &DynamicImage::ImageLuma8(image.to_luma8()),
+                    pixel_format,
+                );
+            }
+            DynamicImage::ImageLumaA16(_) => {
+                return Self::from_dynamic_image(
+                    &DynamicImage::ImageLuma16(image.to_luma16()),
+                    pixel_format,
+                );
+            }
+            image if image.color().bytes_per_pixel() > image.color().channel_count() => {
+                return Self::from_dynamic_image(
+                    &DynamicImage::ImageRgb16(image.to_rgb16()),
+                    pixel_format,
+                );
+            }
+            image => {
+                return Self::from_dynamic_image(
+                    &DynamicImage::ImageRgb8(image.to_rgb8()),
+                    pixel_format,
+                );
+            }
+        };
+
+        Ok(GxiFrame {
+            status: 0,
+            width: image.width() as i32,
+            height: image.height() as i32,
+            pixel_format: format as i32,
+            frame_id: 0,
+            timestamp: 0,
+            data,
+        })
+    }
+}
+
+//----------------------------------------------------------
+//---------------ndarray Conversion-------------------------
+//----------------------------------------------------------
+
+// 形状都是(高, 宽, 通道)，通道顺序和缓冲区里一样，BGR格式还是BGR，见SampleLayout::bgr
+
+#[cfg(feature = "use-ndarray")]
+fn ndarray_shape_error(e: ndarray::ShapeError) -> Error {
+    Error::new(ErrorKind::FrameDataError(format!(
+        "samples do not fit the frame shape: {}",
+        e
+    )))
+}
+
+/// Borrows the buffer of an 8 bit frame, shape `(height, width, channels)`.
+#[cfg(feature = "use-ndarray")]
+impl<'a> TryFrom<&'a GxiFrame> for ArrayView3<'a, u8> {
+    type Error = Error;
+
+    fn try_from(frame: &'a GxiFrame) -> Result<Self> {
+        let (_, layout, _) = frame.image_bytes()?;
+        let (width, height) = frame.width_height();
+        ArrayView3::from_shape((height, width, layout.channels), frame.samples_u8()?)
+            .map_err(ndarray_shape_error)
+    }
+}
+
+#[cfg(feature = "use-ndarray")]
+impl TryFrom<&GxiFrame> for Array3<u8> {
+    type Error = Error;
+
+    fn try_from(frame: &GxiFrame) -> Result<Self> {
+        Ok(ArrayView3::<u8>::try_from(frame)?.to_owned())
+    }
+}
+
+/// Takes over the frame buffer instead of copying it.
+#[cfg(feature = "use-ndarray")]
+impl TryFrom<GxiFrame> for Array3<u8> {
+    type Error = Error;
+
+    fn try_from(mut frame: GxiFrame) -> Result<Self> {
+        let (_, layout, data) = frame.image_bytes()?;
+        let size = data.len();
+        frame.samples_u8()?;
+        let (width, height) = frame.width_height();
+        let mut samples = std::mem::take(&mut frame.data);
+        samples.truncate(size);
+        Array3::from_shape_vec((height, width, layout.channels), samples)
+            .map_err(ndarray_shape_error)
+    }
+}
+
+#[cfg(feature = "use-ndarray")]
+impl TryFrom<&GxiFrame> for Array3<u16> {
+    type Error = Error;
+
+    fn try_from(frame: &GxiFrame) -> Result<Self> {
+        let (_, layout, _) = frame.image_bytes()?;
+        let (width, height) = frame.width_height();
+        Array3::from_shape_vec((height, width, layout.channels), frame.samples_u16()?)
+            .map_err(ndarray_shape_error)
+    }
+}
+
+//----------------------------------------------------------
+//---------------OpenCV Conversion--------------------------
+//----------------------------------------------------------
+
+#[cfg(feature = "use-opencv")]
+fn opencv_error(e: opencv::Error) -> Error {
+    Error::new(ErrorKind::FrameDataError(format!("OpenCV: {:?}", e)))
+}
+
+#[cfg(feature = "use-opencv")]
+fn mat_from_bytes(
+    layout: SampleLayout,
+    rows: i32,
+    cols: i32,
+    data: &[u8],
+) -> Result<BoxedRef<'_, Mat>> {
+    match (layout.sample_bytes, layout.channels) {
+        (1, 1) => Mat::new_rows_cols_with_bytes::<u8>(rows, cols, data),
+        (1, 3) => Mat::new_rows_cols_with_bytes::<Vec3b>(rows, cols, data),
+        (1, _) => Mat::new_rows_cols_with_bytes::<Vec4b>(rows, cols, data),
+        (_, 1) => Mat::new_rows_cols_with_bytes::<u16>(rows, cols, data),
+        _ => Mat::new_rows_cols_with_bytes::<Vec3w>(rows, cols, data),
+    }
+    .map_err(opencv_error)
+}
+
+#[cfg(feature = "use-opencv")]
+impl GxiFrame {
+    /// A `Mat` over the frame buffer without copying, `CV_8UC1/3/4` or `CV_16UC1/3` by the
+    /// pixel format. Channels are in buffer order, so RGB frames are not BGR here, and
+    /// packed formats are not supported since they need unpacking.
+    pub fn as_mat(&self) -> Result<BoxedRef<'_, Mat>> {
+        let (format, layout, data) = self.image_bytes()?;
+        if layout.packed {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "{} frames need unpacking, convert them with Mat::try_from",
+                format
+            ))));
+        }
+        mat_from_bytes(layout, self.height, self.width, data)
+    }
+}
+
+/// An owned `Mat` in OpenCV's BGR channel order, packed formats unpacked to `CV_16UC1`.
+#[cfg(feature = "use-opencv")]
+impl TryFrom<&GxiFrame> for Mat {
+    type Error = Error;
+
+    fn try_from(frame: &GxiFrame) -> Result<Self> {
+        let (_, layout, data) = frame.image_bytes()?;
+        let color = layout.channels > 1;
+        if !layout.packed && (layout.bgr || !color) {
+            return frame.as_mat()?.try_clone().map_err(opencv_error);
+        }
+
+        // RGB要换成BGR，打包格式要先解包，这两种情况只能先复制出来再建Mat
+        let bytes = if layout.sample_bytes == 1 {
+            let mut samples = data.to_vec();
+            swap_red_blue(&mut samples, layout.channels);
+            samples
+        } else {
+            let mut samples = frame.samples_u16()?;
+            if color {
+                swap_red_blue(&mut samples, layout.channels);
+            }
+            samples
+                .iter()
+                .flat_map(|sample| sample.to_ne_bytes())
+                .collect()
+        };
+        let layout = SampleLayout {
+            packed: false,
+            ..layout
+        };
+        mat_from_bytes(layout, frame.height, frame.width, &bytes)?
+            .try_clone()
+            .map_err(opencv_error)
+    }
+}
+
+//----------------------------------------------------------
+//---------------Frame Encoding-----------------------------
+//----------------------------------------------------------
+
+/// Write `frame` to `path` as PNG, TIFF or JPEG.
+///
+/// PNG and TIFF keep 16 bit frames at 16 bit, see `GxiFrame::to_dynamic_image`. JPEG only
+/// takes 8 bit frames and fails on deeper ones instead of silently dropping bits.
+pub fn save_frame<P: AsRef<Path>>(frame: &GxiFrame, path: P, format: SaveFormat) -> Result<()> {
+    let path = path.as_ref();
+    let image = frame.to_dynamic_image()?;
+
+    match format {
+        SaveFormat::Png => image.save_with_format(path, ImageFormat::Png)?,
+        SaveFormat::Tiff => image.save_with_format(path, ImageFormat::Tiff)?,
+        SaveFormat::Jpeg(quality) => {
+            if image.color().bytes_per_pixel() > image.color().channel_count() {
+                return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                    "JPEG has no {:?} support, save it as PNG or TIFF",
+                    image.color()
+                ))));
+            }
+            let writer = BufWriter::new(File::create(path)?);
+            // JPEG没有透明通道，RGBA要先去掉
+            let image = match image {
+                DynamicImage::ImageRgba8(_) => DynamicImage::ImageRgb8(image.to_rgb8()),
+                image => image,
+            };
+            image
+                .write_with_encoder(JpegEncoder::new_with_quality(writer, quality.clamp(1, 100)))?;
+        }
+    }
+    println!("Successfully saved frame to {:?}", path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(format: PixelFormatEntry, width: i32, height: i32, data: Vec<u8>) -> GxiFrame {
+        GxiFrame {
+            status: 0,
+            width,
+            height,
+            pixel_format: format as i32,
+            frame_id: 0,
+            timestamp: 0,
+            data,
+        }
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("gxci-frame-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn save_format_follows_the_extension() {
+        assert_eq!(SaveFormat::from_path("a.png").unwrap(), SaveFormat::Png);
+        assert_eq!(SaveFormat::from_path("a.tif").unwrap(), SaveFormat::Tiff);
+        assert_eq!(
+            SaveFormat::from_path("a.jpg").unwrap(),
+            SaveFormat::Jpeg(90)
+        );
+        assert!(SaveFormat::from_path("a.bmp").is_err());
+    }
+
+    #[test]
+    fn png_and_tiff_keep_12_bit_values() {
+        let samples: [u16; 4] = [0, 1, 2048, 4095];
+        let mono12 = frame(PixelFormatEntry::Mono12, 2, 2, u16_bytes(&samples));
+        for (format, name) in [
+            (SaveFormat::Png, "mono12.png"),
+            (SaveFormat::Tiff, "mono12.tif"),
+        ] {
+            let path = temp_path(name);
+            save_frame(&mono12, &path, format).unwrap();
+            let image = image::open(&path).unwrap();
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(image.as_luma16().unwrap().as_raw(), &samples);
+        }
+    }
+
+    #[test]
+    fn jpeg_rejects_16_bit_frames() {
+        let path = temp_path("mono16.jpg");
+        let mono16 = frame(PixelFormatEntry::Mono16, 2, 1, u16_bytes(&[0, 65535]));
+        let error = save_frame(&mono16, &path, SaveFormat::Jpeg(90)).unwrap_err();
+        assert!(matches!(*error.inner, ErrorKind::InvalidParameter(_)));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn jpeg_writes_8_bit_frames() {
+        let path = temp_path("bgra8.jpg");
+        let bgra8 = frame(
+            PixelFormatEntry::Bgra8,
+            2,
+            1,
+            vec![10, 20, 30, 255, 40, 50, 60, 255],
+        );
+        save_frame(&bgra8, &path, SaveFormat::Jpeg(100)).unwrap();
+        let image = image::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!((image.width(), image.height()), (2, 1));
+        assert!(image.as_rgb8().is_some());
+    }
+
+    #[test]
+    fn sample_layout_maps_formats_to_element_types() {
+        use PixelFormatEntry::*;
+
+        let layout = |format| {
+            let layout = SampleLayout::of(format).unwrap();
+            (
+                layout.sample_bytes,
+                layout.channels,
+                layout.bgr,
+                layout.packed,
+            )
+        };
+        assert_eq!(layout(Mono8), (1, 1, false, false));
+        assert_eq!(layout(BayerRg8), (1, 1, false, false));
+        assert_eq!(layout(Mono12), (2, 1, false, false));
+        assert_eq!(layout(Mono12Packed), (2, 1, false, true));
+        assert_eq!(layout(Rgb8), (1, 3, false, false));
+        assert_eq!(layout(Bgr12), (2, 3, true, false));
+        assert_eq!(layout(Bgra8), (1, 4, true, false));
+        assert!(SampleLayout::of(Yuv422_8).is_err());
+    }
+
+    #[test]
+    fn unpacks_mono_packed_pairs() {
+        let data = [0xAB, 0x21, 0xCD];
+        assert_eq!(unpack_mono_packed(&data, 2, 12), vec![0xAB1, 0xCD2]);
+        assert_eq!(unpack_mono_packed(&data, 2, 10), vec![0x2AD, 0x336]);
+        assert_eq!(unpack_mono_packed(&data, 1, 12), vec![0xAB1]);
+    }
+
+    #[test]
+    fn samples_u16_unpacks_an_odd_pixel_count() {
+        let mono12_packed = frame(
+            PixelFormatEntry::Mono12Packed,
+            3,
+            1,
+            vec![0xAB, 0x21, 0xCD, 0xEF, 0x05],
+        );
+        assert_eq!(
+            mono12_packed.samples_u16().unwrap(),
+            vec![0xAB1, 0xCD2, 0xEF5]
+        );
+    }
+
+    #[test]
+    fn samples_are_little_endian_and_ignore_trailing_chunk_data() {
+        let mut data = u16_bytes(&[0x0102, 0x0FFF]);
+        data.extend_from_slice(&[0xEE; 8]);
+        let mono12 = frame(PixelFormatEntry::Mono12, 2, 1, data);
+        assert_eq!(mono12.samples_u16().unwrap(), vec![0x0102, 0x0FFF]);
+        assert!(mono12.samples_u8().is_err());
+
+        let mono8 = frame(PixelFormatEntry::Mono8, 2, 1, vec![1, 2, 3]);
+        assert_eq!(mono8.samples_u8().unwrap(), &[1, 2]);
+        assert!(mono8.samples_u16().is_err());
+    }
+
+    #[test]
+    fn short_buffer_is_a_frame_data_error() {
+        let mono16 = frame(PixelFormatEntry::Mono16, 2, 2, vec![0; 7]);
+        let error = mono16.samples_u16().unwrap_err();
+        assert!(matches!(*error.inner, ErrorKind::FrameDataError(_)));
+    }
+
+    #[test]
+    fn bgr_frames_become_rgb_images_and_back() {
+        let bgr8 = frame(PixelFormatEntry::Bgr8, 2, 1, vec![1, 2, 3, 4, 5, 6]);
+        let image = bgr8.to_dynamic_image().unwrap();
+        assert_eq!(image.as_rgb8().unwrap().as_raw(), &[3, 2, 1, 6, 5, 4]);
+        let owned = DynamicImage::try_from(bgr8.clone()).unwrap();
+        assert_eq!(owned.as_rgb8().unwrap().as_raw(), &[3, 2, 1, 6, 5, 4]);
+
+        let back = GxiFrame::from_dynamic_image(&image, Some(PixelFormatEntry::Bgr8)).unwrap();
+        assert_eq!(back.pixel_format, PixelFormatEntry::Bgr8 as i32);
+        assert_eq!(back.data, bgr8.data);
+
+        let bgr16 = frame(PixelFormatEntry::Bgr16, 1, 1, u16_bytes(&[100, 200, 300]));
+        let image = bgr16.to_dynamic_image().unwrap();
+        assert_eq!(image.as_rgb16().unwrap().as_raw(), &[300, 200, 100]);
+        let back = GxiFrame::from_dynamic_image(&image, Some(PixelFormatEntry::Bgr16)).unwrap();
+        assert_eq!(back.data, bgr16.data);
+    }
+
+    #[test]
+    fn dynamic_image_round_trip_keeps_bit_depth() {
+        let mono12 = frame(PixelFormatEntry::Mono12, 2, 1, u16_bytes(&[7, 4095]));
+        let image = mono12.to_dynamic_image().unwrap();
+        assert_eq!(image.as_luma16().unwrap().as_raw(), &[7, 4095]);
+        let back = GxiFrame::from_dynamic_image(&image, Some(PixelFormatEntry::Mono12)).unwrap();
+        assert_eq!((back.width, back.height), (2, 1));
+        assert_eq!(back.pixel_format, PixelFormatEntry::Mono12 as i32);
+        assert_eq!(back.data, mono12.data);
+
+        let packed = frame(PixelFormatEntry::Mono12Packed, 2, 1, vec![0xAB, 0x21, 0xCD]);
+        let image = packed.to_dynamic_image().unwrap();
+        let back =
+            GxiFrame::from_dynamic_image(&image, Some(PixelFormatEntry::Mono12Packed)).unwrap();
+        assert_eq!(back.pixel_format, PixelFormatEntry::Mono12 as i32);
+        assert_eq!(back.samples_u16().unwrap(), vec![0xAB1, 0xCD2]);
+
+        let rgba8 = frame(PixelFormatEntry::Rgba8, 1, 1, vec![1, 2, 3, 4]);
+        let back = GxiFrame::from_dynamic_image(&rgba8.to_dynamic_image().unwrap(), None).unwrap();
+        assert_eq!(back.pixel_format, PixelFormatEntry::Rgba8 as i32);
+        assert_eq!(back.data, rgba8.data);
+    }
+
+    #[test]
+    fn from_dynamic_image_ignores_a_format_that_does_not_fit() {
+        let mono8 = frame(PixelFormatEntry::Mono8, 1, 1, vec![9]);
+        let image = mono8.to_dynamic_image().unwrap();
+        let back = GxiFrame::from_dynamic_image(&image, Some(PixelFormatEntry::Rgb16)).unwrap();
+        assert_eq!(back.pixel_format, PixelFormatEntry::Mono8 as i32);
+        assert_eq!(back.data, vec![9]);
+    }
+
+    #[cfg(feature = "use-ndarray")]
+    #[test]
+    fn ndarray_shapes_are_height_width_channels() {
+        let data: Vec<u8> = (0..18).collect();
+        let rgb8 = frame(PixelFormatEntry::Rgb8, 3, 2, data.clone());
+        let view = ArrayView3::<u8>::try_from(&rgb8).unwrap();
+        assert_eq!(view.shape(), &[2, 3, 3]);
+        assert_eq!(view.as_ptr(), rgb8.data.as_ptr());
+        assert_eq!(view[[1, 2, 0]], 15);
+
+        let owned = Array3::<u8>::try_from(rgb8).unwrap();
+        assert_eq!(owned.shape(), &[2, 3, 3]);
+        assert_eq!(owned.as_slice().unwrap(), data.as_slice());
+
+        let mut mono12_data = u16_bytes(&[1, 2, 3, 4]);
+        mono12_data.extend_from_slice(&[0xEE; 4]);
+        let mono12 = frame(PixelFormatEntry::Mono12, 2, 2, mono12_data);
+        let samples = Array3::<u16>::try_from(&mono12).unwrap();
+        assert_eq!(samples.shape(), &[2, 2, 1]);
+        assert_eq!(samples[[1, 0, 0]], 3);
+        assert!(Array3::<u8>::try_from(&mono12).is_err());
+        assert!(Array3::<u8>::try_from(mono12).is_err());
+    }
+}
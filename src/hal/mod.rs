@@ -2,7 +2,12 @@
 pub mod base;
 pub mod check;
 pub mod config;
+pub mod config_file;
 pub mod control;
 pub mod device;
 pub mod event;
+pub mod frame;
 pub mod network;
+pub mod playback;
+pub mod recorder;
+pub mod snapshot;
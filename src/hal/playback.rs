@@ -0,0 +1,1578 @@
+//! Playback of recordings as virtual cameras, a `GXInterface` backend that serves the frames of a `Recorder` directory, a raw container or a plain image directory through the normal device, `gx_get_image` and callback paths.
+// GXInterface照搬C接口传裸指针，和GXInstance一样在方法里解引用
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::hal::base::gxci_init_with;
+use crate::hal::device::GxiFrame;
+use crate::hal::frame::unpacked_format;
+use crate::hal::recorder::{FrameRecord, RAW_FILE, RAW_MAGIC, RawFrameHeader, SIDECAR_FILE};
+use crate::raw::gx_pixel_format::PixelFormatEntry;
+use crate::raw::{
+    gx_callback::*, gx_enum::*, gx_handle::*, gx_interface::GXInterface, gx_struct::*,
+};
+
+use std::ffi::{CStr, c_char, c_void};
+use std::fs::{File, read_dir};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{JoinHandle, sleep, spawn};
+use std::time::{Duration, Instant};
+
+/// Playback devices report this vendor name.
+pub const PLAYBACK_VENDOR_NAME: &str = "gxci";
+/// Playback devices report this model name.
+pub const PLAYBACK_MODEL_NAME: &str = "Playback";
+/// Serial numbers of playback devices are this prefix and the 1 based device index, e.g.
+/// `PLAYBACK0001`.
+pub const PLAYBACK_SERIAL_PREFIX: &str = "PLAYBACK";
+
+// 录像里没有received_at时按这个帧率回放
+const FALLBACK_FRAME_RATE: f64 = 30.0;
+// 回调线程每次最多等这么久，好及时响应注销
+const CALLBACK_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How fast a playback device serves the frames once acquisition is started.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlaybackRate {
+    /// At the intervals they were recorded at, see `FrameRecord::received_at`. Recordings
+    /// without them play at 30 fps.
+    Recorded,
+    /// At a fixed number of frames per second.
+    Fps(f64),
+    /// Every frame as soon as it is asked for.
+    Unlimited,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackOptions {
+    pub rate: PlaybackRate,
+    /// Start over after the last frame instead of timing out.
+    pub looping: bool,
+}
+
+impl Default for PlaybackOptions {
+    fn default() -> Self {
+        PlaybackOptions {
+            rate: PlaybackRate::Recorded,
+            looping: false,
+        }
+    }
+}
+
+fn status_error(status: GX_STATUS_LIST) -> Error {
+    Error::new(ErrorKind::GxStatusError(status as i32))
+}
+
+//----------------------------------------------------------
+//---------------Recording----------------------------------
+//----------------------------------------------------------
+
+enum FrameSource {
+    Images(PathBuf),
+    Raw(Mutex<File>),
+}
+
+/// A recording opened for playback, its frame records and random access to its frames.
+pub struct PlaybackRecording {
+    path: PathBuf,
+    source: FrameSource,
+    records: Vec<FrameRecord>,
+    data_lens: Vec<usize>,
+}
+
+fn read_sidecar(path: &Path) -> Result<Vec<FrameRecord>> {
+    let mut records = Vec::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        records.push(serde_json::from_str(&line).map_err(std::io::Error::other)?);
+    }
+    Ok(records)
+}
+
+// 帧头里的长度来自文件，超出文件剩余部分的说明容器损坏或者不是录像
+fn check_data_len(header: &RawFrameHeader, data_offset: u64, file_len: u64) -> Result<()> {
+    if header.data_len > file_len.saturating_sub(data_offset) {
+        return Err(Error::new(ErrorKind::FrameDataError(format!(
+            "frame {} claims {} bytes at offset {}, past the end of the {} byte container",
+            header.frame_id, header.data_len, data_offset, file_len
+        ))));
+    }
+    Ok(())
+}
+
+// 没有边车文件时直接扫一遍容器里的帧头
+fn scan_raw_container(file: &mut File) -> Result<Vec<FrameRecord>> {
+    let file_len = file.metadata()?.len();
+    let mut records = Vec::new();
+    let mut offset = file.seek(SeekFrom::Start(RAW_MAGIC.len() as u64))?;
+    loop {
+        let mut bytes = [0u8; RawFrameHeader::SIZE];
+        match file.read_exact(&mut bytes) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e.into()),
+        }
+        let header = RawFrameHeader::decode(&bytes);
+        check_data_len(&header, offset + RawFrameHeader::SIZE as u64, file_len)?;
+        records.push(FrameRecord {
+            index: records.len() as u64,
+            frame_id: header.frame_id,
+            timestamp: header.timestamp,
+            received_at: None,
+            width: header.width,
+            height: header.height,
+            pixel_format: header.pixel_format,
+            status: header.status,
+            exposure_time: Some(header.exposure_time).filter(|value| !value.is_nan()),
+            gain: Some(header.gain).filter(|value| !value.is_nan()),
+            file: None,
+            offset: Some(offset),
+        });
+        offset = file.seek(SeekFrom::Current(header.data_len as i64))?;
+    }
+    Ok(records)
+}
+
+// 录制时开着chunk的帧比图像本身长，载荷大小要按录下的数据长度报，从每帧的帧头读
+fn read_raw_data_lens(file: &mut File, records: &[FrameRecord]) -> Result<Vec<usize>> {
+    let file_len = file.metadata()?.len();
+    records
+        .iter()
+        .map(|record| {
+            let offset = record.offset.unwrap_or_default();
+            file.seek(SeekFrom::Start(offset))?;
+            let mut bytes = [0u8; RawFrameHeader::SIZE];
+            file.read_exact(&mut bytes)?;
+            let header = RawFrameHeader::decode(&bytes);
+            check_data_len(&header, offset + RawFrameHeader::SIZE as u64, file_len)?;
+            Ok(header.data_len as usize)
+        })
+        .collect()
+}
+
+// 打包格式的帧存成图片后读回来是解包的，记录要改成实际提供的格式，
+// 否则载荷大小和像素格式都按打包格式报，和给出的帧对不上
+fn unpack_image_records(records: &mut [FrameRecord]) {
+    for record in records {
+        if let Ok(format) = PixelFormatEntry::try_from(record.pixel_format) {
+            record.pixel_format = unpacked_format(format) as i32;
+        }
+    }
+}
+
+// 图片里只有图像，读回来的帧数据正好是图像大小
+fn image_data_lens(records: &[FrameRecord]) -> Vec<usize> {
+    records
+        .iter()
+        .map(|record| {
+            let (width, height) = (record.width.max(0) as usize, record.height.max(0) as usize);
+            PixelFormatEntry::try_from(record.pixel_format)
+                .map_or(width * height, |format| format.image_size(width, height))
+        })
+        .collect()
+}
+
+fn is_image_file(path: &Path) -> bool {
+    path.is_file()
+        && matches!(
+            image::ImageFormat::from_path(path),
+            Ok(image::ImageFormat::Png
+                | image::ImageFormat::Tiff
+                | image::ImageFormat::Jpeg
+                | image::ImageFormat::Bmp)
+        )
+}
+
+// 普通的图片目录按文件名排序，像素格式按第一张图来定
+fn scan_image_dir(dir: &Path) -> Result<Vec<FrameRecord>> {
+    let mut files = read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<PathBuf>>>()?;
+    files.retain(|path| is_image_file(path));
+    files.sort();
+
+    let pixel_format = match files.first() {
+        Some(first) => GxiFrame::from_dynamic_image(&image::open(first)?, None)?.pixel_format,
+        None => 0,
+    };
+    files
+        .iter()
+        .enumerate()
+        .map(|(index, path)| {
+            let (width, height) = image::image_dimensions(path)?;
+            Ok(FrameRecord {
+                index: index as u64,
+                frame_id: index as u64,
+                timestamp: 0,
+                received_at: None,
+                width: width as i32,
+                height: height as i32,
+                pixel_format,
+                status: 0,
+                exposure_time: None,
+                gain: None,
+                file: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned()),
+                offset: None,
+            })
+        })
+        .collect()
+}
+
+fn open_raw_container(path: &Path) -> Result<File> {
+    let mut file = File::open(path)?;
+    let mut magic = [0u8; 8];
+    file.read_exact(&mut magic)?;
+    if &magic != RAW_MAGIC {
+        return Err(Error::new(ErrorKind::InvalidParameter(format!(
+            "{:?} is not a raw recording",
+            path
+        ))));
+    }
+    Ok(file)
+}
+
+impl PlaybackRecording {
+    /// Open a `Recorder` directory, a raw container file, or a directory of images without
+    /// a sidecar, which then play in file name order.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let (dir, raw_path) = if path.is_file() {
+            let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+            (dir, Some(path.clone()))
+        } else {
+            let raw_path = path.join(RAW_FILE);
+            (path.clone(), Some(raw_path).filter(|raw| raw.is_file()))
+        };
+        let sidecar = dir.join(SIDECAR_FILE);
+        let sidecar_records = if sidecar.is_file() {
+            Some(read_sidecar(&sidecar)?)
+        } else {
+            None
+        };
+
+        let (source, records, data_lens) = match raw_path {
+            Some(raw_path) => {
+                let mut file = open_raw_container(&raw_path)?;
+                let records = match sidecar_records {
+                    Some(records) if records.iter().all(|record| record.offset.is_some()) => {
+                        records
+                    }
+                    _ => scan_raw_container(&mut file)?,
+                };
+                let data_lens = read_raw_data_lens(&mut file, &records)?;
+                (FrameSource::Raw(Mutex::new(file)), records, data_lens)
+            }
+            None => {
+                let mut records = match sidecar_records {
+                    Some(records) if records.iter().all(|record| record.file.is_some()) => records,
+                    _ => scan_image_dir(&dir)?,
+                };
+                unpack_image_records(&mut records);
+                let data_lens = image_data_lens(&records);
+                (FrameSource::Images(dir), records, data_lens)
+            }
+        };
+
+        println!(
+            "Successfully opened recording {:?} with {} frames",
+            path,
+            records.len()
+        );
+        Ok(PlaybackRecording {
+            path,
+            source,
+            records,
+            data_lens,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn records(&self) -> &[FrameRecord] {
+        &self.records
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Bytes of frame `index` as recorded, the image and any chunk data after it.
+    pub fn data_len(&self, index: usize) -> Option<usize> {
+        self.data_lens.get(index).copied()
+    }
+
+    /// Read frame `index` with its recorded frame ID, timestamp and status. Frames recorded
+    /// as images get their recorded pixel format back, see `GxiFrame::from_dynamic_image`,
+    /// packed formats unpacked as their records say.
+    pub fn frame(&self, index: usize) -> Result<GxiFrame> {
+        let record = self.records.get(index).ok_or_else(|| {
+            Error::new(ErrorKind::InvalidParameter(format!(
+                "frame {} is out of the {} recorded frames",
+                index,
+                self.records.len()
+            )))
+        })?;
+        let missing = |what: &str| {
+            Error::new(ErrorKind::FrameDataError(format!(
+                "frame {} of {:?} has no {}",
+                index, self.path, what
+            )))
+        };
+
+        match &self.source {
+            FrameSource::Raw(file) => {
+                let offset = record.offset.ok_or_else(|| missing("container offset"))?;
+                let mut file = file.lock().unwrap_or_else(PoisonError::into_inner);
+                file.seek(SeekFrom::Start(offset))?;
+                let (_, frame) = RawFrameHeader::read_frame(&mut *file)?
+                    .ok_or_else(|| missing("data in the container"))?;
+                Ok(frame)
+            }
+            FrameSource::Images(dir) => {
+                let file = record.file.as_ref().ok_or_else(|| missing("image file"))?;
+                let pixel_format = PixelFormatEntry::try_from(record.pixel_format).ok();
+                let mut frame =
+                    GxiFrame::from_dynamic_image(&image::open(dir.join(file))?, pixel_format)?;
+                frame.frame_id = record.frame_id;
+                frame.timestamp = record.timestamp;
+                frame.status = record.status;
+                Ok(frame)
+            }
+        }
+    }
+
+    /// Mean interval between the frames as recorded, `None` without `received_at`.
+    fn mean_interval(&self) -> Option<f64> {
+        let first = self.records.first()?.received_at?;
+        let last = self.records.last()?.received_at?;
+        let gaps = self.records.len().checked_sub(1).filter(|gaps| *gaps > 0)?;
+        Some((last - first) / gaps as f64).filter(|interval| *interval > 0.0)
+    }
+}
+
+//----------------------------------------------------------
+//---------------Playback Device----------------------------
+//----------------------------------------------------------
+
+struct PlaybackState {
+    opened: bool,
+    acquiring: bool,
+    cursor: usize,
+    next_due: Instant,
+    last_served: Option<usize>,
+}
+
+struct CaptureStream {
+    running: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+struct PlaybackDevice {
+    recording: PlaybackRecording,
+    options: PlaybackOptions,
+    serial: String,
+    state: Mutex<PlaybackState>,
+    stream: Mutex<Option<CaptureStream>>,
+}
+
+impl PlaybackDevice {
+    fn state(&self) -> std::sync::MutexGuard<'_, PlaybackState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn user_id(&self) -> String {
+        self.recording
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    fn base_info(&self) -> GX_DEVICE_BASE_INFO {
+        GX_DEVICE_BASE_INFO {
+            szVendorName: info_text(PLAYBACK_VENDOR_NAME),
+            szModelName: info_text(PLAYBACK_MODEL_NAME),
+            szSN: info_text(&self.serial),
+            szDisplayName: info_text(&format!(
+                "{} {}",
+                PLAYBACK_MODEL_NAME,
+                self.recording.path.display()
+            )),
+            szDeviceID: info_text(&self.serial),
+            szUserID: info_text(&self.user_id()),
+            accessStatus: if self.state().opened {
+                GX_ACCESS_STATUS::GX_ACCESS_STATUS_NOACCESS
+            } else {
+                GX_ACCESS_STATUS::GX_ACCESS_STATUS_READWRITE
+            },
+            deviceClass: GX_DEVICE_CLASS::GX_DEVICE_CLASS_UNKNOWN,
+            reserved: [0; 300],
+        }
+    }
+
+    /// The index of the record the device features describe, the next frame to serve.
+    fn current_index(&self) -> Option<usize> {
+        let cursor = self.state().cursor;
+        let len = self.recording.len();
+        (len > 0).then(|| cursor.min(len - 1))
+    }
+
+    fn current_record(&self) -> Option<&FrameRecord> {
+        self.recording.records().get(self.current_index()?)
+    }
+
+    fn interval_after(&self, index: usize) -> Duration {
+        let seconds = match self.options.rate {
+            PlaybackRate::Unlimited => 0.0,
+            PlaybackRate::Fps(fps) => 1.0 / fps,
+            PlaybackRate::Recorded => {
+                let records = self.recording.records();
+                let recorded = records
+                    .get(index)
+                    .and_then(|record| record.received_at)
+                    .zip(records.get(index + 1).and_then(|record| record.received_at))
+                    .map(|(this, next)| next - this)
+                    .filter(|gap| *gap >= 0.0);
+                recorded
+                    .or_else(|| self.recording.mean_interval())
+                    .unwrap_or(1.0 / FALLBACK_FRAME_RATE)
+            }
+        };
+        Duration::from_secs_f64(seconds)
+    }
+
+    fn frame_rate(&self) -> Option<f64> {
+        match self.options.rate {
+            PlaybackRate::Unlimited => None,
+            PlaybackRate::Fps(fps) => Some(fps),
+            PlaybackRate::Recorded => Some(
+                self.recording
+                    .mean_interval()
+                    .map_or(FALLBACK_FRAME_RATE, |interval| 1.0 / interval),
+            ),
+        }
+    }
+
+    fn start_acquisition(&self) {
+        let mut state = self.state();
+        state.acquiring = true;
+        state.next_due = Instant::now();
+    }
+
+    fn stop_acquisition(&self) {
+        self.state().acquiring = false;
+    }
+
+    /// The next frame once it is due, `None` when none is due within `timeout`, acquisition
+    /// is stopped or the recording has ended without looping. A frame longer than
+    /// `max_len` fails with `GX_STATUS_NEED_MORE_BUFFER` and stays the next frame.
+    fn next_frame(&self, timeout: Duration, max_len: Option<usize>) -> Result<Option<GxiFrame>> {
+        let deadline = Instant::now() + timeout;
+        let len = self.recording.len();
+        loop {
+            let due = {
+                let mut state = self.state();
+                if state.acquiring && state.cursor >= len && self.options.looping {
+                    state.cursor = 0;
+                }
+                (state.acquiring && state.cursor < len && state.next_due <= deadline)
+                    .then_some((state.cursor, state.next_due))
+            };
+            let Some((cursor, due)) = due else {
+                sleep(deadline.saturating_duration_since(Instant::now()));
+                return Ok(None);
+            };
+
+            // 等待时不拿状态锁，免得枚举设备、停止采集和关设备要等一帧的间隔；
+            // 醒来后重新确认，回调线程和gx_get_image同时取帧时只有一个能拿到这一帧
+            sleep(due.saturating_duration_since(Instant::now()));
+            let mut state = self.state();
+            if !state.acquiring || state.cursor != cursor || state.next_due != due {
+                continue;
+            }
+            let data_len = self.recording.data_len(cursor).unwrap_or(0);
+            if max_len.is_some_and(|max_len| data_len > max_len) {
+                return Err(status_error(GX_STATUS_LIST::GX_STATUS_NEED_MORE_BUFFER));
+            }
+            state.cursor += 1;
+            state.last_served = Some(cursor);
+            state.next_due = (due + self.interval_after(cursor)).max(Instant::now());
+            drop(state);
+            return self.recording.frame(cursor).map(Some);
+        }
+    }
+
+    fn stop_stream(&self) {
+        let stream = self
+            .stream
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(stream) = stream {
+            stream.running.store(false, Ordering::Relaxed);
+            let _ = stream.handle.join();
+        }
+    }
+}
+
+fn info_text<const N: usize>(text: &str) -> [u8; N] {
+    let mut bytes = [0u8; N];
+    let len = text.len().min(N - 1);
+    bytes[..len].copy_from_slice(&text.as_bytes()[..len]);
+    bytes
+}
+
+fn run_capture_stream(
+    device: Arc<PlaybackDevice>,
+    running: Arc<AtomicBool>,
+    callback: GXCaptureCallBack,
+) {
+    while running.load(Ordering::Relaxed) {
+        match device.next_frame(CALLBACK_POLL_INTERVAL, None) {
+            Ok(Some(frame)) => {
+                let mut param = GX_FRAME_CALLBACK_PARAM {
+                    pUserParam: std::ptr::null_mut(),
+                    status: frame.status,
+                    pImgBuf: frame.data.as_ptr() as *const c_void,
+                    nImgSize: frame.data.len() as i32,
+                    nWidth: frame.width,
+                    nHeight: frame.height,
+                    nPixelFormat: frame.pixel_format,
+                    nFrameID: frame.frame_id,
+                    nTimestamp: frame.timestamp,
+                    reserved: [0],
+                };
+                callback(&mut param);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                println!("Failed to play back frame: {:?}", e);
+                break;
+            }
+        }
+    }
+}
+
+//----------------------------------------------------------
+//---------------Playback Backend---------------------------
+//----------------------------------------------------------
+
+/// A `GXInterface` backend with one virtual camera per recording.
+///
+/// The devices enumerate like real ones, with the serial numbers `PLAYBACK0001`, ... in the
+/// order the recordings were given. Width, height, payload size, pixel format and pixel
+/// size describe the next frame to serve, exposure time and gain the last one served when
+/// they were recorded. The features are read only, commands other than acquisition start
+/// and stop are not implemented.
+pub struct GXPlayback {
+    devices: Vec<Arc<PlaybackDevice>>,
+}
+
+impl GXPlayback {
+    pub fn open<P: AsRef<Path>>(recordings: &[P], options: PlaybackOptions) -> Result<Self> {
+        let recordings = recordings
+            .iter()
+            .map(PlaybackRecording::open)
+            .collect::<Result<Vec<PlaybackRecording>>>()?;
+        Self::from_recordings(recordings, options)
+    }
+
+    pub fn from_recordings(
+        recordings: Vec<PlaybackRecording>,
+        options: PlaybackOptions,
+    ) -> Result<Self> {
+        if let PlaybackRate::Fps(fps) = options.rate {
+            if !(fps.is_finite() && fps > 0.0) {
+                return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                    "playback frame rate must be positive, got {}",
+                    fps
+                ))));
+            }
+        }
+        let devices = recordings
+            .into_iter()
+            .enumerate()
+            .map(|(index, recording)| {
+                Arc::new(PlaybackDevice {
+                    recording,
+                    options,
+                    serial: format!("{}{:04}", PLAYBACK_SERIAL_PREFIX, index + 1),
+                    state: Mutex::new(PlaybackState {
+                        opened: false,
+                        acquiring: false,
+                        cursor: 0,
+                        next_due: Instant::now(),
+                        last_served: None,
+                    }),
+                    stream: Mutex::new(None),
+                })
+            })
+            .collect();
+        Ok(GXPlayback { devices })
+    }
+
+    /// The opened device behind a handle, handles are the 1 based device index.
+    fn device(&self, device: GX_DEV_HANDLE) -> Result<&Arc<PlaybackDevice>> {
+        let index = (device as usize).wrapping_sub(1);
+        self.devices
+            .get(index)
+            .filter(|device| device.state().opened)
+            .ok_or_else(|| status_error(GX_STATUS_LIST::GX_STATUS_INVALID_HANDLE))
+    }
+
+    fn open_index(&self, index: usize, device: *mut GX_DEV_HANDLE) -> Result<i32> {
+        let playback = self
+            .devices
+            .get(index)
+            .ok_or_else(|| status_error(GX_STATUS_LIST::GX_STATUS_NOT_FOUND_DEVICE))?;
+        if device.is_null() {
+            return Err(status_error(GX_STATUS_LIST::GX_STATUS_INVALID_PARAMETER));
+        }
+        {
+            let mut state = playback.state();
+            if state.opened {
+                return Err(status_error(GX_STATUS_LIST::GX_STATUS_INVALID_ACCESS));
+            }
+            state.opened = true;
+            state.acquiring = false;
+            state.cursor = 0;
+            state.last_served = None;
+        }
+        unsafe { *device = (index + 1) as GX_DEV_HANDLE };
+        println!("Successfully opened playback device {}", playback.serial);
+        Ok(0)
+    }
+
+    fn is_implemented(feature_id: GX_FEATURE_ID) -> bool {
+        use GX_FEATURE_ID::*;
+
+        matches!(
+            feature_id,
+            GX_STRING_DEVICE_VENDOR_NAME
+                | GX_STRING_DEVICE_MODEL_NAME
+                | GX_STRING_DEVICE_SERIAL_NUMBER
+                | GX_STRING_DEVICE_USERID
+                | GX_INT_SENSOR_WIDTH
+                | GX_INT_SENSOR_HEIGHT
+                | GX_INT_WIDTH_MAX
+                | GX_INT_HEIGHT_MAX
+                | GX_INT_WIDTH
+                | GX_INT_HEIGHT
+                | GX_INT_OFFSET_X
+                | GX_INT_OFFSET_Y
+                | GX_INT_PAYLOAD_SIZE
+                | GX_ENUM_PIXEL_FORMAT
+                | GX_ENUM_PIXEL_SIZE
+                | GX_FLOAT_EXPOSURE_TIME
+                | GX_FLOAT_GAIN
+                | GX_FLOAT_ACQUISITION_FRAME_RATE
+                | GX_FLOAT_CURRENT_ACQUISITION_FRAME_RATE
+                | GX_COMMAND_ACQUISITION_START
+                | GX_COMMAND_ACQUISITION_STOP
+        )
+    }
+
+    /// Setting a feature fails as read only when it is implemented.
+    fn read_only(feature_id: GX_FEATURE_ID) -> Result<i32> {
+        Err(status_error(if Self::is_implemented(feature_id) {
+            GX_STATUS_LIST::GX_STATUS_INVALID_ACCESS
+        } else {
+            GX_STATUS_LIST::GX_STATUS_NOT_IMPLEMENTED
+        }))
+    }
+
+    fn not_implemented() -> Result<i32> {
+        Err(status_error(GX_STATUS_LIST::GX_STATUS_NOT_IMPLEMENTED))
+    }
+
+    fn int_value(&self, device: GX_DEV_HANDLE, feature_id: GX_FEATURE_ID) -> Result<i64> {
+        use GX_FEATURE_ID::*;
+
+        let device = self.device(device)?;
+        let record = device.current_record();
+        let (width, height, pixel_format) = record.map_or((0, 0, 0), |record| {
+            (record.width, record.height, record.pixel_format)
+        });
+        match feature_id {
+            GX_INT_SENSOR_WIDTH | GX_INT_WIDTH_MAX | GX_INT_WIDTH => Ok(width as i64),
+            GX_INT_SENSOR_HEIGHT | GX_INT_HEIGHT_MAX | GX_INT_HEIGHT => Ok(height as i64),
+            GX_INT_OFFSET_X | GX_INT_OFFSET_Y => Ok(0),
+            GX_INT_PAYLOAD_SIZE => Ok(device
+                .current_index()
+                .and_then(|index| device.recording.data_len(index))
+                .unwrap_or(0) as i64),
+            GX_ENUM_PIXEL_FORMAT => Ok(pixel_format as i64),
+            GX_ENUM_PIXEL_SIZE => Ok(PixelFormatEntry::try_from(pixel_format)
+                .map_or(8, |format| format.bits_per_pixel())
+                as i64),
+            _ => Err(status_error(GX_STATUS_LIST::GX_STATUS_NOT_IMPLEMENTED)),
+        }
+    }
+
+    fn string_value(&self, device: GX_DEV_HANDLE, feature_id: GX_FEATURE_ID) -> Result<String> {
+        use GX_FEATURE_ID::*;
+
+        let device = self.device(device)?;
+        match feature_id {
+            GX_STRING_DEVICE_VENDOR_NAME => Ok(PLAYBACK_VENDOR_NAME.to_string()),
+            GX_STRING_DEVICE_MODEL_NAME => Ok(PLAYBACK_MODEL_NAME.to_string()),
+            GX_STRING_DEVICE_SERIAL_NUMBER => Ok(device.serial.clone()),
+            GX_STRING_DEVICE_USERID => Ok(device.user_id()),
+            _ => Err(status_error(GX_STATUS_LIST::GX_STATUS_NOT_IMPLEMENTED)),
+        }
+    }
+
+    fn write_out<T>(target: *mut T, value: T) -> Result<i32> {
+        if target.is_null() {
+            return Err(status_error(GX_STATUS_LIST::GX_STATUS_INVALID_PARAMETER));
+        }
+        unsafe { *target = value };
+        Ok(0)
+    }
+}
+
+/// Make the recordings the backend, one device per recording, instead of the GxIAPI library.
+pub fn gxci_init_playback<P: AsRef<Path>>(
+    recordings: &[P],
+    options: PlaybackOptions,
+) -> Result<()> {
+    gxci_init_with(Box::new(GXPlayback::open(recordings, options)?))
+}
+
+impl GXInterface for GXPlayback {
+    /// `library_path` is the path of a single recording, played at the recorded rate.
+    fn new(library_path: &str) -> Result<Self> {
+        GXPlayback::open(&[library_path], PlaybackOptions::default())
+    }
+
+    fn gx_init_lib(&self) -> Result<i32> {
+        Ok(0)
+    }
+
+    fn gx_close_lib(&self) -> Result<()> {
+        for device in &self.devices {
+            device.stop_stream();
+            device.state().opened = false;
+        }
+        Ok(())
+    }
+
+    fn gx_update_device_list(&self, device_num: *mut u32, _timeout: u32) -> Result<i32> {
+        Self::write_out(device_num, self.devices.len() as u32)
+    }
+
+    fn gx_update_all_device_list(&self, num_devices: *mut u32, timeout: u32) -> Result<i32> {
+        self.gx_update_device_list(num_devices, timeout)
+    }
+
+    fn gx_get_all_device_base_info(
+        &self,
+        p_device_info: *mut GX_DEVICE_BASE_INFO,
+        p_buffer_size: *mut usize,
+    ) -> Result<i32> {
+        if p_device_info.is_null() || p_buffer_size.is_null() {
+            return Err(status_error(GX_STATUS_LIST::GX_STATUS_INVALID_PARAMETER));
+        }
+        let needed = self.devices.len() * size_of::<GX_DEVICE_BASE_INFO>();
+        unsafe {
+            if *p_buffer_size < needed {
+                *p_buffer_size = needed;
+                return Err(status_error(GX_STATUS_LIST::GX_STATUS_NEED_MORE_BUFFER));
+            }
+            for (index, device) in self.devices.iter().enumerate() {
+                p_device_info.add(index).write(device.base_info());
+            }
+            *p_buffer_size = needed;
+        }
+        Ok(0)
+    }
+
+    fn gx_open_device_by_index(&self, index: u32, device: *mut GX_DEV_HANDLE) -> Result<i32> {
+        // 和GxIAPI一样，索引从1开始
+        let index = (index as usize)
+            .checked_sub(1)
+            .ok_or_else(|| status_error(GX_STATUS_LIST::GX_STATUS_NOT_FOUND_DEVICE))?;
+        self.open_index(index, device)
+    }
+
+    fn gx_open_device(
+        &self,
+        open_param: *const GX_OPEN_PARAM,
+        device_handle: *mut GX_DEV_HANDLE,
+    ) -> Result<i32> {
+        if open_param.is_null() {
+            return Err(status_error(GX_STATUS_LIST::GX_STATUS_INVALID_PARAMETER));
+        }
+        let open_param = unsafe { &*open_param };
+        if open_param.pszContent.is_null() {
+            return Err(status_error(GX_STATUS_LIST::GX_STATUS_INVALID_PARAMETER));
+        }
+        let content = unsafe { CStr::from_ptr(open_param.pszContent) }
+            .to_string_lossy()
+            .into_owned();
+
+        let index = match open_param.openMode {
+            GX_OPEN_MODE::GX_OPEN_SN => self
+                .devices
+                .iter()
+                .position(|device| device.serial == content),
+            GX_OPEN_MODE::GX_OPEN_USERID => self
+                .devices
+                .iter()
+                .position(|device| device.user_id() == content),
+            GX_OPEN_MODE::GX_OPEN_INDEX => content
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| index.checked_sub(1)),
+            GX_OPEN_MODE::GX_OPEN_IP | GX_OPEN_MODE::GX_OPEN_MAC => None,
+        }
+        .ok_or_else(|| status_error(GX_STATUS_LIST::GX_STATUS_NOT_FOUND_DEVICE))?;
+        self.open_index(index, device_handle)
+    }
+
+    fn gx_close_device(&self, device: GX_DEV_HANDLE) -> Result<i32> {
+        let device = self.device(device)?;
+        device.stop_stream();
+        let mut state = device.state();
+        state.acquiring = false;
+        state.opened = false;
+        Ok(0)
+    }
+
+    fn gx_export_config_file(
+        &self,
+        device: GX_DEV_HANDLE,
+        _file_path: *const c_char,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::not_implemented()
+    }
+
+    fn gx_import_config_file(
+        &self,
+        device: GX_DEV_HANDLE,
+        _file_path: *const c_char,
+        _verify: bool,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::not_implemented()
+    }
+
+    fn gx_send_command(&self, device: GX_DEV_HANDLE, feature_id: GX_FEATURE_ID) -> Result<i32> {
+        let device = self.device(device)?;
+        match feature_id {
+            GX_FEATURE_ID::GX_COMMAND_ACQUISITION_START => device.start_acquisition(),
+            GX_FEATURE_ID::GX_COMMAND_ACQUISITION_STOP => device.stop_acquisition(),
+            _ => return Self::not_implemented(),
+        }
+        Ok(0)
+    }
+
+    /// Waits for the next frame to be due and copies it into `pImgBuf`, which needs room for
+    /// `GX_INT_PAYLOAD_SIZE` bytes, and fails with `GX_STATUS_NEED_MORE_BUFFER` without
+    /// using up the frame otherwise. Fails with `GX_STATUS_TIMEOUT` when none is due within
+    /// `timeout` ms or the recording has ended, and with `GX_STATUS_INVALID_CALL` before
+    /// acquisition start.
+    fn gx_get_image(
+        &self,
+        device: GX_DEV_HANDLE,
+        p_frame_data: *mut GX_FRAME_DATA,
+        timeout: i32,
+    ) -> Result<i32> {
+        let device = self.device(device)?;
+        if p_frame_data.is_null() {
+            return Err(status_error(GX_STATUS_LIST::GX_STATUS_INVALID_PARAMETER));
+        }
+        if !device.state().acquiring {
+            return Err(status_error(GX_STATUS_LIST::GX_STATUS_INVALID_CALL));
+        }
+        let frame_data = unsafe { &mut *p_frame_data };
+        let capacity = if frame_data.pImgBuf.is_null() {
+            0
+        } else {
+            frame_data.nImgSize.max(0) as usize
+        };
+        let frame = device
+            .next_frame(Duration::from_millis(timeout.max(0) as u64), Some(capacity))?
+            .ok_or_else(|| status_error(GX_STATUS_LIST::GX_STATUS_TIMEOUT))?;
+        if capacity < frame.data.len() {
+            return Err(status_error(GX_STATUS_LIST::GX_STATUS_NEED_MORE_BUFFER));
+        }
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                frame.data.as_ptr(),
+                frame_data.pImgBuf as *mut u8,
+                frame.data.len(),
+            );
+        }
+        frame_data.nStatus = frame.status;
+        frame_data.nWidth = frame.width;
+        frame_data.nHeight = frame.height;
+        frame_data.nPixelFormat = frame.pixel_format;
+        frame_data.nImgSize = frame.data.len() as i32;
+        frame_data.nFrameID = frame.frame_id;
+        frame_data.nTimestamp = frame.timestamp;
+        Ok(0)
+    }
+
+    fn gx_flush_queue(&self, device: GX_DEV_HANDLE) -> Result<i32> {
+        self.device(device)?;
+        Ok(0)
+    }
+
+    fn gx_flush_event(&self, device: GX_DEV_HANDLE) -> Result<i32> {
+        self.device(device)?;
+        Ok(0)
+    }
+
+    fn gx_get_feature_name(
+        &self,
+        device: GX_DEV_HANDLE,
+        _feature_id: GX_FEATURE_ID,
+        _name: *mut c_char,
+        _size: *mut usize,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::not_implemented()
+    }
+
+    fn gx_is_implemented(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        is_implemented: *mut bool,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::write_out(is_implemented, Self::is_implemented(feature_id))
+    }
+
+    fn gx_is_readable(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        is_readable: *mut bool,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::write_out(is_readable, Self::is_implemented(feature_id))
+    }
+
+    fn gx_is_writable(
+        &self,
+        device: GX_DEV_HANDLE,
+        _feature_id: GX_FEATURE_ID,
+        is_writable: *mut bool,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::write_out(is_writable, false)
+    }
+
+    fn gx_get_int(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        int_value: *mut i64,
+    ) -> Result<i32> {
+        let value = self.int_value(device, feature_id)?;
+        Self::write_out(int_value, value)
+    }
+
+    fn gx_set_int(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        _int_value: i64,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::read_only(feature_id)
+    }
+
+    fn gx_get_float(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        float_value: *mut f64,
+    ) -> Result<i32> {
+        use GX_FEATURE_ID::*;
+
+        let device = self.device(device)?;
+        let last_served = device
+            .state()
+            .last_served
+            .and_then(|index| device.recording.records().get(index));
+        let value = match feature_id {
+            GX_FLOAT_EXPOSURE_TIME => last_served.and_then(|record| record.exposure_time),
+            GX_FLOAT_GAIN => last_served.and_then(|record| record.gain),
+            GX_FLOAT_ACQUISITION_FRAME_RATE | GX_FLOAT_CURRENT_ACQUISITION_FRAME_RATE => {
+                device.frame_rate()
+            }
+            _ => None,
+        };
+        match value {
+            Some(value) => Self::write_out(float_value, value),
+            None => Self::not_implemented(),
+        }
+    }
+
+    fn gx_set_float(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        _float_value: f64,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::read_only(feature_id)
+    }
+
+    fn gx_get_enum_entry_nums(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        entry_nums: *mut u32,
+    ) -> Result<i32> {
+        match feature_id {
+            GX_FEATURE_ID::GX_ENUM_PIXEL_FORMAT | GX_FEATURE_ID::GX_ENUM_PIXEL_SIZE => {
+                self.device(device)?;
+                Self::write_out(entry_nums, 1)
+            }
+            _ => Self::not_implemented(),
+        }
+    }
+
+    /// A playback device has one entry per enum, the current value.
+    fn gx_get_enum_description(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        enum_description: *mut GX_ENUM_DESCRIPTION,
+        buffer_size: *mut usize,
+    ) -> Result<i32> {
+        let value = match feature_id {
+            GX_FEATURE_ID::GX_ENUM_PIXEL_FORMAT | GX_FEATURE_ID::GX_ENUM_PIXEL_SIZE => {
+                self.int_value(device, feature_id)?
+            }
+            _ => return Self::not_implemented(),
+        };
+        if enum_description.is_null() || buffer_size.is_null() {
+            return Err(status_error(GX_STATUS_LIST::GX_STATUS_INVALID_PARAMETER));
+        }
+        let needed = size_of::<GX_ENUM_DESCRIPTION>();
+        if unsafe { *buffer_size } < needed {
+            unsafe { *buffer_size = needed };
+            return Err(status_error(GX_STATUS_LIST::GX_STATUS_NEED_MORE_BUFFER));
+        }
+
+        let symbolic = match feature_id {
+            GX_FEATURE_ID::GX_ENUM_PIXEL_FORMAT => PixelFormatEntry::try_from(value)
+                .map_or_else(|_| value.to_string(), |format| format.name().to_string()),
+            _ => format!("Bpp{}", value),
+        };
+        let mut description = GX_ENUM_DESCRIPTION::new();
+        description.nValue = value;
+        let len = symbolic.len().min(description.szSymbolic.len() - 1);
+        for (target, byte) in description
+            .szSymbolic
+            .iter_mut()
+            .zip(&symbolic.as_bytes()[..len])
+        {
+            *target = *byte as c_char;
+        }
+        unsafe {
+            enum_description.write(description);
+            *buffer_size = needed;
+        }
+        Ok(0)
+    }
+
+    fn gx_get_enum(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        enum_value: *mut i64,
+    ) -> Result<i32> {
+        let value = self.int_value(device, feature_id)?;
+        Self::write_out(enum_value, value)
+    }
+
+    fn gx_set_enum(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        _enum_value: i64,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::read_only(feature_id)
+    }
+
+    fn gx_get_bool(
+        &self,
+        device: GX_DEV_HANDLE,
+        _feature_id: GX_FEATURE_ID,
+        _value: *mut bool,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::not_implemented()
+    }
+
+    fn gx_set_bool(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        _value: bool,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::read_only(feature_id)
+    }
+
+    fn gx_get_string_length(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        size: *mut usize,
+    ) -> Result<i32> {
+        let value = self.string_value(device, feature_id)?;
+        Self::write_out(size, value.len() + 1)
+    }
+
+    fn gx_get_string_max_length(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        size: *mut usize,
+    ) -> Result<i32> {
+        self.gx_get_string_length(device, feature_id, size)
+    }
+
+    fn gx_get_string(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        content: *mut c_char,
+        size: *mut usize,
+    ) -> Result<i32> {
+        let value = self.string_value(device, feature_id)?;
+        if content.is_null() || size.is_null() {
+            return Err(status_error(GX_STATUS_LIST::GX_STATUS_INVALID_PARAMETER));
+        }
+        unsafe {
+            if *size < value.len() + 1 {
+                *size = value.len() + 1;
+                return Err(status_error(GX_STATUS_LIST::GX_STATUS_NEED_MORE_BUFFER));
+            }
+            std::ptr::copy_nonoverlapping(value.as_ptr() as *const c_char, content, value.len());
+            *content.add(value.len()) = 0;
+            *size = value.len() + 1;
+        }
+        Ok(0)
+    }
+
+    fn gx_set_string(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        _content: *const c_char,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::read_only(feature_id)
+    }
+
+    fn gx_get_buffer_length(
+        &self,
+        device: GX_DEV_HANDLE,
+        _feature_id: GX_FEATURE_ID,
+        _size: *mut usize,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::not_implemented()
+    }
+
+    fn gx_get_buffer(
+        &self,
+        device: GX_DEV_HANDLE,
+        _feature_id: GX_FEATURE_ID,
+        _buffer: *mut u8,
+        _size: *mut usize,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::not_implemented()
+    }
+
+    fn gx_set_buffer(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        _buffer: *const u8,
+        _size: usize,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::read_only(feature_id)
+    }
+
+    /// Width and height ranges hold only the current value, the rest are not implemented.
+    fn gx_get_int_range(
+        &self,
+        device: GX_DEV_HANDLE,
+        feature_id: GX_FEATURE_ID,
+        int_range: *mut GX_INT_RANGE,
+    ) -> Result<i32> {
+        use GX_FEATURE_ID::*;
+
+        if !matches!(
+            feature_id,
+            GX_INT_WIDTH | GX_INT_HEIGHT | GX_INT_OFFSET_X | GX_INT_OFFSET_Y
+        ) {
+            return Self::not_implemented();
+        }
+        let value = self.int_value(device, feature_id)?;
+        let mut range = GX_INT_RANGE::new();
+        range.nMin = value;
+        range.nMax = value;
+        range.nInc = 1;
+        Self::write_out(int_range, range)
+    }
+
+    fn gx_get_float_range(
+        &self,
+        device: GX_DEV_HANDLE,
+        _feature_id: GX_FEATURE_ID,
+        _float_range: *mut GX_FLOAT_RANGE,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::not_implemented()
+    }
+
+    fn gx_get_event_num_in_queue(&self, device: GX_DEV_HANDLE, event_num: *mut u32) -> Result<i32> {
+        self.device(device)?;
+        Self::write_out(event_num, 0)
+    }
+
+    fn gx_get_last_error(
+        &self,
+        error_code: *mut GX_STATUS_LIST,
+        err_text: *mut c_char,
+        size: *mut usize,
+    ) -> Result<i32> {
+        // 回放设备的错误都直接通过返回值给出，这里没有需要补充的
+        Self::write_out(error_code, GX_STATUS_LIST::GX_STATUS_SUCCESS)?;
+        if !err_text.is_null() && !size.is_null() && unsafe { *size } > 0 {
+            unsafe { *err_text = 0 };
+        }
+        if !size.is_null() {
+            unsafe { *size = 1 };
+        }
+        Ok(0)
+    }
+
+    fn gx_set_acquisition_buffer_number(
+        &self,
+        device: GX_DEV_HANDLE,
+        _buffer_num: u64,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Ok(0)
+    }
+
+    /// Calls `callback` from a playback thread with each frame while acquisition runs.
+    fn gx_register_capture_callback(
+        &self,
+        device: *mut c_void,
+        callback: GXCaptureCallBack,
+    ) -> Result<i32> {
+        let playback = self.device(device)?;
+        let mut stream = playback
+            .stream
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if stream.is_some() {
+            return Err(status_error(GX_STATUS_LIST::GX_STATUS_INVALID_CALL));
+        }
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_device = playback.clone();
+        let thread_running = running.clone();
+        let handle = spawn(move || run_capture_stream(thread_device, thread_running, callback));
+        *stream = Some(CaptureStream { running, handle });
+        Ok(0)
+    }
+
+    /// Stops the playback thread and waits for the callback in progress, so the callback
+    /// must not call back into the HAL, which is locked meanwhile.
+    fn gx_unregister_capture_callback(&self, device: *mut c_void) -> Result<i32> {
+        self.device(device)?.stop_stream();
+        Ok(0)
+    }
+
+    /// A playback device never goes offline, the callback is accepted and never called.
+    fn gx_register_device_offline_callback(
+        &self,
+        device: GX_DEV_HANDLE,
+        _user_param: *mut std::os::raw::c_void,
+        _callback_fun: GXDeviceOfflineCallBack,
+        callback_handle: *mut GX_EVENT_CALLBACK_HANDLE,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::write_out(callback_handle, std::ptr::null_mut())
+    }
+
+    fn gx_unregister_device_offline_callback(
+        &self,
+        device: GX_DEV_HANDLE,
+        _callback_handle: GX_EVENT_CALLBACK_HANDLE,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Ok(0)
+    }
+
+    fn gx_register_feature_callback(
+        &self,
+        device: GX_DEV_HANDLE,
+        _user_param: *mut std::os::raw::c_void,
+        _callback_fun: GXFeatureCallBack,
+        _feature_id: GX_FEATURE_ID,
+        _callback_handle: *mut GX_FEATURE_CALLBACK_HANDLE,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::not_implemented()
+    }
+
+    fn gx_unregister_feature_callback(
+        &self,
+        device: GX_DEV_HANDLE,
+        _feature_id: GX_FEATURE_ID,
+        _callback_handle: GX_FEATURE_CALLBACK_HANDLE,
+    ) -> Result<i32> {
+        self.device(device)?;
+        Self::not_implemented()
+    }
+}
+
+impl Drop for GXPlayback {
+    fn drop(&mut self) {
+        for device in &self.devices {
+            device.stop_stream();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hal::recorder::{RecordFormat, Recorder};
+    use crate::utils::builder::GXDeviceBaseInfoBuilder;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("gxci-playback-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn frame(frame_id: u64, format: PixelFormatEntry, data: Vec<u8>) -> GxiFrame {
+        GxiFrame {
+            status: 0,
+            width: 4,
+            height: 2,
+            pixel_format: format as i32,
+            frame_id,
+            timestamp: 100 * frame_id,
+            data,
+        }
+    }
+
+    fn record(dir: &Path, format: RecordFormat, frames: Vec<GxiFrame>) {
+        let mut recorder = Recorder::start(dir, format, 8).unwrap();
+        recorder.record(frames).unwrap();
+        recorder.finish().unwrap();
+    }
+
+    fn unlimited() -> PlaybackOptions {
+        PlaybackOptions {
+            rate: PlaybackRate::Unlimited,
+            looping: false,
+        }
+    }
+
+    fn status_of(result: Result<i32>) -> i32 {
+        match result {
+            Err(e) => match *e.inner {
+                ErrorKind::GxStatusError(status) => status,
+                kind => panic!("not a status error: {:?}", kind),
+            },
+            Ok(status) => status,
+        }
+    }
+
+    fn open_started(playback: &GXPlayback) -> GX_DEV_HANDLE {
+        let mut device: GX_DEV_HANDLE = std::ptr::null_mut();
+        playback.gx_open_device_by_index(1, &mut device).unwrap();
+        playback
+            .gx_send_command(device, GX_FEATURE_ID::GX_COMMAND_ACQUISITION_START)
+            .unwrap();
+        device
+    }
+
+    fn int(playback: &GXPlayback, device: GX_DEV_HANDLE, feature_id: GX_FEATURE_ID) -> i64 {
+        let mut value = 0;
+        playback.gx_get_int(device, feature_id, &mut value).unwrap();
+        value
+    }
+
+    fn get_image(
+        playback: &GXPlayback,
+        device: GX_DEV_HANDLE,
+        buffer: &mut [u8],
+    ) -> (Result<i32>, GX_FRAME_DATA) {
+        let mut frame_data = GX_FRAME_DATA {
+            nStatus: 0,
+            pImgBuf: buffer.as_mut_ptr() as *mut c_void,
+            nWidth: 0,
+            nHeight: 0,
+            nPixelFormat: 0,
+            nImgSize: buffer.len() as i32,
+            nFrameID: 0,
+            nTimestamp: 0,
+            reserved: [0],
+        };
+        let result = playback.gx_get_image(device, &mut frame_data, 0);
+        (result, frame_data)
+    }
+
+    #[test]
+    fn lists_one_device_per_recording() {
+        let dirs = [temp_dir("list-a"), temp_dir("list-b")];
+        for dir in &dirs {
+            record(
+                dir,
+                RecordFormat::Raw,
+                vec![frame(1, PixelFormatEntry::Mono8, vec![0; 8])],
+            );
+        }
+        let playback = GXPlayback::open(&dirs, unlimited()).unwrap();
+
+        let mut count = 0;
+        playback.gx_update_device_list(&mut count, 0).unwrap();
+        assert_eq!(count, 2);
+        let mut infos = vec![GXDeviceBaseInfoBuilder::new().build(); 2];
+        let mut size = 0;
+        assert_eq!(
+            status_of(playback.gx_get_all_device_base_info(infos.as_mut_ptr(), &mut size)),
+            GX_STATUS_LIST::GX_STATUS_NEED_MORE_BUFFER as i32
+        );
+        playback
+            .gx_get_all_device_base_info(infos.as_mut_ptr(), &mut size)
+            .unwrap();
+        let text = |bytes: &[u8]| {
+            CStr::from_bytes_until_nul(bytes)
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(text(&infos[0].szSN), "PLAYBACK0001");
+        assert_eq!(text(&infos[1].szSN), "PLAYBACK0002");
+        assert_eq!(text(&infos[0].szModelName), PLAYBACK_MODEL_NAME);
+        for dir in &dirs {
+            std::fs::remove_dir_all(dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn plays_back_raw_recording() {
+        let dir = temp_dir("raw");
+        // 4x2的Mono8图像是8字节，后面4字节当作chunk数据，载荷大小要算上
+        let frames = vec![
+            frame(7, PixelFormatEntry::Mono8, (0..12).collect()),
+            frame(8, PixelFormatEntry::Mono8, (12..24).collect()),
+        ];
+        record(&dir, RecordFormat::Raw, frames.clone());
+        let playback = GXPlayback::open(&[&dir], unlimited()).unwrap();
+        let device = open_started(&playback);
+
+        assert_eq!(
+            int(&playback, device, GX_FEATURE_ID::GX_INT_PAYLOAD_SIZE),
+            12
+        );
+        let mut buffer = vec![0u8; 12];
+        for expected in &frames {
+            let (result, frame_data) = get_image(&playback, device, &mut buffer);
+            result.unwrap();
+            assert_eq!(frame_data.nFrameID, expected.frame_id);
+            assert_eq!(frame_data.nTimestamp, expected.timestamp);
+            assert_eq!(frame_data.nImgSize, 12);
+            assert_eq!(buffer, expected.data);
+        }
+        let (result, _) = get_image(&playback, device, &mut buffer);
+        assert_eq!(status_of(result), GX_STATUS_LIST::GX_STATUS_TIMEOUT as i32);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn short_buffer_does_not_use_up_the_frame() {
+        let dir = temp_dir("short");
+        record(
+            &dir,
+            RecordFormat::Raw,
+            vec![frame(1, PixelFormatEntry::Mono8, vec![5; 8])],
+        );
+        let playback = GXPlayback::open(&[&dir], unlimited()).unwrap();
+        let device = open_started(&playback);
+
+        let (result, _) = get_image(&playback, device, &mut [0u8; 4]);
+        assert_eq!(
+            status_of(result),
+            GX_STATUS_LIST::GX_STATUS_NEED_MORE_BUFFER as i32
+        );
+        let mut buffer = vec![0u8; 8];
+        let (result, frame_data) = get_image(&playback, device, &mut buffer);
+        result.unwrap();
+        assert_eq!(frame_data.nFrameID, 1);
+        assert_eq!(buffer, vec![5; 8]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn plays_back_packed_png_recording_unpacked() {
+        let dir = temp_dir("packed");
+        // 4x2的Mono12Packed是12字节，读回来是Mono12，每像素2字节
+        let packed = vec![
+            0x12, 0x34, 0x56, 0xff, 0x0f, 0x00, 0, 0, 0, 0x80, 0x21, 0x01,
+        ];
+        let recorded = frame(3, PixelFormatEntry::Mono12Packed, packed);
+        let samples = recorded.samples_u16().unwrap();
+        record(&dir, RecordFormat::Png, vec![recorded]);
+        let playback = GXPlayback::open(&[&dir], unlimited()).unwrap();
+        let device = open_started(&playback);
+
+        assert_eq!(
+            int(&playback, device, GX_FEATURE_ID::GX_INT_PAYLOAD_SIZE),
+            16
+        );
+        let mut format = 0;
+        playback
+            .gx_get_enum(device, GX_FEATURE_ID::GX_ENUM_PIXEL_FORMAT, &mut format)
+            .unwrap();
+        assert_eq!(format, PixelFormatEntry::Mono12 as i64);
+
+        let mut buffer = vec![0u8; 16];
+        let (result, frame_data) = get_image(&playback, device, &mut buffer);
+        result.unwrap();
+        assert_eq!(frame_data.nPixelFormat, PixelFormatEntry::Mono12 as i32);
+        assert_eq!(frame_data.nFrameID, 3);
+        let played = frame(3, PixelFormatEntry::Mono12, buffer);
+        assert_eq!(played.samples_u16().unwrap(), samples);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
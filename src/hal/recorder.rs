@@ -0,0 +1,524 @@
+//! Recording frames to disk on a background thread, as an image sequence or a raw container, with a JSON-lines metadata sidecar.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::hal::control::chunk_data::ChunkIds;
+use crate::hal::device::GxiFrame;
+
+use serde::{Deserialize, Serialize};
+
+use std::fs::{File, create_dir_all};
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, SyncSender, TrySendError, sync_channel};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{JoinHandle, spawn};
+use std::time::Instant;
+
+#[cfg(feature = "solo")]
+use crate::hal::control::acquisition::gxi_get_exposure_time;
+#[cfg(feature = "solo")]
+use crate::hal::control::analog::gxi_get_gain;
+#[cfg(feature = "solo")]
+use crate::hal::device::gxi_get_frame;
+
+/// Name of the JSON-lines sidecar in the recording directory.
+pub const SIDECAR_FILE: &str = "frames.jsonl";
+/// Name of the raw container in the recording directory.
+pub const RAW_FILE: &str = "frames.gxraw";
+
+/// How a `Recorder` writes the frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFormat {
+    /// One lossless PNG per frame, 16 bit formats stay 16 bit.
+    Png,
+    /// One lossless TIFF per frame, 16 bit formats stay 16 bit.
+    Tiff,
+    /// All frames in one container with their original buffer bytes, any pixel format.
+    Raw,
+}
+
+impl RecordFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            RecordFormat::Png => "png",
+            RecordFormat::Tiff => "tiff",
+            RecordFormat::Raw => "gxraw",
+        }
+    }
+}
+
+/// One line of the sidecar.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameRecord {
+    /// Position in the recording, from 0.
+    pub index: u64,
+    pub frame_id: u64,
+    /// Device timestamp in ticks.
+    pub timestamp: u64,
+    /// Seconds from the start of the recording to the frame being pushed, playback uses it
+    /// to serve frames at the recorded rate.
+    #[serde(default)]
+    pub received_at: Option<f64>,
+    pub width: i32,
+    pub height: i32,
+    /// `nPixelFormat` of the frame, see `PixelFormatEntry`.
+    pub pixel_format: i32,
+    pub status: i32,
+    /// Exposure time in us.
+    pub exposure_time: Option<f64>,
+    /// Gain in dB.
+    pub gain: Option<f64>,
+    /// Image file of the frame, relative to the recording directory.
+    pub file: Option<String>,
+    /// Byte offset of the frame record in the raw container.
+    pub offset: Option<u64>,
+}
+
+//----------------------------------------------------------
+//---------------Raw Container------------------------------
+//----------------------------------------------------------
+
+// 文件头是8字节魔数，后面一帧接一帧：56字节小端帧头 + 原始缓冲区字节，帧头见RawFrameHeader
+pub const RAW_MAGIC: &[u8; 8] = b"GXCIRAW1";
+
+/// The header in front of each frame in the raw container.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RawFrameHeader {
+    pub frame_id: u64,
+    pub timestamp: u64,
+    pub width: i32,
+    pub height: i32,
+    pub pixel_format: i32,
+    pub status: i32,
+    /// NaN when unknown.
+    pub exposure_time: f64,
+    /// NaN when unknown.
+    pub gain: f64,
+    pub data_len: u64,
+}
+
+impl RawFrameHeader {
+    pub const SIZE: usize = 56;
+
+    pub fn encode(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+        bytes[0..8].copy_from_slice(&self.frame_id.to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.timestamp.to_le_bytes());
+        bytes[16..20].copy_from_slice(&self.width.to_le_bytes());
+        bytes[20..24].copy_from_slice(&self.height.to_le_bytes());
+        bytes[24..28].copy_from_slice(&self.pixel_format.to_le_bytes());
+        bytes[28..32].copy_from_slice(&self.status.to_le_bytes());
+        bytes[32..40].copy_from_slice(&self.exposure_time.to_le_bytes());
+        bytes[40..48].copy_from_slice(&self.gain.to_le_bytes());
+        bytes[48..56].copy_from_slice(&self.data_len.to_le_bytes());
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8; Self::SIZE]) -> Self {
+        let u64_at = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
+        let i32_at = |at: usize| i32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+        RawFrameHeader {
+            frame_id: u64_at(0),
+            timestamp: u64_at(8),
+            width: i32_at(16),
+            height: i32_at(20),
+            pixel_format: i32_at(24),
+            status: i32_at(28),
+            exposure_time: f64::from_bits(u64_at(32)),
+            gain: f64::from_bits(u64_at(40)),
+            data_len: u64_at(48),
+        }
+    }
+
+    /// Read the next frame, `None` at the end of the container.
+    pub fn read_frame<R: Read>(reader: &mut R) -> Result<Option<(Self, GxiFrame)>> {
+        let mut bytes = [0u8; Self::SIZE];
+        match reader.read_exact(&mut bytes) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+        let header = Self::decode(&bytes);
+        // data_len来自文件，损坏的容器里可能是个极大的数，不能按它预先分配
+        let mut data = Vec::new();
+        reader.take(header.data_len).read_to_end(&mut data)?;
+        if data.len() as u64 != header.data_len {
+            return Err(Error::new(ErrorKind::FrameDataError(format!(
+                "frame {} ends after {} of its {} bytes",
+                header.frame_id,
+                data.len(),
+                header.data_len
+            ))));
+        }
+        let frame = GxiFrame {
+            status: header.status,
+            width: header.width,
+            height: header.height,
+            pixel_format: header.pixel_format,
+            frame_id: header.frame_id,
+            timestamp: header.timestamp,
+            data,
+        };
+        Ok(Some((header, frame)))
+    }
+}
+
+//----------------------------------------------------------
+//---------------Writer Thread------------------------------
+//----------------------------------------------------------
+
+/// Counters of a recording, shared with the writer thread.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecorderStats {
+    pub queued: u64,
+    pub written: u64,
+    pub bytes_written: u64,
+    /// Frames not recorded because the queue was full, the disk did not keep up.
+    pub dropped: u64,
+    pub dropped_frame_ids: Vec<u64>,
+}
+
+struct RecordWriter {
+    dir: PathBuf,
+    format: RecordFormat,
+    sidecar: BufWriter<File>,
+    raw: Option<BufWriter<File>>,
+    raw_offset: u64,
+    stats: Arc<Mutex<RecorderStats>>,
+}
+
+impl RecordWriter {
+    fn write(&mut self, frame: &GxiFrame, mut record: FrameRecord) -> Result<()> {
+        let bytes = match &mut self.raw {
+            Some(raw) => {
+                let header = RawFrameHeader {
+                    frame_id: frame.frame_id,
+                    timestamp: frame.timestamp,
+                    width: frame.width,
+                    height: frame.height,
+                    pixel_format: frame.pixel_format,
+                    status: frame.status,
+                    exposure_time: record.exposure_time.unwrap_or(f64::NAN),
+                    gain: record.gain.unwrap_or(f64::NAN),
+                    data_len: frame.data.len() as u64,
+                };
+                raw.write_all(&header.encode())?;
+                raw.write_all(&frame.data)?;
+                record.offset = Some(self.raw_offset);
+                let bytes = (RawFrameHeader::SIZE + frame.data.len()) as u64;
+                self.raw_offset += bytes;
+                bytes
+            }
+            None => {
+                let file = format!("frame_{:06}.{}", record.index, self.format.extension());
+                let path = self.dir.join(&file);
+                let image_format = match self.format {
+                    RecordFormat::Tiff => image::ImageFormat::Tiff,
+                    _ => image::ImageFormat::Png,
+                };
+                frame
+                    .to_dynamic_image()?
+                    .save_with_format(&path, image_format)?;
+                record.file = Some(file);
+                path.metadata().map(|metadata| metadata.len()).unwrap_or(0)
+            }
+        };
+
+        let line = serde_json::to_string(&record).map_err(std::io::Error::other)?;
+        writeln!(self.sidecar, "{}", line)?;
+
+        let mut stats = self.stats.lock().unwrap_or_else(PoisonError::into_inner);
+        stats.written += 1;
+        stats.bytes_written += bytes;
+        Ok(())
+    }
+
+    fn run(mut self, receiver: Receiver<(GxiFrame, FrameRecord)>) -> Result<()> {
+        for (frame, record) in receiver {
+            self.write(&frame, record)?;
+        }
+        self.sidecar.flush()?;
+        if let Some(raw) = &mut self.raw {
+            raw.flush()?;
+        }
+        Ok(())
+    }
+}
+
+//----------------------------------------------------------
+//---------------Recorder-----------------------------------
+//----------------------------------------------------------
+
+/// Writes the frames pushed into it to a directory on a background thread.
+///
+/// The queue between `push` and the writer holds `queue_capacity` frames. When it is full,
+/// the frame is dropped and counted rather than blocking the capture loop, see `stats`.
+/// Exposure and gain are taken from the frame's chunk data when it carries them, otherwise
+/// from the values given to `set_settings`.
+pub struct Recorder {
+    dir: PathBuf,
+    sender: Option<SyncSender<(GxiFrame, FrameRecord)>>,
+    handle: Option<JoinHandle<Result<()>>>,
+    stats: Arc<Mutex<RecorderStats>>,
+    started: Instant,
+    next_index: u64,
+    exposure_time: Option<f64>,
+    gain: Option<f64>,
+    chunk_ids: Option<ChunkIds>,
+}
+
+impl Recorder {
+    /// Create `dir` if needed and start the writer thread.
+    pub fn start<P: AsRef<Path>>(
+        dir: P,
+        format: RecordFormat,
+        queue_capacity: usize,
+    ) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        create_dir_all(&dir)?;
+        let sidecar = BufWriter::new(File::create(dir.join(SIDECAR_FILE))?);
+        let raw = if format == RecordFormat::Raw {
+            let mut raw = BufWriter::new(File::create(dir.join(RAW_FILE))?);
+            raw.write_all(RAW_MAGIC)?;
+            Some(raw)
+        } else {
+            None
+        };
+
+        let stats = Arc::new(Mutex::new(RecorderStats::default()));
+        let writer = RecordWriter {
+            dir: dir.clone(),
+            format,
+            sidecar,
+            raw,
+            raw_offset: RAW_MAGIC.len() as u64,
+            stats: stats.clone(),
+        };
+        let (sender, receiver) = sync_channel(queue_capacity.max(1));
+        let handle = spawn(move || writer.run(receiver));
+
+        println!("Successfully started recording {:?} to {:?}", format, dir);
+        Ok(Recorder {
+            dir,
+            sender: Some(sender),
+            handle: Some(handle),
+            stats,
+            started: Instant::now(),
+            next_index: 0,
+            exposure_time: None,
+            gain: None,
+            chunk_ids: None,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Exposure time in us and gain in dB to record for frames without chunk values.
+    pub fn set_settings(&mut self, exposure_time: Option<f64>, gain: Option<f64>) {
+        self.exposure_time = exposure_time;
+        self.gain = gain;
+    }
+
+    /// Chunk IDs to read exposure time and gain from frames sent with chunk data.
+    pub fn set_chunk_ids(&mut self, chunk_ids: ChunkIds) {
+        self.chunk_ids = Some(chunk_ids);
+    }
+
+    /// Read exposure time and gain from the device for `set_settings`.
+    #[cfg(feature = "solo")]
+    pub fn read_settings(&mut self) -> Result<()> {
+        self.set_settings(Some(gxi_get_exposure_time()?), Some(gxi_get_gain()?));
+        Ok(())
+    }
+
+    /// A copy of the counters so far.
+    pub fn stats(&self) -> RecorderStats {
+        self.stats
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Queue `frame` for writing. Returns `false` when it was dropped because the queue is
+    /// full, and an error when the writer has stopped, `finish` tells why.
+    pub fn push(&mut self, frame: GxiFrame) -> Result<bool> {
+        let sender = self.sender.as_ref().ok_or_else(writer_stopped)?;
+        let chunks = self.chunk_ids.and_then(|ids| frame.chunks(ids).ok());
+        let record = FrameRecord {
+            index: self.next_index,
+            frame_id: frame.frame_id,
+            timestamp: frame.timestamp,
+            received_at: Some(self.started.elapsed().as_secs_f64()),
+            width: frame.width,
+            height: frame.height,
+            pixel_format: frame.pixel_format,
+            status: frame.status,
+            exposure_time: chunks
+                .as_ref()
+                .and_then(|chunks| chunks.exposure_time())
+                .or(self.exposure_time),
+            gain: chunks
+                .as_ref()
+                .and_then(|chunks| chunks.gain())
+                .or(self.gain),
+            file: None,
+            offset: None,
+        };
+        let frame_id = frame.frame_id;
+
+        match sender.try_send((frame, record)) {
+            Ok(()) => {
+                self.next_index += 1;
+                self.stats
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .queued += 1;
+                Ok(true)
+            }
+            Err(TrySendError::Full(_)) => {
+                let mut stats = self.stats.lock().unwrap_or_else(PoisonError::into_inner);
+                stats.dropped += 1;
+                stats.dropped_frame_ids.push(frame_id);
+                Ok(false)
+            }
+            Err(TrySendError::Disconnected(_)) => Err(writer_stopped()),
+        }
+    }
+
+    /// Push every frame of `frames`, e.g. the frames of a `FrameSequence`.
+    pub fn record<I>(&mut self, frames: I) -> Result<()>
+    where
+        I: IntoIterator<Item = GxiFrame>,
+    {
+        for frame in frames {
+            self.push(frame)?;
+        }
+        Ok(())
+    }
+
+    /// Record `count` frames of the running acquisition.
+    #[cfg(feature = "solo")]
+    pub fn record_acquisition(&mut self, count: usize, timeout: u32) -> Result<()> {
+        for _ in 0..count {
+            let frame = gxi_get_frame(timeout)?;
+            self.push(frame)?;
+        }
+        Ok(())
+    }
+
+    /// Write the queued frames, stop the writer and return the final counters.
+    pub fn finish(mut self) -> Result<RecorderStats> {
+        self.shutdown()?;
+        let stats = self.stats();
+        println!(
+            "Successfully recorded {} frames to {:?}, {} dropped",
+            stats.written, self.dir, stats.dropped
+        );
+        Ok(stats)
+    }
+
+    fn shutdown(&mut self) -> Result<()> {
+        self.sender.take();
+        match self.handle.take() {
+            Some(handle) => handle.join().map_err(|_| {
+                Error::new(ErrorKind::FrameDataError(
+                    "recorder writer thread panicked".to_string(),
+                ))
+            })?,
+            None => Ok(()),
+        }
+    }
+}
+
+fn writer_stopped() -> Error {
+    Error::new(ErrorKind::FrameDataError(
+        "recorder writer has stopped, finish() returns its error".to_string(),
+    ))
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        if let Err(e) = self.shutdown() {
+            println!("Failed to finish recording: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(frame_id: u64, data_len: u64) -> RawFrameHeader {
+        RawFrameHeader {
+            frame_id,
+            timestamp: 1_000 + frame_id,
+            width: 4,
+            height: 2,
+            pixel_format: 0x0108_0001,
+            status: 0,
+            exposure_time: 1000.5,
+            gain: 2.0,
+            data_len,
+        }
+    }
+
+    fn container(frames: &[(RawFrameHeader, Vec<u8>)]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for (header, data) in frames {
+            bytes.extend_from_slice(&header.encode());
+            bytes.extend_from_slice(data);
+        }
+        bytes
+    }
+
+    #[test]
+    fn header_round_trips() {
+        let header = header(7, 8);
+        assert_eq!(RawFrameHeader::decode(&header.encode()), header);
+    }
+
+    #[test]
+    fn header_keeps_unknown_exposure_and_gain_as_nan() {
+        let header = RawFrameHeader {
+            exposure_time: f64::NAN,
+            gain: f64::NAN,
+            ..header(7, 8)
+        };
+        let decoded = RawFrameHeader::decode(&header.encode());
+        assert!(decoded.exposure_time.is_nan());
+        assert!(decoded.gain.is_nan());
+        assert_eq!(decoded.data_len, 8);
+    }
+
+    #[test]
+    fn reads_frames_until_end_of_container() {
+        let frames = vec![(header(1, 8), vec![1u8; 8]), (header(2, 12), vec![2u8; 12])];
+        let bytes = container(&frames);
+        let mut reader = bytes.as_slice();
+
+        for (header, data) in &frames {
+            let (read_header, frame) = RawFrameHeader::read_frame(&mut reader).unwrap().unwrap();
+            assert_eq!(read_header, *header);
+            assert_eq!(frame.frame_id, header.frame_id);
+            assert_eq!(frame.timestamp, header.timestamp);
+            assert_eq!(frame.data, *data);
+        }
+        assert!(RawFrameHeader::read_frame(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn oversized_data_len_is_an_error() {
+        let bytes = container(&[(header(1, u64::MAX), vec![1u8; 8])]);
+        let mut reader = bytes.as_slice();
+        assert!(RawFrameHeader::read_frame(&mut reader).is_err());
+    }
+
+    #[test]
+    fn truncated_frame_data_is_an_error() {
+        let bytes = container(&[(header(1, 8), vec![1u8; 8])]);
+        let mut reader = &bytes[..bytes.len() - 3];
+        assert!(RawFrameHeader::read_frame(&mut reader).is_err());
+    }
+}
@@ -0,0 +1,775 @@
+//! Portable feature snapshots: every readable feature of the device saved to TOML or JSON,
+//! so a camera recipe can live in version control and be restored on another device.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::raw::gx_enum::GX_FEATURE_ID;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+#[cfg(feature = "solo")]
+use crate::hal::config::*;
+#[cfg(feature = "solo")]
+use crate::raw::gx_enum::GX_FEATURE_TYPE;
+#[cfg(feature = "solo")]
+use crate::utils::matching::match_feature_type;
+
+/// A feature value as stored in a snapshot. Enum features keep their numeric value here and
+/// the symbolic name in `FeatureEntry::symbolic`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FeatureValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+/// One feature of a snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeatureEntry {
+    /// The `GX_FEATURE_ID` variant, e.g. `GX_FLOAT_EXPOSURE_TIME`.
+    pub id: String,
+    /// The SDK feature name, e.g. `ExposureTime`.
+    pub name: String,
+    pub value: FeatureValue,
+    /// The symbolic name of an enum value, e.g. `Continuous`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbolic: Option<String>,
+    /// Whether the feature was writable when the snapshot was taken, only those are restored.
+    pub writable: bool,
+    /// The selector entry the value belongs to, for features that have one value per entry
+    /// of a selector such as the line mode per line.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_by: Option<SelectorEntry>,
+}
+
+/// The selector entry a selector-indexed value was read under, e.g. `LineSelector` `Line2`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectorEntry {
+    /// The `GX_FEATURE_ID` variant of the selector, e.g. `GX_ENUM_LINE_SELECTOR`.
+    pub id: String,
+    pub value: i64,
+    /// The symbolic name of the entry, e.g. `Line2`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbolic: Option<String>,
+}
+
+impl FeatureEntry {
+    /// The feature this entry is for, `None` when the id is not known to this version.
+    pub fn feature_id(&self) -> Option<GX_FEATURE_ID> {
+        feature_id_from_name(&self.id)
+    }
+
+    /// The id, with the selector entry for selector-indexed values, e.g.
+    /// `GX_ENUM_LINE_MODE[GX_ENUM_LINE_SELECTOR=2]`. Unique within a snapshot.
+    pub fn key(&self) -> String {
+        match &self.selected_by {
+            Some(selector) => format!("{}[{}={}]", self.id, selector.id, selector.value),
+            None => self.id.clone(),
+        }
+    }
+
+    /// The feature name, with the selector entry for selector-indexed values, e.g.
+    /// `LineMode[Line2]`.
+    pub fn display_name(&self) -> String {
+        match &self.selected_by {
+            Some(selector) => match &selector.symbolic {
+                Some(symbolic) => format!("{}[{}]", self.name, symbolic),
+                None => format!("{}[{}]", self.name, selector.value),
+            },
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// Look up a `GX_FEATURE_ID` by its variant name.
+pub fn feature_id_from_name(name: &str) -> Option<GX_FEATURE_ID> {
+    GX_FEATURE_ID::ALL
+        .into_iter()
+        .find(|feature_id| format!("{:?}", feature_id) == name)
+}
+
+/// The readable features of a device, in `GX_FEATURE_ID` order, followed by the
+/// selector-indexed values for each selector entry.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeatureSnapshot {
+    /// Model of the device the snapshot was taken from, for reference only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_model: Option<String>,
+    /// Serial number of the device the snapshot was taken from, for reference only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_serial: Option<String>,
+    pub features: Vec<FeatureEntry>,
+}
+
+impl FeatureSnapshot {
+    /// The entry of a feature that is not selector-indexed.
+    pub fn get(&self, feature_id: GX_FEATURE_ID) -> Option<&FeatureEntry> {
+        let id = format!("{:?}", feature_id);
+        self.features
+            .iter()
+            .find(|entry| entry.id == id && entry.selected_by.is_none())
+    }
+
+    /// The entry of a selector-indexed feature for the selector entry `selector_value`.
+    pub fn get_selected(
+        &self,
+        feature_id: GX_FEATURE_ID,
+        selector_value: i64,
+    ) -> Option<&FeatureEntry> {
+        let id = format!("{:?}", feature_id);
+        self.features.iter().find(|entry| {
+            entry.id == id
+                && entry
+                    .selected_by
+                    .as_ref()
+                    .is_some_and(|selector| selector.value == selector_value)
+        })
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self).map_err(std::io::Error::other)?)
+    }
+
+    pub fn from_json(text: &str) -> Result<Self> {
+        serde_json::from_str(text).map_err(|e| {
+            Error::new(ErrorKind::InvalidParameter(format!(
+                "invalid JSON snapshot: {}",
+                e
+            )))
+        })
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string_pretty(self).map_err(std::io::Error::other)?)
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self> {
+        toml::from_str(text).map_err(|e| {
+            Error::new(ErrorKind::InvalidParameter(format!(
+                "invalid TOML snapshot: {}",
+                e
+            )))
+        })
+    }
+
+    /// Write the snapshot to `path`, as TOML or JSON by its `.toml` or `.json` extension.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let text = match SnapshotFormat::of(path.as_ref())? {
+            SnapshotFormat::Toml => self.to_toml()?,
+            SnapshotFormat::Json => self.to_json()?,
+        };
+        fs::write(path, text)?;
+        Ok(())
+    }
+
+    /// Read a snapshot written by `save`, as TOML or JSON by the extension of `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let format = SnapshotFormat::of(path.as_ref())?;
+        let text = fs::read_to_string(path)?;
+        match format {
+            SnapshotFormat::Toml => Self::from_toml(&text),
+            SnapshotFormat::Json => Self::from_json(&text),
+        }
+    }
+
+    /// The settings whose values differ from `other`, in the order of this snapshot with the
+    /// features only `other` has at the end. Selector-indexed values are compared per
+    /// selector entry. Read only features such as temperatures and counters change on their
+    /// own and are left out.
+    pub fn diff(&self, other: &FeatureSnapshot) -> Vec<FeatureDifference> {
+        let mut differences = Vec::new();
+        let mut push = |left: Option<&FeatureEntry>, right: Option<&FeatureEntry>| {
+            let entry = left.or(right).expect("one side has the feature");
+            let settable = left.is_some_and(|e| e.writable) || right.is_some_and(|e| e.writable);
+            let left_value = left.map(|e| e.value.clone());
+            let right_value = right.map(|e| e.value.clone());
+            if settable && left_value != right_value {
+                differences.push(FeatureDifference {
+                    id: entry.key(),
+                    name: entry.display_name(),
+                    left: left_value,
+                    right: right_value,
+                });
+            }
+        };
+
+        for entry in &self.features {
+            let key = entry.key();
+            push(Some(entry), other.features.iter().find(|e| e.key() == key));
+        }
+        for entry in &other.features {
+            let key = entry.key();
+            if !self.features.iter().any(|e| e.key() == key) {
+                push(None, Some(entry));
+            }
+        }
+        differences
+    }
+}
+
+/// A setting whose value differs between two snapshots, `None` where a snapshot lacks it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureDifference {
+    /// `FeatureEntry::key` of the setting.
+    pub id: String,
+    /// `FeatureEntry::display_name` of the setting.
+    pub name: String,
+    pub left: Option<FeatureValue>,
+    pub right: Option<FeatureValue>,
+}
+
+impl fmt::Display for FeatureValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FeatureValue::Bool(value) => write!(f, "{}", value),
+            FeatureValue::Int(value) => write!(f, "{}", value),
+            FeatureValue::Float(value) => write!(f, "{}", value),
+            FeatureValue::String(value) => write!(f, "{:?}", value),
+        }
+    }
+}
+
+impl fmt::Display for FeatureDifference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let show = |value: &Option<FeatureValue>| match value {
+            Some(value) => value.to_string(),
+            None => "(missing)".to_string(),
+        };
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.name,
+            show(&self.left),
+            show(&self.right)
+        )
+    }
+}
+
+enum SnapshotFormat {
+    Toml,
+    Json,
+}
+
+impl SnapshotFormat {
+    fn of(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("toml") => Ok(SnapshotFormat::Toml),
+            Some(extension) if extension.eq_ignore_ascii_case("json") => Ok(SnapshotFormat::Json),
+            _ => Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "snapshot path {:?} must end in .toml or .json",
+                path
+            )))),
+        }
+    }
+}
+
+//----------------------------------------------------------
+//---------------Take And Restore---------------------------
+//----------------------------------------------------------
+
+#[cfg(feature = "solo")]
+fn read_feature(feature_id: GX_FEATURE_ID, feature_type: GX_FEATURE_TYPE) -> Result<FeatureEntry> {
+    let value = match feature_type {
+        GX_FEATURE_TYPE::GX_FEATURE_INT => FeatureValue::Int(gxi_get_int(feature_id)?),
+        GX_FEATURE_TYPE::GX_FEATURE_FLOAT => FeatureValue::Float(gxi_get_float(feature_id)?),
+        GX_FEATURE_TYPE::GX_FEATURE_ENUM => FeatureValue::Int(gxi_get_enum(feature_id)?),
+        GX_FEATURE_TYPE::GX_FEATURE_BOOL => FeatureValue::Bool(gxi_get_bool(feature_id)?),
+        GX_FEATURE_TYPE::GX_FEATURE_STRING => FeatureValue::String(gxi_get_string(feature_id)?),
+        _ => {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "{:?} has no value to snapshot",
+                feature_id
+            ))));
+        }
+    };
+    let symbolic = match (&feature_type, &value) {
+        (GX_FEATURE_TYPE::GX_FEATURE_ENUM, FeatureValue::Int(enum_value)) => {
+            gxi_get_enum_description(feature_id)
+                .ok()
+                .and_then(|descriptions| {
+                    descriptions
+                        .into_iter()
+                        .find(|description| description.n_value == *enum_value)
+                })
+                .map(|description| description.sz_symbolic)
+        }
+        _ => None,
+    };
+
+    Ok(FeatureEntry {
+        id: format!("{:?}", feature_id),
+        name: gxi_get_feature_name(feature_id).unwrap_or_else(|_| format!("{:?}", feature_id)),
+        value,
+        symbolic,
+        writable: gxi_is_writable(feature_id).unwrap_or(false),
+        selected_by: None,
+    })
+}
+
+// 这些feature按选择器的每个取值各存一份，只读当前选中的那份会丢掉其余的，
+// 比如白平衡只剩一个通道、IO只剩一条线，所以快照和恢复时都要逐个切换选择器
+const SELECTED_FEATURES: &[(GX_FEATURE_ID, &[GX_FEATURE_ID])] = {
+    use GX_FEATURE_ID::*;
+
+    &[
+        (
+            GX_ENUM_TRIGGER_SELECTOR,
+            &[
+                GX_ENUM_TRIGGER_MODE,
+                GX_ENUM_TRIGGER_SOURCE,
+                GX_ENUM_TRIGGER_ACTIVATION,
+            ],
+        ),
+        (GX_ENUM_USER_OUTPUT_SELECTOR, &[GX_BOOL_USER_OUTPUT_VALUE]),
+        (
+            GX_ENUM_LINE_SELECTOR,
+            &[
+                GX_ENUM_LINE_MODE,
+                GX_BOOL_LINE_INVERTER,
+                GX_ENUM_LINE_SOURCE,
+            ],
+        ),
+        (GX_ENUM_GAIN_SELECTOR, &[GX_FLOAT_GAIN]),
+        (GX_ENUM_BLACKLEVEL_SELECTOR, &[GX_FLOAT_BLACKLEVEL]),
+        (GX_ENUM_BALANCE_RATIO_SELECTOR, &[GX_FLOAT_BALANCE_RATIO]),
+        (GX_ENUM_EVENT_SELECTOR, &[GX_ENUM_EVENT_NOTIFICATION]),
+        (GX_ENUM_CHUNK_SELECTOR, &[GX_BOOL_CHUNK_ENABLE]),
+        (
+            GX_ENUM_COLOR_TRANSFORMATION_VALUE_SELECTOR,
+            &[GX_FLOAT_COLOR_TRANSFORMATION_VALUE],
+        ),
+        (
+            GX_ENUM_TIMER_SELECTOR,
+            &[
+                GX_FLOAT_TIMER_DURATION,
+                GX_FLOAT_TIMER_DELAY,
+                GX_ENUM_TIMER_TRIGGER_SOURCE,
+                GX_ENUM_TIMER_TRIGGER_ACTIVATION,
+            ],
+        ),
+        (
+            GX_ENUM_COUNTER_SELECTOR,
+            &[
+                GX_ENUM_COUNTER_EVENT_SOURCE,
+                GX_ENUM_COUNTER_RESET_SOURCE,
+                GX_ENUM_COUNTER_RESET_ACTIVATION,
+                GX_ENUM_COUNTER_TRIGGER_SOURCE,
+                GX_INT_COUNTER_DURATION,
+            ],
+        ),
+    ]
+};
+
+/// The selector a feature has one value per entry of, see `FeatureEntry::selected_by`.
+pub fn selector_of(feature_id: GX_FEATURE_ID) -> Option<GX_FEATURE_ID> {
+    SELECTED_FEATURES
+        .iter()
+        .find(|(_, features)| {
+            features
+                .iter()
+                .any(|feature| *feature as isize == feature_id as isize)
+        })
+        .map(|(selector, _)| *selector)
+}
+
+#[cfg(feature = "solo")]
+fn is_selector(feature_id: GX_FEATURE_ID) -> bool {
+    SELECTED_FEATURES
+        .iter()
+        .any(|(selector, _)| *selector as isize == feature_id as isize)
+}
+
+#[cfg(feature = "solo")]
+fn is_snapshot_readable(feature_id: GX_FEATURE_ID) -> bool {
+    // 有些相机对数据流层或者不认识的feature直接返回错误而不是未实现，当作没有处理
+    gxi_is_implemented(feature_id).unwrap_or(false) && gxi_is_readable(feature_id).unwrap_or(false)
+}
+
+/// Read `features` under each entry of `selector`, and select the entry that was selected
+/// before again.
+#[cfg(feature = "solo")]
+fn read_selected_features(
+    selector: GX_FEATURE_ID,
+    features: &[GX_FEATURE_ID],
+) -> Result<Vec<FeatureEntry>> {
+    if !is_snapshot_readable(selector) {
+        return Ok(Vec::new());
+    }
+    let selected = gxi_get_enum(selector)?;
+    // 选择器不可写时只能读当前选中的那一份
+    let switchable = gxi_is_writable(selector).unwrap_or(false);
+    let mut entries = Vec::new();
+    let read = (|| -> Result<()> {
+        for description in gxi_get_enum_description(selector)? {
+            if description.n_value != selected
+                && (!switchable || gxi_set_enum(selector, description.n_value).is_err())
+            {
+                continue;
+            }
+            for feature_id in features {
+                if !is_snapshot_readable(*feature_id) {
+                    continue;
+                }
+                match read_feature(*feature_id, match_feature_type(*feature_id)) {
+                    Ok(mut entry) => {
+                        entry.selected_by = Some(SelectorEntry {
+                            id: format!("{:?}", selector),
+                            value: description.n_value,
+                            symbolic: Some(description.sz_symbolic.clone()),
+                        });
+                        entries.push(entry);
+                    }
+                    Err(e) => println!(
+                        "Skipped {:?} for {} in snapshot: {:?}",
+                        feature_id, description.sz_symbolic, e
+                    ),
+                }
+            }
+        }
+        Ok(())
+    })();
+    let reselected = if switchable {
+        gxi_set_enum(selector, selected)
+    } else {
+        Ok(())
+    };
+    read?;
+    reselected?;
+    Ok(entries)
+}
+
+/// Read every implemented and readable feature of the opened device. Commands and buffers
+/// have no value and are left out, features that fail to read are skipped with a message.
+/// Selector-indexed features such as the line mode are read for every entry of their
+/// selector, which is switched through and then put back.
+#[cfg(feature = "solo")]
+pub fn gxi_take_snapshot() -> Result<FeatureSnapshot> {
+    let mut snapshot = FeatureSnapshot::default();
+    for feature_id in GX_FEATURE_ID::ALL {
+        let feature_type = match_feature_type(feature_id);
+        if matches!(
+            feature_type,
+            GX_FEATURE_TYPE::GX_FEATURE_COMMAND | GX_FEATURE_TYPE::GX_FEATURE_BUFFER
+        ) || selector_of(feature_id).is_some()
+        {
+            continue;
+        }
+        if !is_snapshot_readable(feature_id) {
+            continue;
+        }
+        match read_feature(feature_id, feature_type) {
+            Ok(entry) => snapshot.features.push(entry),
+            Err(e) => println!("Skipped {:?} in snapshot: {:?}", feature_id, e),
+        }
+    }
+    for (selector, features) in SELECTED_FEATURES {
+        match read_selected_features(*selector, features) {
+            Ok(entries) => snapshot.features.extend(entries),
+            Err(e) => println!(
+                "Skipped the features under {:?} in snapshot: {:?}",
+                selector, e
+            ),
+        }
+    }
+
+    let string_of = |snapshot: &FeatureSnapshot, feature_id| match snapshot
+        .get(feature_id)
+        .map(|entry| &entry.value)
+    {
+        Some(FeatureValue::String(value)) => Some(value.clone()),
+        _ => None,
+    };
+    snapshot.device_model = string_of(&snapshot, GX_FEATURE_ID::GX_STRING_DEVICE_MODEL_NAME);
+    snapshot.device_serial = string_of(&snapshot, GX_FEATURE_ID::GX_STRING_DEVICE_SERIAL_NUMBER);
+    println!(
+        "Successfully took snapshot of {} features",
+        snapshot.features.len()
+    );
+    Ok(snapshot)
+}
+
+/// What `gxi_restore_snapshot` did with each feature of the snapshot, by `FeatureEntry::key`.
+#[derive(Debug, Clone, Default)]
+pub struct RestoreReport {
+    pub applied: Vec<String>,
+    /// Read only in the snapshot, unknown, not implemented or not writable on this device.
+    pub skipped: Vec<String>,
+    /// The features the device refused, with the error.
+    pub failed: Vec<(String, String)>,
+}
+
+impl RestoreReport {
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+// 像素格式和binning会改变宽高的范围，宽高又限制offset的范围，所以要按这个顺序写；
+// 按选择器分别写的值要切换选择器，选择器本身放到最后写，恢复成快照时选中的那一项
+#[cfg(feature = "solo")]
+fn restore_stage(feature_id: GX_FEATURE_ID) -> u8 {
+    if is_selector(feature_id) {
+        return 4;
+    }
+    match feature_id {
+        GX_FEATURE_ID::GX_ENUM_PIXEL_FORMAT
+        | GX_FEATURE_ID::GX_ENUM_BINNING_HORIZONTAL_MODE
+        | GX_FEATURE_ID::GX_ENUM_BINNING_VERTICAL_MODE
+        | GX_FEATURE_ID::GX_INT_BINNING_HORIZONTAL
+        | GX_FEATURE_ID::GX_INT_BINNING_VERTICAL
+        | GX_FEATURE_ID::GX_INT_DECIMATION_HORIZONTAL
+        | GX_FEATURE_ID::GX_INT_DECIMATION_VERTICAL => 0,
+        GX_FEATURE_ID::GX_INT_WIDTH | GX_FEATURE_ID::GX_INT_HEIGHT => 1,
+        GX_FEATURE_ID::GX_INT_OFFSET_X | GX_FEATURE_ID::GX_INT_OFFSET_Y => 2,
+        _ => 3,
+    }
+}
+
+#[cfg(feature = "solo")]
+fn is_auto_feature(feature_id: GX_FEATURE_ID) -> bool {
+    matches!(
+        feature_id,
+        GX_FEATURE_ID::GX_ENUM_EXPOSURE_AUTO
+            | GX_FEATURE_ID::GX_ENUM_GAIN_AUTO
+            | GX_FEATURE_ID::GX_ENUM_BLACKLEVEL_AUTO
+            | GX_FEATURE_ID::GX_ENUM_BALANCE_WHITE_AUTO
+    )
+}
+
+#[cfg(feature = "solo")]
+fn write_feature(feature_id: GX_FEATURE_ID, value: &FeatureValue) -> Result<()> {
+    match (match_feature_type(feature_id), value) {
+        (GX_FEATURE_TYPE::GX_FEATURE_INT, FeatureValue::Int(value)) => {
+            gxi_set_int(feature_id, *value)
+        }
+        (GX_FEATURE_TYPE::GX_FEATURE_FLOAT, FeatureValue::Float(value)) => {
+            gxi_set_float(feature_id, *value)
+        }
+        // 手写的文件里浮点数可能没有小数点，会被读成整数
+        (GX_FEATURE_TYPE::GX_FEATURE_FLOAT, FeatureValue::Int(value)) => {
+            gxi_set_float(feature_id, *value as f64)
+        }
+        (GX_FEATURE_TYPE::GX_FEATURE_ENUM, FeatureValue::Int(value)) => {
+            gxi_set_enum(feature_id, *value)
+        }
+        (GX_FEATURE_TYPE::GX_FEATURE_BOOL, FeatureValue::Bool(value)) => {
+            gxi_set_bool(feature_id, *value)
+        }
+        (GX_FEATURE_TYPE::GX_FEATURE_STRING, FeatureValue::String(value)) => {
+            gxi_set_string(feature_id, value)
+        }
+        (feature_type, value) => Err(Error::new(ErrorKind::InvalidParameter(format!(
+            "{:?} value {:?} does not match feature type {:?}",
+            feature_id, value, feature_type
+        )))),
+    }
+}
+
+// 按选择器分别存的值先切到它的那一项，是否可写也要切过去之后才查得准
+#[cfg(feature = "solo")]
+fn select_entry(entry: &FeatureEntry) -> Result<bool> {
+    let Some(selector) = &entry.selected_by else {
+        return Ok(true);
+    };
+    let selector_id = feature_id_from_name(&selector.id).ok_or_else(|| {
+        Error::new(ErrorKind::InvalidParameter(format!(
+            "unknown selector {}",
+            selector.id
+        )))
+    })?;
+    gxi_set_enum(selector_id, selector.value)?;
+    Ok(entry
+        .feature_id()
+        .is_some_and(|feature_id| gxi_is_writable(feature_id).unwrap_or(false)))
+}
+
+/// Write the writable features of `snapshot` to the opened device, in dependency order:
+/// auto modes off, then pixel format and binning, then the ROI, then the other values with
+/// the selector-indexed ones under their selector entry, then the selectors, and the auto
+/// modes last so the manual values are not overridden. Stop acquisition first, the device
+/// locks the format and ROI while it runs. Individual failures are collected in the report
+/// instead of stopping the restore.
+#[cfg(feature = "solo")]
+pub fn gxi_restore_snapshot(snapshot: &FeatureSnapshot) -> Result<RestoreReport> {
+    let mut report = RestoreReport::default();
+    let mut pending: Vec<(GX_FEATURE_ID, &FeatureEntry)> = Vec::new();
+    for entry in &snapshot.features {
+        match entry.feature_id() {
+            Some(feature_id)
+                if entry.writable
+                    && gxi_is_implemented(feature_id).unwrap_or(false)
+                    && (entry.selected_by.is_some()
+                        || gxi_is_writable(feature_id).unwrap_or(false)) =>
+            {
+                pending.push((feature_id, entry))
+            }
+            _ => report.skipped.push(entry.key()),
+        }
+    }
+
+    // 自动曝光之类开着的时候手动值写不进去或者马上被覆盖，先关掉，最后再恢复成快照里的值
+    let (autos, mut manuals): (Vec<_>, Vec<_>) = pending
+        .into_iter()
+        .partition(|(feature_id, _)| is_auto_feature(*feature_id));
+    for (feature_id, _) in &autos {
+        let _ = gxi_set_enum(*feature_id, 0);
+    }
+
+    manuals.sort_by_key(|(feature_id, _)| restore_stage(*feature_id));
+    // offset先归零，否则新的宽高加上旧的offset可能超出传感器范围
+    for (feature_id, _) in manuals.iter().filter(|(id, _)| restore_stage(*id) == 2) {
+        let _ = gxi_set_int(*feature_id, 0);
+    }
+
+    for (feature_id, entry) in manuals.iter().chain(autos.iter()) {
+        match select_entry(entry).and_then(|writable| {
+            if writable {
+                write_feature(*feature_id, &entry.value).map(|_| true)
+            } else {
+                Ok(false)
+            }
+        }) {
+            Ok(true) => report.applied.push(entry.key()),
+            Ok(false) => report.skipped.push(entry.key()),
+            Err(e) => report.failed.push((entry.key(), format!("{:?}", e))),
+        }
+    }
+
+    println!(
+        "Restored snapshot: {} applied, {} skipped, {} failed",
+        report.applied.len(),
+        report.skipped.len(),
+        report.failed.len()
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, value: FeatureValue, writable: bool) -> FeatureEntry {
+        FeatureEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            value,
+            symbolic: None,
+            writable,
+            selected_by: None,
+        }
+    }
+
+    fn line_mode(line: i64, mode: i64) -> FeatureEntry {
+        FeatureEntry {
+            id: "GX_ENUM_LINE_MODE".to_string(),
+            name: "LineMode".to_string(),
+            value: FeatureValue::Int(mode),
+            symbolic: Some(if mode == 0 { "Input" } else { "Output" }.to_string()),
+            writable: true,
+            selected_by: Some(SelectorEntry {
+                id: "GX_ENUM_LINE_SELECTOR".to_string(),
+                value: line,
+                symbolic: Some(format!("Line{}", line)),
+            }),
+        }
+    }
+
+    fn snapshot() -> FeatureSnapshot {
+        FeatureSnapshot {
+            device_model: Some("MER-139".to_string()),
+            device_serial: None,
+            features: vec![
+                entry("GX_INT_WIDTH", FeatureValue::Int(1280), true),
+                // 整数值的浮点数也要读回浮点数，不能变成整数
+                entry("GX_FLOAT_EXPOSURE_TIME", FeatureValue::Float(1000.0), true),
+                entry("GX_FLOAT_GAIN", FeatureValue::Float(2.5), true),
+                entry("GX_BOOL_GAMMA_ENABLE", FeatureValue::Bool(true), true),
+                entry(
+                    "GX_STRING_DEVICE_USERID",
+                    FeatureValue::String("left".to_string()),
+                    true,
+                ),
+                entry(
+                    "GX_FLOAT_DEVICE_TEMPERATURE",
+                    FeatureValue::Float(40.5),
+                    false,
+                ),
+                line_mode(0, 0),
+                line_mode(1, 1),
+            ],
+        }
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let snapshot = snapshot();
+        assert_eq!(
+            FeatureSnapshot::from_toml(&snapshot.to_toml().unwrap()).unwrap(),
+            snapshot
+        );
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let snapshot = snapshot();
+        assert_eq!(
+            FeatureSnapshot::from_json(&snapshot.to_json().unwrap()).unwrap(),
+            snapshot
+        );
+    }
+
+    #[test]
+    fn looks_up_selector_indexed_values_by_entry() {
+        let snapshot = snapshot();
+        assert!(snapshot.get(GX_FEATURE_ID::GX_ENUM_LINE_MODE).is_none());
+        let line1 = snapshot
+            .get_selected(GX_FEATURE_ID::GX_ENUM_LINE_MODE, 1)
+            .unwrap();
+        assert_eq!(line1.value, FeatureValue::Int(1));
+        assert_eq!(line1.key(), "GX_ENUM_LINE_MODE[GX_ENUM_LINE_SELECTOR=1]");
+        assert_eq!(line1.display_name(), "LineMode[Line1]");
+        assert!(
+            selector_of(GX_FEATURE_ID::GX_FLOAT_BALANCE_RATIO).is_some_and(|selector| {
+                selector as isize == GX_FEATURE_ID::GX_ENUM_BALANCE_RATIO_SELECTOR as isize
+            })
+        );
+        assert!(selector_of(GX_FEATURE_ID::GX_INT_WIDTH).is_none());
+    }
+
+    #[test]
+    fn diff_reports_changed_missing_and_per_selector_settings() {
+        let left = snapshot();
+        let mut right = snapshot();
+        right.features[2].value = FeatureValue::Float(3.0);
+        right.features[5].value = FeatureValue::Float(45.0);
+        right.features[7].value = FeatureValue::Int(0);
+        right.features.remove(3);
+        right
+            .features
+            .push(entry("GX_INT_OFFSET_X", FeatureValue::Int(16), true));
+
+        let differences = left.diff(&right);
+        let summary = differences
+            .iter()
+            .map(|difference| difference.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            summary,
+            vec![
+                "GX_FLOAT_GAIN: 2.5 -> 3",
+                "GX_BOOL_GAMMA_ENABLE: true -> (missing)",
+                "LineMode[Line1]: 1 -> 0",
+                "GX_INT_OFFSET_X: (missing) -> 16",
+            ]
+        );
+        assert_eq!(
+            differences[2].id,
+            "GX_ENUM_LINE_MODE[GX_ENUM_LINE_SELECTOR=1]"
+        );
+        assert!(left.diff(&left).is_empty());
+    }
+}
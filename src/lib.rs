@@ -9,3 +9,6 @@ pub use opencv;
 
 #[cfg(feature = "use-imageproc")]
 pub use imageproc;
+
+#[cfg(feature = "use-ndarray")]
+pub use ndarray;
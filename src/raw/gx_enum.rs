@@ -502,6 +502,54 @@ pub enum GX_FEATURE_ID {
         | GX_FEATURE_LEVEL::GX_FEATURE_LEVEL_REMOTE_DEV as u32)
         as isize, //< Trigger Cache Enable
 
+    //----------------DigitalIO Section----------------------------------
+    GX_ENUM_USER_OUTPUT_SELECTOR = (4000
+        | GX_FEATURE_TYPE::GX_FEATURE_ENUM as u32
+        | GX_FEATURE_LEVEL::GX_FEATURE_LEVEL_REMOTE_DEV as u32)
+        as isize, //< Selects which bit of the User Output register will be set by UserOutputValue.
+    GX_BOOL_USER_OUTPUT_VALUE = (4001
+        | GX_FEATURE_TYPE::GX_FEATURE_BOOL as u32
+        | GX_FEATURE_LEVEL::GX_FEATURE_LEVEL_REMOTE_DEV as u32)
+        as isize, //< Sets the value of the bit selected by UserOutputSelector.
+    GX_ENUM_USER_OUTPUT_MODE = (4002
+        | GX_FEATURE_TYPE::GX_FEATURE_ENUM as u32
+        | GX_FEATURE_LEVEL::GX_FEATURE_LEVEL_REMOTE_DEV as u32)
+        as isize, //< Output signal can be used for different purposes, flash or a user-defined constant level
+    GX_ENUM_STROBE_SWITCH = (4003
+        | GX_FEATURE_TYPE::GX_FEATURE_ENUM as u32
+        | GX_FEATURE_LEVEL::GX_FEATURE_LEVEL_REMOTE_DEV as u32)
+        as isize, //< Set the flash light switch
+    GX_ENUM_LINE_SELECTOR = (4004
+        | GX_FEATURE_TYPE::GX_FEATURE_ENUM as u32
+        | GX_FEATURE_LEVEL::GX_FEATURE_LEVEL_REMOTE_DEV as u32)
+        as isize, //< Selects the physical line (or pin) of the external device connector to configure.
+    GX_ENUM_LINE_MODE = (4005
+        | GX_FEATURE_TYPE::GX_FEATURE_ENUM as u32
+        | GX_FEATURE_LEVEL::GX_FEATURE_LEVEL_REMOTE_DEV as u32) as isize, //< Controls if the physical Line is used to Input or Output a signal.
+    GX_BOOL_LINE_INVERTER = (4006
+        | GX_FEATURE_TYPE::GX_FEATURE_BOOL as u32
+        | GX_FEATURE_LEVEL::GX_FEATURE_LEVEL_REMOTE_DEV as u32)
+        as isize, //< Controls the inversion of the signal of the selected input or output Line.
+    GX_ENUM_LINE_SOURCE = (4007
+        | GX_FEATURE_TYPE::GX_FEATURE_ENUM as u32
+        | GX_FEATURE_LEVEL::GX_FEATURE_LEVEL_REMOTE_DEV as u32) as isize, //< Selects which internal acquisition or I/O source signal to output on the selected Line.
+    GX_BOOL_LINE_STATUS = (4008
+        | GX_FEATURE_TYPE::GX_FEATURE_BOOL as u32
+        | GX_FEATURE_LEVEL::GX_FEATURE_LEVEL_REMOTE_DEV as u32) as isize, //< Returns the current status of the selected input or output Line.
+    GX_INT_LINE_STATUS_ALL = (4009
+        | GX_FEATURE_TYPE::GX_FEATURE_INT as u32
+        | GX_FEATURE_LEVEL::GX_FEATURE_LEVEL_REMOTE_DEV as u32)
+        as isize, //< Returns the current status of all available Line signals at time of polling in a single bit field.
+    GX_FLOAT_PULSE_WIDTH = (4010
+        | GX_FEATURE_TYPE::GX_FEATURE_FLOAT as u32
+        | GX_FEATURE_LEVEL::GX_FEATURE_LEVEL_REMOTE_DEV as u32) as isize, //< User-defined pulse width
+    GX_INT_LINE_RANGE = (4011
+        | GX_FEATURE_TYPE::GX_FEATURE_INT as u32
+        | GX_FEATURE_LEVEL::GX_FEATURE_LEVEL_REMOTE_DEV as u32) as isize, //< flash line ragne
+    GX_INT_LINE_DELAY = (4012
+        | GX_FEATURE_TYPE::GX_FEATURE_INT as u32
+        | GX_FEATURE_LEVEL::GX_FEATURE_LEVEL_REMOTE_DEV as u32) as isize, //< flash line delay
+
     //----------------AnalogControls Section----------------------------
     GX_ENUM_GAIN_AUTO = (5000
         | GX_FEATURE_TYPE::GX_FEATURE_ENUM as u32
@@ -1360,3 +1408,891 @@ pub enum GX_RESET_DEVICE_MODE {
 }
 
 pub type GX_RESET_DEVICE_MODE_CMD = i32;
+
+//------------------------------------------------------------------------------
+//  Data Stream Entry Definition
+//------------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_DS_RESEND_MODE_ENTRY {
+    GX_DS_RESEND_MODE_OFF = 0, //< Turn off resend mode
+    GX_DS_RESEND_MODE_ON = 1,  //< Turn on resend mode
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_DS_STREAM_BUFFER_HANDLING_MODE_ENTRY {
+    GX_DS_STREAM_BUFFER_HANDLING_MODE_OLDEST_FIRST = 1, //< OldestFirst Mode
+    GX_DS_STREAM_BUFFER_HANDLING_MODE_OLDEST_FIRST_OVERWRITE = 2, //< OldestFirstOverwrite Mode
+    GX_DS_STREAM_BUFFER_HANDLING_MODE_NEWEST_ONLY = 3,  //< NewestOnly Mode
+}
+
+//------------------------------------------------------------------------------
+//  Acquisition Entry Definition
+//------------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_ACQUISITION_MODE_ENTRY {
+    GX_ACQ_MODE_SINGLE_FRAME = 0, //< Single frame mode
+    GX_ACQ_MODE_MULITI_FRAME = 1, //< Multi frame mode
+    GX_ACQ_MODE_CONTINUOUS = 2,   //< Continuous mode
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_ACQUISITION_FRAME_RATE_MODE_ENTRY {
+    GX_ACQUISITION_FRAME_RATE_MODE_OFF = 0, //< Turn off frame rate control mode
+    GX_ACQUISITION_FRAME_RATE_MODE_ON = 1,  //< Turn on frame rate control mode
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_ACQUISITION_BURST_MODE_ENTRY {
+    GX_ENUM_ACQUISITION_BURST_MODE_STANDARD = 0, //< Standard mode
+    GX_ENUM_ACQUISITION_BURST_MODE_HIGH_SPEED = 1, //< High-speed mode
+}
+
+//------------------------------------------------------------------------------
+//  Acquisition Trigger Entry Definition
+//------------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_TRIGGER_SELECTOR_ENTRY {
+    GX_ENUM_TRIGGER_SELECTOR_FRAME_START = 1, //< Capture single frame
+    GX_ENUM_TRIGGER_SELECTOR_FRAME_BURST_START = 2, //< High speed continuous shooting
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_TRIGGER_MODE_ENTRY {
+    GX_TRIGGER_MODE_OFF = 0, //< Switch off the trigger mode
+    GX_TRIGGER_MODE_ON = 1,  //< Switch on the trigger mode
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_TRIGGER_SOURCE_ENTRY {
+    GX_TRIGGER_SOURCE_SOFTWARE = 0,    //< Software trigger
+    GX_TRIGGER_SOURCE_LINE0 = 1,       //< Trigger source 0
+    GX_TRIGGER_SOURCE_LINE1 = 2,       //< Trigger source 1
+    GX_TRIGGER_SOURCE_LINE2 = 3,       //< Trigger source 2
+    GX_TRIGGER_SOURCE_LINE3 = 4,       //< Trigger source 3
+    GX_TRIGGER_SOURCE_COUNTER2END = 5, //< Counter 2 end trigger
+    GX_TRIGGER_SOURCE_TRIGGER = 6,     //< Trigger source
+    GX_TRIGGER_SOURCE_MULTISOURCE = 7, //< MultiSource
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_TRIGGER_ACTIVATION_ENTRY {
+    GX_TRIGGER_ACTIVATION_FALLINGEDGE = 0, //< Falling edge trigger
+    GX_TRIGGER_ACTIVATION_RISINGEDGE = 1,  //< Rising edge trigger
+    GX_TRIGGER_ACTIVATION_ANYEDGE = 2,     //< Falling or Rising edge trigger
+    GX_TRIGGER_ACTIVATION_LEVELHIGH = 3,   //< High trigger
+    GX_TRIGGER_ACTIVATION_LEVELLOW = 4,    //< Low trigger
+}
+
+//------------------------------------------------------------------------------
+//  Event Entry Definition
+//------------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_EVENT_SELECTOR_ENTRY {
+    GX_ENUM_EVENT_SELECTOR_EXPOSUREEND = 0x0004, //< End of exposure
+    GX_ENUM_EVENT_SELECTOR_BLOCK_DISCARD = 0x9000, //< Image frame discarding
+    GX_ENUM_EVENT_SELECTOR_EVENT_OVERRUN = 0x9001, //< Event queue overflow
+    GX_ENUM_EVENT_SELECTOR_FRAMESTART_OVERTRIGGER = 0x9002, //< Trigger signal overflow
+    GX_ENUM_EVENT_SELECTOR_BLOCK_NOT_EMPTY = 0x9003, //< Image frame memory is not empty
+    GX_ENUM_EVENT_SELECTOR_INTERNAL_ERROR = 0x9004, //< Internal error events
+    GX_ENUM_EVENT_SELECTOR_FRAMEBURSTSTART_OVERTRIGGER = 0x9005, //< Multi frame trigger mask event
+    GX_ENUM_EVENT_SELECTOR_FRAMESTART_WAIT = 0x9006, //< Frame Wait Event
+    GX_ENUM_EVENT_SELECTOR_FRAMEBURSTSTART_WAIT = 0x9007, //< Multi frame wait event
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_EVENT_NOTIFICATION_ENTRY {
+    GX_ENUM_EVENT_NOTIFICATION_OFF = 0, //< Turn off event
+    GX_ENUM_EVENT_NOTIFICATION_ON = 1,  //< Turn on event
+}
+
+//------------------------------------------------------------------------------
+//  Image Format Entry Definition
+//------------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_SENSOR_BIT_DEPTH_ENTRY {
+    GX_SENSOR_BIT_DEPTH_BPP8 = 8,
+    GX_SENSOR_BIT_DEPTH_BPP10 = 10,
+    GX_SENSOR_BIT_DEPTH_BPP12 = 12,
+}
+
+//------------------------------------------------------------------------------
+//  LUT Entry Definition
+//------------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_LUT_SELECTOR_ENTRY {
+    GX_ENUM_LUT_SELECTOR_LUMINANCE = 0, //< Luminance
+}
+
+//------------------------------------------------------------------------------
+//  Counter And Timer Entry Definition
+//------------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_TIMER_SELECTOR_ENTRY {
+    GX_TIMER_SELECTOR_TIMER1 = 1, //< Timer 1
+    GX_TIMER_SELECTOR_TIMER2 = 2, //< Timer2
+    GX_TIMER_SELECTOR_TIMER3 = 3, //< Timer3
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_TIMER_TRIGGER_SOURCE_ENTRY {
+    GX_TIMER_TRIGGER_SOURCE_EXPOSURE_START = 1, //< Exposure start
+    GX_TIMER_TRIGGER_SOURCE_LINE10 = 10,        //< Receive pin 10 signal start timing
+    GX_TIMER_TRIGGER_SOURCE_LINE14 = 14,        //< Receive pin 14 signal start timing
+    GX_TIMER_TRIGGER_SOURCE_STROBE = 16,        //< Start timing when receiving flash signal
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_TIMER_TRIGGER_ACTIVATION_ENTRY {
+    GX_TIMER_TRIGGER_ACTIVATION_RISINGEDGE = 0, //< Trigger of rising edge of timer
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_COUNTER_SELECTOR_ENTRY {
+    GX_COUNTER_SELECTOR_COUNTER1 = 1, //< Counter 1
+    GX_COUNTER_SELECTOR_COUNTER2 = 2, //< Counter2
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_COUNTER_EVENT_SOURCE_ENTRY {
+    GX_COUNTER_EVENT_SOURCE_FRAME_START = 1,   //< Frame start
+    GX_COUNTER_EVENT_SOURCE_FRAME_TRIGGER = 2, //< Count the number of "frame triggered" events
+    GX_COUNTER_EVENT_SOURCE_ACQUISITION_TRIGGER = 3, //< Count the number of "Collection Triggered" events
+    GX_COUNTER_EVENT_SOURCE_OFF = 4,                 //< Close
+    GX_COUNTER_EVENT_SOURCE_SOFTWARE = 5,            //< Count the number of "soft trigger" events
+    GX_COUNTER_EVENT_SOURCE_LINE0 = 6, //< Count the number of "Line 0 Triggered" events
+    GX_COUNTER_EVENT_SOURCE_LINE1 = 7, //< Count the number of "Line 1 Triggered" events
+    GX_COUNTER_EVENT_SOURCE_LINE2 = 8, //< Count the number of "Line 2 Triggered" events
+    GX_COUNTER_EVENT_SOURCE_LINE3 = 9, //< Count the number of "Line 3 Triggered" events
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_COUNTER_RESET_SOURCE_ENTRY {
+    GX_COUNTER_RESET_SOURCE_OFF = 0,         //< Counter reset off
+    GX_COUNTER_RESET_SOURCE_SOFTWARE = 1,    //< Software
+    GX_COUNTER_RESET_SOURCE_LINE0 = 2,       //< Line 0
+    GX_COUNTER_RESET_SOURCE_LINE1 = 3,       //< Line 1
+    GX_COUNTER_RESET_SOURCE_LINE2 = 4,       //< Line 2
+    GX_COUNTER_RESET_SOURCE_LINE3 = 5,       //< Line 3
+    GX_COUNTER_RESET_SOURCE_COUNTER2END = 6, //< Counter2End
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_COUNTER_TRIGGER_SOURCE_ENTRY {
+    GX_COUNTER_TRIGGER_SOURCE_OFF = 0,      //< Close
+    GX_COUNTER_TRIGGER_SOURCE_SOFTWARE = 1, //< Soft trigger
+    GX_COUNTER_TRIGGER_SOURCE_LINE0 = 2,    //< Line 0
+    GX_COUNTER_TRIGGER_SOURCE_LINE1 = 3,    //< Line 1
+    GX_COUNTER_TRIGGER_SOURCE_LINE2 = 4,    //< Line 2
+    GX_COUNTER_TRIGGER_SOURCE_LINE3 = 5,    //< Line 3
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_COUNTER_RESET_ACTIVATION_ENTRY {
+    GX_COUNTER_RESET_ACTIVATION_RISING_EDGE = 1, //< Rising edge counter reset.
+}
+
+//------------------------------------------------------------------------------
+//  Digital IO Entry Definition
+//------------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_USER_OUTPUT_SELECTOR_ENTRY {
+    GX_USER_OUTPUT_SELECTOR_OUTPUT0 = 1, //< Output 0
+    GX_USER_OUTPUT_SELECTOR_OUTPUT1 = 2, //< Output 1
+    GX_USER_OUTPUT_SELECTOR_OUTPUT2 = 4, //< Output 2
+    GX_USER_OUTPUT_SELECTOR_OUTPUT3 = 5, //< Output 3
+    GX_USER_OUTPUT_SELECTOR_OUTPUT4 = 6, //< Output 4
+    GX_USER_OUTPUT_SELECTOR_OUTPUT5 = 7, //< Output 5
+    GX_USER_OUTPUT_SELECTOR_OUTPUT6 = 8, //< Output 6
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_USER_OUTPUT_MODE_ENTRY {
+    GX_USER_OUTPUT_MODE_STROBE = 0,      //< Strobe light
+    GX_USER_OUTPUT_MODE_USERDEFINED = 1, //< User defined
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_STROBE_SWITCH_ENTRY {
+    GX_STROBE_SWITCH_OFF = 0, //< Switch off the strobe light
+    GX_STROBE_SWITCH_ON = 1,  //< Switch on the strobe light
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_LINE_SELECTOR_ENTRY {
+    GX_ENUM_LINE_SELECTOR_LINE0 = 0,        //< Line 0
+    GX_ENUM_LINE_SELECTOR_LINE1 = 1,        //< Line 1
+    GX_ENUM_LINE_SELECTOR_LINE2 = 2,        //< Line 2
+    GX_ENUM_LINE_SELECTOR_LINE3 = 3,        //< Line 3
+    GX_ENUM_LINE_SELECTOR_LINE4 = 4,        //< Line 4
+    GX_ENUM_LINE_SELECTOR_LINE5 = 5,        //< Line 5
+    GX_ENUM_LINE_SELECTOR_LINE6 = 6,        //< Line 6
+    GX_ENUM_LINE_SELECTOR_LINE7 = 7,        //< Line 7
+    GX_ENUM_LINE_SELECTOR_LINE8 = 8,        //< Line 8
+    GX_ENUM_LINE_SELECTOR_LINE9 = 9,        //< Line 9
+    GX_ENUM_LINE_SELECTOR_LINE10 = 10,      //< Line 10
+    GX_ENUM_LINE_SELECTOR_LINE_STROBE = 11, //< Dedicated flash pin
+    GX_ENUM_LINE_SELECTOR_LINE11 = 12,      //< Line 11
+    GX_ENUM_LINE_SELECTOR_LINE12 = 13,      //< Line 12
+    GX_ENUM_LINE_SELECTOR_LINE13 = 14,      //< Line 13
+    GX_ENUM_LINE_SELECTOR_LINE14 = 15,      //< Line 14
+    GX_ENUM_LINE_SELECTOR_TRIGGER = 16,     //< Trigger input
+    GX_ENUM_LINE_SELECTOR_IO1 = 17,         //< GPIO output
+    GX_ENUM_LINE_SELECTOR_IO2 = 18,         //< GPIO input
+    GX_ENUM_LINE_SELECTOR_FLASH_P = 19,     //< flash_B output
+    GX_ENUM_LINE_SELECTOR_FLASH_W = 20,     //< flash_W output
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_LINE_MODE_ENTRY {
+    GX_ENUM_LINE_MODE_INPUT = 0,  //< Input
+    GX_ENUM_LINE_MODE_OUTPUT = 1, //< Output
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_LINE_SOURCE_ENTRY {
+    GX_ENUM_LINE_SOURCE_OFF = 0,                      //< Off
+    GX_ENUM_LINE_SOURCE_STROBE = 1,                   //< Strobe light
+    GX_ENUM_LINE_SOURCE_USEROUTPUT0 = 2,              //< UserDefined output 0
+    GX_ENUM_LINE_SOURCE_USEROUTPUT1 = 3,              //< UserDefined output 1
+    GX_ENUM_LINE_SOURCE_USEROUTPUT2 = 4,              //< UserDefined output 2
+    GX_ENUM_LINE_SOURCE_EXPOSURE_ACTIVE = 5,          //< Exposure active
+    GX_ENUM_LINE_SOURCE_FRAME_TRIGGER_WAIT = 6,       //< Frame trigger wait
+    GX_ENUM_LINE_SOURCE_ACQUISITION_TRIGGER_WAIT = 7, //< Acquisition trigger wait
+    GX_ENUM_LINE_SOURCE_TIMER1_ACTIVE = 8,            //< Timer 1 active
+    GX_ENUM_LINE_SOURCE_USEROUTPUT3 = 9,              //< User defined output 3
+    GX_ENUM_LINE_SOURCE_USEROUTPUT4 = 10,             //< User defined output 4
+    GX_ENUM_LINE_SOURCE_USEROUTPUT5 = 11,             //< User defined output 5
+    GX_ENUM_LINE_SOURCE_USEROUTPUT6 = 12,             //< User defined output 6
+    GX_ENUM_LINE_SOURCE_TIMER2_ACTIVE = 13,           //< Timer 2 active
+    GX_ENUM_LINE_SOURCE_TIMER3_ACTIVE = 14,           //< Timer 3 active
+    GX_ENUM_LINE_SOURCE_FRAME_TRIGGER = 15,           //< frame trigger
+    GX_ENUM_LINE_SOURCE_Flash_W = 16,                 //< Flash_w
+    GX_ENUM_LINE_SOURCE_Flash_P = 17,                 //< Flash_P
+    GX_ENUM_LINE_SOURCE_SERIAL_PORT_0 = 18,           //< SerialPort0
+}
+
+//------------------------------------------------------------------------------
+//  HDR And Multi Gray Entry Definition
+//------------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_HDR_MODE_ENTRY {
+    GX_HDR_MODE_OFF = 0,
+    GX_HDR_MODE_CONTINUOUS = 1,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_MGC_CONTROL_MODE_ENTRY {
+    GX_MGC_MODE_OFF = 0,
+    GX_MGC_MODE_TWO_FRAME = 1,
+    GX_MGC_MODE_FOUR_FRAME = 2,
+}
+
+//------------------------------------------------------------------------------
+//  IMU Entry Definition
+//------------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_IMU_CONFIG_ACC_RANGE_ENTRY {
+    GX_IMU_CONFIG_ACC_RANGE_16G = 2, //< Accelerometer range 16g
+    GX_IMU_CONFIG_ACC_RANGE_8G = 3,  //< Accelerometer range 8g
+    GX_IMU_CONFIG_ACC_RANGE_4G = 4,  //< Accelerometer range 4g
+    GX_IMU_CONFIG_ACC_RANGE_2G = 5,  //< Accelerometer range 2g
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_SWITCH_ENTRY {
+    GX_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_ON = 0, //< Accelerometer low pass filter on
+    GX_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_OFF = 1, //< Accelerometer low pass filter off
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_IMU_CONFIG_ACC_ODR_ENTRY {
+    GX_IMU_CONFIG_ACC_ODR_1000HZ = 0, //< Accelerometer output data rate 1000Hz
+    GX_IMU_CONFIG_ACC_ODR_500HZ = 1,  //< Accelerometer output data rate 500Hz
+    GX_IMU_CONFIG_ACC_ODR_250HZ = 2,  //< Accelerometer output data rate 250Hz
+    GX_IMU_CONFIG_ACC_ODR_125HZ = 3,  //< Accelerometer output data rate 125Hz
+    GX_IMU_CONFIG_ACC_ODR_63HZ = 4,   //< Accelerometer output data rate 63Hz
+    GX_IMU_CONFIG_ACC_ODR_31HZ = 5,   //< Accelerometer output data rate 31Hz
+    GX_IMU_CONFIG_ACC_ODR_16HZ = 6,   //< Accelerometer output data rate 16Hz
+    GX_IMU_CONFIG_ACC_ODR_2000HZ = 8, //< Accelerometer output data rate 2000Hz
+    GX_IMU_CONFIG_ACC_ODR_4000HZ = 9, //< Accelerometer output data rate 4000Hz
+    GX_IMU_CONFIG_ACC_ODR_8000HZ = 10, //< Accelerometer output data rate 8000Hz
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_FREQUENCY_ENTRY {
+    GX_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_FREQUENCY_ODR040 = 0, //< Cutoff at ODR * 0.40
+    GX_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_FREQUENCY_ODR025 = 1, //< Cutoff at ODR * 0.25
+    GX_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_FREQUENCY_ODR011 = 2, //< Cutoff at ODR * 0.11
+    GX_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_FREQUENCY_ODR004 = 3, //< Cutoff at ODR * 0.04
+    GX_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_FREQUENCY_ODR002 = 4, //< Cutoff at ODR * 0.02
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_IMU_CONFIG_GYRO_RANGE_ENTRY {
+    GX_IMU_CONFIG_GYRO_RANGE_125DPS = 2,  //< Gyro range 125dps
+    GX_IMU_CONFIG_GYRO_RANGE_250DPS = 3,  //< Gyro range 250dps
+    GX_IMU_CONFIG_GYRO_RANGE_500DPS = 4,  //< Gyro range 500dps
+    GX_IMU_CONFIG_GYRO_RANGE_1000DPS = 5, //< Gyro range 1000dps
+    GX_IMU_CONFIG_GYRO_RANGE_2000DPS = 6, //< Gyro range 2000dps
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_IMU_CONFIG_GYRO_ODR_LOW_PASS_FILTER_SWITCH_ENTRY {
+    GX_IMU_CONFIG_GYRO_ODR_LOW_PASS_FILTER_ON = 0, //< Gyro low pass filter on
+    GX_IMU_CONFIG_GYRO_ODR_LOW_PASS_FILTER_OFF = 1, //< Gyro low pass filter off
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_IMU_CONFIG_GYRO_ODR_ENTRY {
+    GX_IMU_CONFIG_GYRO_ODR_1000HZ = 0, //< Gyro output data rate 1000Hz
+    GX_IMU_CONFIG_GYRO_ODR_500HZ = 1,  //< Gyro output data rate 500Hz
+    GX_IMU_CONFIG_GYRO_ODR_250HZ = 2,  //< Gyro output data rate 250Hz
+    GX_IMU_CONFIG_GYRO_ODR_125HZ = 3,  //< Gyro output data rate 125Hz
+    GX_IMU_CONFIG_GYRO_ODR_63HZ = 4,   //< Gyro output data rate 63Hz
+    GX_IMU_CONFIG_GYRO_ODR_31HZ = 5,   //< Gyro output data rate 31Hz
+    GX_IMU_CONFIG_GYRO_ODR_4KHZ = 9,   //< Gyro output data rate 4000Hz
+    GX_IMU_CONFIG_GYRO_ODR_8KHZ = 10,  //< Gyro output data rate 8000Hz
+    GX_IMU_CONFIG_GYRO_ODR_16KHZ = 11, //< Gyro output data rate 16000Hz
+    GX_IMU_CONFIG_GYRO_ODR_32KHZ = 12, //< Gyro output data rate 32000Hz
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_IMU_TEMPERATURE_ODR_ENTRY {
+    GX_IMU_TEMPERATURE_ODR_500HZ = 0, //< Thermometer output data rate 500Hz
+    GX_IMU_TEMPERATURE_ODR_250HZ = 1, //< Thermometer output data rate 250Hz
+    GX_IMU_TEMPERATURE_ODR_125HZ = 2, //< Thermometer output data rate 125Hz
+    GX_IMU_TEMPERATURE_ODR_63HZ = 3,  //< Thermometer output data rate 63Hz
+}
+
+//------------------------------------------------------------------------------
+//  Serial Port Entry Definition
+//------------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_SERIALPORT_SELECTOR_ENTRY {
+    GX_SERIALPORT_SERIALPORT_0 = 0, //< Serial port 0
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_SERIALPORT_SOURCE_ENTRY {
+    GX_SERIALPORT_SERIALPORT_SOURCE_OFF = 0, //< Serial port input source off
+    GX_SERIALPORT_SERIALPORT_SOURCE_LINE_0 = 1, //< Serial port input source line 0
+    GX_SERIALPORT_SERIALPORT_SOURCE_LINE_1 = 2, //< Serial port input source line 1
+    GX_SERIALPORT_SERIALPORT_SOURCE_LINE_2 = 3, //< Serial port input source line 2
+    GX_SERIALPORT_SERIALPORT_SOURCE_LINE_3 = 4, //< Serial port input source line 3
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_SERIALPORT_BAUNDRATE_ENTRY {
+    GX_SERIALPORT_BAUNDRATE_9600 = 5,   //< Serial port baud rate is 9600
+    GX_SERIALPORT_BAUNDRATE_19200 = 6,  //< Serial port baud rate is 19200
+    GX_SERIALPORT_BAUNDRATE_38400 = 7,  //< Serial port baud rate is 38400
+    GX_SERIALPORT_BAUNDRATE_76800 = 8,  //< Serial port baud rate is 76800
+    GX_SERIALPORT_BAUNDRATE_115200 = 9, //< Serial port baud rate is 115200
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_SERIALPORT_STOP_BITS_ENTRY {
+    GX_SERIALPORT_STOP_BITS_ONE = 0,        //< 1 stop bit
+    GX_SERIALPORT_STOP_BITS_ONEANDHALF = 1, //< 1.5 stop bits
+    GX_SERIALPORT_STOP_BITS_TWO = 2,        //< 2 stop bits
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_SERIALPORT_PARITY_ENTRY {
+    GX_SERIALPORT_PARITY_NONE = 0,  //< None
+    GX_SERIALPORT_PARITY_ODD = 1,   //< Odd
+    GX_SERIALPORT_PARITY_EVEN = 2,  //< Even
+    GX_SERIALPORT_PARITY_MARK = 3,  //< Mark
+    GX_SERIALPORT_PARITY_SPACE = 4, //< Space
+}
+
+//------------------------------------------------------------------------------
+//  Encoder Entry Definition
+//------------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_ENCODER_SELECTOR_ENTRY {
+    GX_ENUM_ENCODER0 = 0, //< Encoder selector 0
+    GX_ENUM_ENCODER1 = 1, //< Encoder selector 1
+    GX_ENUM_ENCODER2 = 2, //< Encoder selector 2
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_ENCODER_SOURCEA_ENTRY {
+    GX_ENUM_SOURCEA_OFF = 0,   //< Encoder phase A input off
+    GX_ENUM_SOURCEA_LINE0 = 1, //< Encoder phase A input Line0
+    GX_ENUM_SOURCEA_LINE1 = 2, //< Encoder phase A input Line1
+    GX_ENUM_SOURCEA_LINE2 = 3, //< Encoder phase A input Line2
+    GX_ENUM_SOURCEA_LINE3 = 4, //< Encoder phase A input Line3
+    GX_ENUM_SOURCEA_LINE4 = 5, //< Encoder phase A input Line4
+    GX_ENUM_SOURCEA_LINE5 = 6, //< Encoder phase A input Line5
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_ENCODER_SOURCEB_ENTRY {
+    GX_ENUM_SOURCEB_OFF = 0,   //< Encoder phase B input off
+    GX_ENUM_SOURCEB_LINE0 = 1, //< Encoder phase B input Line0
+    GX_ENUM_SOURCEB_LINE1 = 2, //< Encoder phase B input Line1
+    GX_ENUM_SOURCEB_LINE2 = 3, //< Encoder phase B input Line2
+    GX_ENUM_SOURCEB_LINE3 = 4, //< Encoder phase B input Line3
+    GX_ENUM_SOURCEB_LINE4 = 5, //< Encoder phase B input Line4
+    GX_ENUM_SOURCEB_LINE5 = 6, //< Encoder phase B input Line5
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_ENCODER_MODE_ENTRY {
+    GX_ENUM_HIGH_RESOLUTION = 0, //< Encoder Mode
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_ENCODER_DIRECTION_ENTRY {
+    GX_ENUM_FORWARD = 0,  //< Encoder direction forward
+    GX_ENUM_BACKWARD = 1, //< Encoder direction backward
+}
+
+//------------------------------------------------------------------------------
+//  Chunk Data Entry Definition
+//------------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_CHUNK_SELECTOR_ENTRY {
+    GX_CHUNK_SELECTOR_CHUNK_FRAME_ID = 1,      //< Frame ID
+    GX_CHUNK_SELECTOR_CHUNK_TIME_STAMP = 2,    //< Timestamp
+    GX_CHUNK_SELECTOR_CHUNK_COUNTER_VALUE = 3, //< Counter value
+}
+
+//------------------------------------------------------------------------------
+//  Device Temperature Entry Definition
+//------------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_DEVICE_TEMPERATURE_SELECTOR_ENTRY {
+    GX_DEVICE_TEMPERATURE_SELECTOR_SENSOR = 1,    //< Sensor
+    GX_DEVICE_TEMPERATURE_SELECTOR_MAINBOARD = 2, //< Mainboard
+}
+
+//------------------------------------------------------------------------------
+//  Image Format Entry Definition
+//------------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_BINNING_HORIZONTAL_MODE_ENTRY {
+    GX_BINNING_HORIZONTAL_MODE_SUM = 0, //< Horizontal value sum of BINNING
+    GX_BINNING_HORIZONTAL_MODE_AVERAGE = 1, //< Average horizontal value of BINNING
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_BINNING_VERTICAL_MODE_ENTRY {
+    GX_BINNING_VERTICAL_MODE_SUM = 0, //< Vertical value sum of BINNING
+    GX_BINNING_VERTICAL_MODE_AVERAGE = 1, //< Average Vertical value of BINNING
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_SENSOR_SHUTTER_MODE_ENTRY {
+    GX_SENSOR_SHUTTER_MODE_GLOBAL = 0, //< All pixels are exposed at the same time and the exposure time is equal
+    GX_SENSOR_SHUTTER_MODE_ROLLING = 1, //< All pixels have the same exposure time, but the exposure start time is different
+    GX_SENSOR_SHUTTER_MODE_GLOBALRESET = 2, //< The exposure start time of all pixels is the same, but the exposure time is not the same
+}
+
+//------------------------------------------------------------------------------
+//  Region Entry Definition
+//------------------------------------------------------------------------------
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_REGION_SEND_MODE_ENTRY {
+    GX_REGION_SEND_SINGLE_ROI_MODE = 0, //< Single ROI
+    GX_REGION_SEND_MULTI_ROI_MODE = 1,  //< Multi ROI
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_REGION_MODE_ENTRY {
+    GX_REGION_MODE_OFF = 0, //< Close currently selected region
+    GX_REGION_MODE_ON = 1,  //< Open currently selected region
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GX_REGION_SELECTOR_ENTRY {
+    GX_REGION_SELECTOR_REGION0 = 0, //< Region 0
+    GX_REGION_SELECTOR_REGION1 = 1, //< Region 1
+    GX_REGION_SELECTOR_REGION2 = 2, //< Region 2
+    GX_REGION_SELECTOR_REGION3 = 3, //< Region 3
+    GX_REGION_SELECTOR_REGION4 = 4, //< Region 4
+    GX_REGION_SELECTOR_REGION5 = 5, //< Region 5
+    GX_REGION_SELECTOR_REGION6 = 6, //< Region 6
+    GX_REGION_SELECTOR_REGION7 = 7, //< Region 7
+}
+
+//------------------------------------------------------------------------------
+//  Feature ID List
+//------------------------------------------------------------------------------
+impl GX_FEATURE_ID {
+    /// Every feature ID in declaration order, for walking all features of a device.
+    pub const ALL: [GX_FEATURE_ID; 316] = [
+        GX_FEATURE_ID::GX_STRING_DEVICE_VENDOR_NAME,
+        GX_FEATURE_ID::GX_STRING_DEVICE_MODEL_NAME,
+        GX_FEATURE_ID::GX_STRING_DEVICE_FIRMWARE_VERSION,
+        GX_FEATURE_ID::GX_STRING_DEVICE_VERSION,
+        GX_FEATURE_ID::GX_STRING_DEVICE_SERIAL_NUMBER,
+        GX_FEATURE_ID::GX_STRING_FACTORY_SETTING_VERSION,
+        GX_FEATURE_ID::GX_STRING_DEVICE_USERID,
+        GX_FEATURE_ID::GX_INT_DEVICE_LINK_SELECTOR,
+        GX_FEATURE_ID::GX_ENUM_DEVICE_LINK_THROUGHPUT_LIMIT_MODE,
+        GX_FEATURE_ID::GX_INT_DEVICE_LINK_THROUGHPUT_LIMIT,
+        GX_FEATURE_ID::GX_INT_DEVICE_LINK_CURRENT_THROUGHPUT,
+        GX_FEATURE_ID::GX_COMMAND_DEVICE_RESET,
+        GX_FEATURE_ID::GX_INT_TIMESTAMP_TICK_FREQUENCY,
+        GX_FEATURE_ID::GX_COMMAND_TIMESTAMP_LATCH,
+        GX_FEATURE_ID::GX_COMMAND_TIMESTAMP_RESET,
+        GX_FEATURE_ID::GX_COMMAND_TIMESTAMP_LATCH_RESET,
+        GX_FEATURE_ID::GX_INT_TIMESTAMP_LATCH_VALUE,
+        GX_FEATURE_ID::GX_STRING_DEVICE_PHY_VERSION,
+        GX_FEATURE_ID::GX_ENUM_DEVICE_TEMPERATURE_SELECTOR,
+        GX_FEATURE_ID::GX_FLOAT_DEVICE_TEMPERATURE,
+        GX_FEATURE_ID::GX_STRING_DEVICE_ISP_FIRMWARE_VERSION,
+        GX_FEATURE_ID::GX_ENUM_LOWPOWER_MODE,
+        GX_FEATURE_ID::GX_ENUM_CLOSE_CCD,
+        GX_FEATURE_ID::GX_INT_SENSOR_WIDTH,
+        GX_FEATURE_ID::GX_INT_SENSOR_HEIGHT,
+        GX_FEATURE_ID::GX_INT_WIDTH_MAX,
+        GX_FEATURE_ID::GX_INT_HEIGHT_MAX,
+        GX_FEATURE_ID::GX_INT_OFFSET_X,
+        GX_FEATURE_ID::GX_INT_OFFSET_Y,
+        GX_FEATURE_ID::GX_INT_WIDTH,
+        GX_FEATURE_ID::GX_INT_HEIGHT,
+        GX_FEATURE_ID::GX_INT_BINNING_HORIZONTAL,
+        GX_FEATURE_ID::GX_INT_BINNING_VERTICAL,
+        GX_FEATURE_ID::GX_INT_DECIMATION_HORIZONTAL,
+        GX_FEATURE_ID::GX_INT_DECIMATION_VERTICAL,
+        GX_FEATURE_ID::GX_ENUM_PIXEL_SIZE,
+        GX_FEATURE_ID::GX_ENUM_PIXEL_COLOR_FILTER,
+        GX_FEATURE_ID::GX_ENUM_PIXEL_FORMAT,
+        GX_FEATURE_ID::GX_BOOL_REVERSE_X,
+        GX_FEATURE_ID::GX_BOOL_REVERSE_Y,
+        GX_FEATURE_ID::GX_ENUM_TEST_PATTERN,
+        GX_FEATURE_ID::GX_ENUM_TEST_PATTERN_GENERATOR_SELECTOR,
+        GX_FEATURE_ID::GX_ENUM_REGION_SEND_MODE,
+        GX_FEATURE_ID::GX_ENUM_REGION_MODE,
+        GX_FEATURE_ID::GX_ENUM_REGION_SELECTOR,
+        GX_FEATURE_ID::GX_INT_CENTER_WIDTH,
+        GX_FEATURE_ID::GX_INT_CENTER_HEIGHT,
+        GX_FEATURE_ID::GX_ENUM_BINNING_HORIZONTAL_MODE,
+        GX_FEATURE_ID::GX_ENUM_BINNING_VERTICAL_MODE,
+        GX_FEATURE_ID::GX_ENUM_SENSOR_SHUTTER_MODE,
+        GX_FEATURE_ID::GX_INT_DECIMATION_LINENUMBER,
+        GX_FEATURE_ID::GX_INT_SENSOR_DECIMATION_HORIZONTAL,
+        GX_FEATURE_ID::GX_INT_SENSOR_DECIMATION_VERTICAL,
+        GX_FEATURE_ID::GX_ENUM_SENSOR_SELECTOR,
+        GX_FEATURE_ID::GX_INT_CURRENT_SENSOR_WIDTH,
+        GX_FEATURE_ID::GX_INT_CURRENT_SENSOR_HEIGHT,
+        GX_FEATURE_ID::GX_INT_CURRENT_SENSOR_OFFSETX,
+        GX_FEATURE_ID::GX_INT_CURRENT_SENSOR_OFFSETY,
+        GX_FEATURE_ID::GX_INT_CURRENT_SENSOR_WIDTHMAX,
+        GX_FEATURE_ID::GX_INT_CURRENT_SENSOR_HEIGHTMAX,
+        GX_FEATURE_ID::GX_ENUM_SENSOR_BIT_DEPTH,
+        GX_FEATURE_ID::GX_BOOL_WATERMARK_ENABLE,
+        GX_FEATURE_ID::GX_INT_PAYLOAD_SIZE,
+        GX_FEATURE_ID::GX_BOOL_GEV_CURRENT_IPCONFIGURATION_LLA,
+        GX_FEATURE_ID::GX_BOOL_GEV_CURRENT_IPCONFIGURATION_DHCP,
+        GX_FEATURE_ID::GX_BOOL_GEV_CURRENT_IPCONFIGURATION_PERSISTENTIP,
+        GX_FEATURE_ID::GX_INT_ESTIMATED_BANDWIDTH,
+        GX_FEATURE_ID::GX_INT_GEV_HEARTBEAT_TIMEOUT,
+        GX_FEATURE_ID::GX_INT_GEV_PACKETSIZE,
+        GX_FEATURE_ID::GX_INT_GEV_PACKETDELAY,
+        GX_FEATURE_ID::GX_INT_GEV_LINK_SPEED,
+        GX_FEATURE_ID::GX_ENUM_ACQUISITION_MODE,
+        GX_FEATURE_ID::GX_COMMAND_ACQUISITION_START,
+        GX_FEATURE_ID::GX_COMMAND_ACQUISITION_STOP,
+        GX_FEATURE_ID::GX_INT_ACQUISITION_SPEED_LEVEL,
+        GX_FEATURE_ID::GX_INT_ACQUISITION_FRAME_COUNT,
+        GX_FEATURE_ID::GX_ENUM_TRIGGER_MODE,
+        GX_FEATURE_ID::GX_COMMAND_TRIGGER_SOFTWARE,
+        GX_FEATURE_ID::GX_ENUM_TRIGGER_ACTIVATION,
+        GX_FEATURE_ID::GX_ENUM_TRIGGER_SWITCH,
+        GX_FEATURE_ID::GX_FLOAT_EXPOSURE_TIME,
+        GX_FEATURE_ID::GX_ENUM_EXPOSURE_AUTO,
+        GX_FEATURE_ID::GX_FLOAT_TRIGGER_FILTER_RAISING,
+        GX_FEATURE_ID::GX_FLOAT_TRIGGER_FILTER_FALLING,
+        GX_FEATURE_ID::GX_ENUM_TRIGGER_SOURCE,
+        GX_FEATURE_ID::GX_ENUM_EXPOSURE_MODE,
+        GX_FEATURE_ID::GX_ENUM_TRIGGER_SELECTOR,
+        GX_FEATURE_ID::GX_FLOAT_TRIGGER_DELAY,
+        GX_FEATURE_ID::GX_ENUM_TRANSFER_CONTROL_MODE,
+        GX_FEATURE_ID::GX_ENUM_TRANSFER_OPERATION_MODE,
+        GX_FEATURE_ID::GX_COMMAND_TRANSFER_START,
+        GX_FEATURE_ID::GX_INT_TRANSFER_BLOCK_COUNT,
+        GX_FEATURE_ID::GX_BOOL_FRAMESTORE_COVER_ACTIVE,
+        GX_FEATURE_ID::GX_ENUM_ACQUISITION_FRAME_RATE_MODE,
+        GX_FEATURE_ID::GX_FLOAT_ACQUISITION_FRAME_RATE,
+        GX_FEATURE_ID::GX_FLOAT_CURRENT_ACQUISITION_FRAME_RATE,
+        GX_FEATURE_ID::GX_ENUM_FIXED_PATTERN_NOISE_CORRECT_MODE,
+        GX_FEATURE_ID::GX_INT_ACQUISITION_BURST_FRAME_COUNT,
+        GX_FEATURE_ID::GX_ENUM_ACQUISITION_STATUS_SELECTOR,
+        GX_FEATURE_ID::GX_BOOL_ACQUISITION_STATUS,
+        GX_FEATURE_ID::GX_FLOAT_EXPOSURE_DELAY,
+        GX_FEATURE_ID::GX_FLOAT_EXPOSURE_OVERLAP_TIME_MAX,
+        GX_FEATURE_ID::GX_ENUM_EXPOSURE_TIME_MODE,
+        GX_FEATURE_ID::GX_ENUM_ACQUISITION_BURST_MODE,
+        GX_FEATURE_ID::GX_ENUM_OVERLAP_MODE,
+        GX_FEATURE_ID::GX_ENUM_MULTISOURCE_SELECTOR,
+        GX_FEATURE_ID::GX_BOOL_MULTISOURCE_ENABLE,
+        GX_FEATURE_ID::GX_BOOL_TRIGGER_CACHE_ENABLE,
+        GX_FEATURE_ID::GX_ENUM_USER_OUTPUT_SELECTOR,
+        GX_FEATURE_ID::GX_BOOL_USER_OUTPUT_VALUE,
+        GX_FEATURE_ID::GX_ENUM_USER_OUTPUT_MODE,
+        GX_FEATURE_ID::GX_ENUM_STROBE_SWITCH,
+        GX_FEATURE_ID::GX_ENUM_LINE_SELECTOR,
+        GX_FEATURE_ID::GX_ENUM_LINE_MODE,
+        GX_FEATURE_ID::GX_BOOL_LINE_INVERTER,
+        GX_FEATURE_ID::GX_ENUM_LINE_SOURCE,
+        GX_FEATURE_ID::GX_BOOL_LINE_STATUS,
+        GX_FEATURE_ID::GX_INT_LINE_STATUS_ALL,
+        GX_FEATURE_ID::GX_FLOAT_PULSE_WIDTH,
+        GX_FEATURE_ID::GX_INT_LINE_RANGE,
+        GX_FEATURE_ID::GX_INT_LINE_DELAY,
+        GX_FEATURE_ID::GX_ENUM_GAIN_AUTO,
+        GX_FEATURE_ID::GX_ENUM_GAIN_SELECTOR,
+        GX_FEATURE_ID::GX_ENUM_BLACKLEVEL_AUTO,
+        GX_FEATURE_ID::GX_ENUM_BLACKLEVEL_SELECTOR,
+        GX_FEATURE_ID::GX_ENUM_BALANCE_WHITE_AUTO,
+        GX_FEATURE_ID::GX_ENUM_BALANCE_RATIO_SELECTOR,
+        GX_FEATURE_ID::GX_FLOAT_BALANCE_RATIO,
+        GX_FEATURE_ID::GX_ENUM_COLOR_CORRECT,
+        GX_FEATURE_ID::GX_ENUM_DEAD_PIXEL_CORRECT,
+        GX_FEATURE_ID::GX_FLOAT_GAIN,
+        GX_FEATURE_ID::GX_FLOAT_BLACKLEVEL,
+        GX_FEATURE_ID::GX_BOOL_GAMMA_ENABLE,
+        GX_FEATURE_ID::GX_ENUM_GAMMA_MODE,
+        GX_FEATURE_ID::GX_FLOAT_GAMMA,
+        GX_FEATURE_ID::GX_INT_DIGITAL_SHIFT,
+        GX_FEATURE_ID::GX_ENUM_LIGHT_SOURCE_PRESET,
+        GX_FEATURE_ID::GX_BOOL_BLACKLEVEL_CALIB_STATUS,
+        GX_FEATURE_ID::GX_INT_BLACKLEVEL_CALIB_VALUE,
+        GX_FEATURE_ID::GX_INT_ADC_LEVEL,
+        GX_FEATURE_ID::GX_INT_H_BLANKING,
+        GX_FEATURE_ID::GX_INT_V_BLANKING,
+        GX_FEATURE_ID::GX_STRING_USER_PASSWORD,
+        GX_FEATURE_ID::GX_STRING_VERIFY_PASSWORD,
+        GX_FEATURE_ID::GX_BUFFER_USER_DATA,
+        GX_FEATURE_ID::GX_INT_GRAY_VALUE,
+        GX_FEATURE_ID::GX_ENUM_AA_LIGHT_ENVIRONMENT,
+        GX_FEATURE_ID::GX_INT_AAROI_OFFSETX,
+        GX_FEATURE_ID::GX_INT_AAROI_OFFSETY,
+        GX_FEATURE_ID::GX_INT_AAROI_WIDTH,
+        GX_FEATURE_ID::GX_INT_AAROI_HEIGHT,
+        GX_FEATURE_ID::GX_FLOAT_AUTO_GAIN_MIN,
+        GX_FEATURE_ID::GX_FLOAT_AUTO_GAIN_MAX,
+        GX_FEATURE_ID::GX_FLOAT_AUTO_EXPOSURE_TIME_MIN,
+        GX_FEATURE_ID::GX_FLOAT_AUTO_EXPOSURE_TIME_MAX,
+        GX_FEATURE_ID::GX_BUFFER_FRAME_INFORMATION,
+        GX_FEATURE_ID::GX_INT_CONTRAST_PARAM,
+        GX_FEATURE_ID::GX_FLOAT_GAMMA_PARAM,
+        GX_FEATURE_ID::GX_INT_COLOR_CORRECTION_PARAM,
+        GX_FEATURE_ID::GX_ENUM_IMAGE_GRAY_RAISE_SWITCH,
+        GX_FEATURE_ID::GX_ENUM_AWB_LAMP_HOUSE,
+        GX_FEATURE_ID::GX_INT_AWBROI_OFFSETX,
+        GX_FEATURE_ID::GX_INT_AWBROI_OFFSETY,
+        GX_FEATURE_ID::GX_INT_AWBROI_WIDTH,
+        GX_FEATURE_ID::GX_INT_AWBROI_HEIGHT,
+        GX_FEATURE_ID::GX_ENUM_SHARPNESS_MODE,
+        GX_FEATURE_ID::GX_FLOAT_SHARPNESS,
+        GX_FEATURE_ID::GX_ENUM_USER_DATA_FILED_SELECTOR,
+        GX_FEATURE_ID::GX_BUFFER_USER_DATA_FILED_VALUE,
+        GX_FEATURE_ID::GX_ENUM_FLAT_FIELD_CORRECTION,
+        GX_FEATURE_ID::GX_ENUM_NOISE_REDUCTION_MODE,
+        GX_FEATURE_ID::GX_FLOAT_NOISE_REDUCTION,
+        GX_FEATURE_ID::GX_BUFFER_FFCLOAD,
+        GX_FEATURE_ID::GX_BUFFER_FFCSAVE,
+        GX_FEATURE_ID::GX_ENUM_STATIC_DEFECT_CORRECTION,
+        GX_FEATURE_ID::GX_ENUM_2D_NOISE_REDUCTION_MODE,
+        GX_FEATURE_ID::GX_ENUM_3D_NOISE_REDUCTION_MODE,
+        GX_FEATURE_ID::GX_COMMAND_CLOSE_ISP,
+        GX_FEATURE_ID::GX_BUFFER_STATIC_DEFECT_CORRECTION_VALUE_ALL,
+        GX_FEATURE_ID::GX_BUFFER_STATIC_DEFECT_CORRECTION_FLASH_VALUE,
+        GX_FEATURE_ID::GX_INT_STATIC_DEFECT_CORRECTION_FINISH,
+        GX_FEATURE_ID::GX_BUFFER_STATIC_DEFECT_CORRECTION_INFO,
+        GX_FEATURE_ID::GX_COMMAND_STRIP_CALIBRATION_START,
+        GX_FEATURE_ID::GX_COMMAND_STRIP_CALIBRATION_STOP,
+        GX_FEATURE_ID::GX_ENUM_USER_SET_SELECTOR,
+        GX_FEATURE_ID::GX_COMMAND_USER_SET_LOAD,
+        GX_FEATURE_ID::GX_COMMAND_USER_SET_SAVE,
+        GX_FEATURE_ID::GX_ENUM_USER_SET_DEFAULT,
+        GX_FEATURE_ID::GX_ENUM_EVENT_SELECTOR,
+        GX_FEATURE_ID::GX_ENUM_EVENT_NOTIFICATION,
+        GX_FEATURE_ID::GX_INT_EVENT_EXPOSUREEND,
+        GX_FEATURE_ID::GX_INT_EVENT_EXPOSUREEND_TIMESTAMP,
+        GX_FEATURE_ID::GX_INT_EVENT_EXPOSUREEND_FRAMEID,
+        GX_FEATURE_ID::GX_INT_EVENT_BLOCK_DISCARD,
+        GX_FEATURE_ID::GX_INT_EVENT_BLOCK_DISCARD_TIMESTAMP,
+        GX_FEATURE_ID::GX_INT_EVENT_OVERRUN,
+        GX_FEATURE_ID::GX_INT_EVENT_OVERRUN_TIMESTAMP,
+        GX_FEATURE_ID::GX_INT_EVENT_FRAMESTART_OVERTRIGGER,
+        GX_FEATURE_ID::GX_INT_EVENT_FRAMESTART_OVERTRIGGER_TIMESTAMP,
+        GX_FEATURE_ID::GX_INT_EVENT_BLOCK_NOT_EMPTY,
+        GX_FEATURE_ID::GX_INT_EVENT_BLOCK_NOT_EMPTY_TIMESTAMP,
+        GX_FEATURE_ID::GX_INT_EVENT_INTERNAL_ERROR,
+        GX_FEATURE_ID::GX_INT_EVENT_INTERNAL_ERROR_TIMESTAMP,
+        GX_FEATURE_ID::GX_INT_EVENT_FRAMEBURSTSTART_OVERTRIGGER,
+        GX_FEATURE_ID::GX_INT_EVENT_FRAMEBURSTSTART_OVERTRIGGER_FRAMEID,
+        GX_FEATURE_ID::GX_INT_EVENT_FRAMEBURSTSTART_OVERTRIGGER_TIMESTAMP,
+        GX_FEATURE_ID::GX_INT_EVENT_FRAMESTART_WAIT,
+        GX_FEATURE_ID::GX_INT_EVENT_FRAMESTART_WAIT_TIMESTAMP,
+        GX_FEATURE_ID::GX_INT_EVENT_FRAMEBURSTSTART_WAIT,
+        GX_FEATURE_ID::GX_INT_EVENT_FRAMEBURSTSTART_WAIT_TIMESTAMP,
+        GX_FEATURE_ID::GX_INT_EVENT_BLOCK_DISCARD_FRAMEID,
+        GX_FEATURE_ID::GX_INT_EVENT_FRAMESTART_OVERTRIGGER_FRAMEID,
+        GX_FEATURE_ID::GX_INT_EVENT_BLOCK_NOT_EMPTY_FRAMEID,
+        GX_FEATURE_ID::GX_INT_EVENT_FRAMESTART_WAIT_FRAMEID,
+        GX_FEATURE_ID::GX_INT_EVENT_FRAMEBURSTSTART_WAIT_FRAMEID,
+        GX_FEATURE_ID::GX_ENUM_EVENT_SIMPLE_MODE,
+        GX_FEATURE_ID::GX_ENUM_LUT_SELECTOR,
+        GX_FEATURE_ID::GX_BUFFER_LUT_VALUEALL,
+        GX_FEATURE_ID::GX_BOOL_LUT_ENABLE,
+        GX_FEATURE_ID::GX_INT_LUT_INDEX,
+        GX_FEATURE_ID::GX_INT_LUT_VALUE,
+        GX_FEATURE_ID::GX_BOOL_CHUNKMODE_ACTIVE,
+        GX_FEATURE_ID::GX_ENUM_CHUNK_SELECTOR,
+        GX_FEATURE_ID::GX_BOOL_CHUNK_ENABLE,
+        GX_FEATURE_ID::GX_ENUM_COLOR_TRANSFORMATION_MODE,
+        GX_FEATURE_ID::GX_BOOL_COLOR_TRANSFORMATION_ENABLE,
+        GX_FEATURE_ID::GX_ENUM_COLOR_TRANSFORMATION_VALUE_SELECTOR,
+        GX_FEATURE_ID::GX_FLOAT_COLOR_TRANSFORMATION_VALUE,
+        GX_FEATURE_ID::GX_ENUM_SATURATION_MODE,
+        GX_FEATURE_ID::GX_INT_SATURATION,
+        GX_FEATURE_ID::GX_ENUM_TIMER_SELECTOR,
+        GX_FEATURE_ID::GX_FLOAT_TIMER_DURATION,
+        GX_FEATURE_ID::GX_FLOAT_TIMER_DELAY,
+        GX_FEATURE_ID::GX_ENUM_TIMER_TRIGGER_SOURCE,
+        GX_FEATURE_ID::GX_ENUM_COUNTER_SELECTOR,
+        GX_FEATURE_ID::GX_ENUM_COUNTER_EVENT_SOURCE,
+        GX_FEATURE_ID::GX_ENUM_COUNTER_RESET_SOURCE,
+        GX_FEATURE_ID::GX_ENUM_COUNTER_RESET_ACTIVATION,
+        GX_FEATURE_ID::GX_COMMAND_COUNTER_RESET,
+        GX_FEATURE_ID::GX_ENUM_COUNTER_TRIGGER_SOURCE,
+        GX_FEATURE_ID::GX_INT_COUNTER_DURATION,
+        GX_FEATURE_ID::GX_ENUM_TIMER_TRIGGER_ACTIVATION,
+        GX_FEATURE_ID::GX_ENUM_REMOVE_PARAMETER_LIMIT,
+        GX_FEATURE_ID::GX_ENUM_HDR_MODE,
+        GX_FEATURE_ID::GX_INT_HDR_TARGET_LONG_VALUE,
+        GX_FEATURE_ID::GX_INT_HDR_TARGET_SHORT_VALUE,
+        GX_FEATURE_ID::GX_INT_HDR_TARGET_MAIN_VALUE,
+        GX_FEATURE_ID::GX_ENUM_MGC_MODE,
+        GX_FEATURE_ID::GX_INT_MGC_SELECTOR,
+        GX_FEATURE_ID::GX_FLOAT_MGC_EXPOSURE_TIME,
+        GX_FEATURE_ID::GX_FLOAT_MGC_GAIN,
+        GX_FEATURE_ID::GX_BUFFER_STRIPED_CALIBRATION_INFO,
+        GX_FEATURE_ID::GX_FLOAT_CONTRAST,
+        GX_FEATURE_ID::GX_BUFFER_IMU_DATA,
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_ACC_RANGE,
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_SWITCH,
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_ACC_ODR,
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_ACC_ODR_LOW_PASS_FILTER_FREQUENCY,
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_XRANGE,
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_YRANGE,
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_ZRANGE,
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_ODR_LOW_PASS_FILTER_SWITCH,
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_ODR,
+        GX_FEATURE_ID::GX_ENUM_IMU_CONFIG_GYRO_ODR_LOW_PASS_FILTER_FREQUENCY,
+        GX_FEATURE_ID::GX_FLOAT_IMU_ROOM_TEMPERATURE,
+        GX_FEATURE_ID::GX_ENUM_IMU_TEMPERATURE_ODR,
+        GX_FEATURE_ID::GX_INT_FRAME_BUFFER_COUNT,
+        GX_FEATURE_ID::GX_COMMAND_FRAME_BUFFER_FLUSH,
+        GX_FEATURE_ID::GX_ENUM_SERIALPORT_SELECTOR,
+        GX_FEATURE_ID::GX_ENUM_SERIALPORT_SOURCE,
+        GX_FEATURE_ID::GX_ENUM_SERIALPORT_BAUDRATE,
+        GX_FEATURE_ID::GX_INT_SERIALPORT_DATA_BITS,
+        GX_FEATURE_ID::GX_ENUM_SERIALPORT_STOP_BITS,
+        GX_FEATURE_ID::GX_ENUM_SERIALPORT_PARITY,
+        GX_FEATURE_ID::GX_INT_TRANSMIT_QUEUE_MAX_CHARACTER_COUNT,
+        GX_FEATURE_ID::GX_INT_TRANSMIT_QUEUE_CURRENT_CHARACTER_COUNT,
+        GX_FEATURE_ID::GX_INT_RECEIVE_QUEUE_MAX_CHARACTER_COUNT,
+        GX_FEATURE_ID::GX_INT_RECEIVE_QUEUE_CURRENT_CHARACTER_COUNT,
+        GX_FEATURE_ID::GX_INT_RECEIVE_FRAMING_ERROR_COUNT,
+        GX_FEATURE_ID::GX_INT_RECEIVE_PARITY_ERROR_COUNT,
+        GX_FEATURE_ID::GX_COMMAND_RECEIVE_QUEUE_CLEAR,
+        GX_FEATURE_ID::GX_BUFFER_SERIALPORT_DATA,
+        GX_FEATURE_ID::GX_INT_SERIALPORT_DATA_LENGTH,
+        GX_FEATURE_ID::GX_ENUM_ENCODER_SELECTOR,
+        GX_FEATURE_ID::GX_ENUM_ENCODER_DIRECTION,
+        GX_FEATURE_ID::GX_INT_ENCODER_VALUE,
+        GX_FEATURE_ID::GX_ENUM_ENCODER_SOURCEA,
+        GX_FEATURE_ID::GX_ENUM_ENCODER_SOURCEB,
+        GX_FEATURE_ID::GX_ENUM_ENCODER_MODE,
+        GX_FEATURE_ID::GX_DEV_INT_COMMAND_TIMEOUT,
+        GX_FEATURE_ID::GX_DEV_INT_COMMAND_RETRY_COUNT,
+        GX_FEATURE_ID::GX_DS_INT_ANNOUNCED_BUFFER_COUNT,
+        GX_FEATURE_ID::GX_DS_INT_DELIVERED_FRAME_COUNT,
+        GX_FEATURE_ID::GX_DS_INT_LOST_FRAME_COUNT,
+        GX_FEATURE_ID::GX_DS_INT_INCOMPLETE_FRAME_COUNT,
+        GX_FEATURE_ID::GX_DS_INT_DELIVERED_PACKET_COUNT,
+        GX_FEATURE_ID::GX_DS_INT_RESEND_PACKET_COUNT,
+        GX_FEATURE_ID::GX_DS_INT_RESCUED_PACKED_COUNT,
+        GX_FEATURE_ID::GX_DS_INT_RESEND_COMMAND_COUNT,
+        GX_FEATURE_ID::GX_DS_INT_UNEXPECTED_PACKED_COUNT,
+        GX_FEATURE_ID::GX_DS_INT_MAX_PACKET_COUNT_IN_ONE_BLOCK,
+        GX_FEATURE_ID::GX_DS_INT_MAX_PACKET_COUNT_IN_ONE_COMMAND,
+        GX_FEATURE_ID::GX_DS_INT_RESEND_TIMEOUT,
+        GX_FEATURE_ID::GX_DS_INT_MAX_WAIT_PACKET_COUNT,
+        GX_FEATURE_ID::GX_DS_ENUM_RESEND_MODE,
+        GX_FEATURE_ID::GX_DS_INT_MISSING_BLOCKID_COUNT,
+        GX_FEATURE_ID::GX_DS_INT_BLOCK_TIMEOUT,
+        GX_FEATURE_ID::GX_DS_INT_STREAM_TRANSFER_SIZE,
+        GX_FEATURE_ID::GX_DS_INT_STREAM_TRANSFER_NUMBER_URB,
+        GX_FEATURE_ID::GX_DS_INT_PACKET_TIMEOUT,
+        GX_FEATURE_ID::GX_DS_INT_SOCKET_BUFFER_SIZE,
+        GX_FEATURE_ID::GX_DS_ENUM_STOP_ACQUISITION_MODE,
+        GX_FEATURE_ID::GX_DS_ENUM_STREAM_BUFFER_HANDLING_MODE,
+        GX_FEATURE_ID::GX_DS_INT_ACQUISITION_BUFFER_CACHE_PREC,
+        GX_FEATURE_ID::GX_DS_ENUM_MULTI_RESEND_MODE,
+        GX_FEATURE_ID::GX_DS_INT_MAX_NUM_QUEUE_BUFFER,
+    ];
+}
@@ -79,8 +79,12 @@ pub trait GXInterface {
     fn gx_export_config_file(&self, device: GX_DEV_HANDLE, file_path: *const c_char)
     -> Result<i32>;
 
-    fn gx_import_config_file(&self, device: GX_DEV_HANDLE, file_path: *const c_char)
-    -> Result<i32>;
+    fn gx_import_config_file(
+        &self,
+        device: GX_DEV_HANDLE,
+        file_path: *const c_char,
+        verify: bool,
+    ) -> Result<i32>;
 
     // Command
     fn gx_send_command(&self, device: GX_DEV_HANDLE, feature_id: GX_FEATURE_ID) -> Result<i32>;
@@ -611,7 +615,8 @@ impl GXInterface for GXInstance {
         }
     }
 
-    /// Import a configuration file to the camera
+    /// Import a configuration file to the camera, with `verify` the SDK reads every written
+    /// value back and fails when one does not match
     ///
     /// # Examples
     ///
@@ -622,13 +627,14 @@ impl GXInterface for GXInstance {
         &self,
         device: GX_DEV_HANDLE,
         file_path: *const c_char,
+        verify: bool,
     ) -> Result<i32> {
         unsafe {
             let gx_import_config_file: Symbol<
-                extern "C" fn(device: GX_DEV_HANDLE, file_path: *const c_char) -> i32,
+                extern "C" fn(device: GX_DEV_HANDLE, file_path: *const c_char, verify: bool) -> i32,
             > = self.lib.get(b"GXImportConfigFile")?;
             println!("Imported config file from: {:?}", CStr::from_ptr(file_path));
-            Ok(gx_import_config_file(device, file_path))
+            Ok(gx_import_config_file(device, file_path, verify))
         }
     }
 
@@ -1,9 +1,14 @@
 //! Pixel format definitions for GenICam.
 #![allow(dead_code)]
 
+use crate::error::{Error, ErrorKind};
+
+use std::fmt;
+use std::str::FromStr;
+
 // Use simple enums to represent the size and color filter entries.
 #[repr(C)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PixelSizeEntry {
     Bpp8 = 8,
     Bpp10 = 10,
@@ -18,7 +23,7 @@ pub enum PixelSizeEntry {
 }
 
 #[repr(C)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PixelColorFilterEntry {
     None = 0,
     BayerRG = 1,
@@ -41,9 +46,9 @@ pub const PIXEL_36BIT: u32 = 0x00240000;
 pub const PIXEL_48BIT: u32 = 0x00300000;
 pub const PIXEL_64BIT: u32 = 0x00400000;
 
-// Enum for pixel formats, including detailed descriptions as associated constants.
+// Enum for pixel formats, the full GX_PIXEL_FORMAT_ENTRY list of GxPixelFormat.h.
 #[repr(u32)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PixelFormatEntry {
     Undefined = 0,
     Mono8 = PIXEL_MONO | PIXEL_8BIT | 0x0001,
@@ -52,17 +57,32 @@ pub enum PixelFormatEntry {
     Mono12 = PIXEL_MONO | PIXEL_16BIT | 0x0005,
     Mono14 = PIXEL_MONO | PIXEL_16BIT | 0x0025,
     Mono16 = PIXEL_MONO | PIXEL_16BIT | 0x0007,
+
     BayerGr8 = PIXEL_MONO | PIXEL_8BIT | 0x0008,
     BayerRg8 = PIXEL_MONO | PIXEL_8BIT | 0x0009,
+    BayerGb8 = PIXEL_MONO | PIXEL_8BIT | 0x000A,
+    BayerBg8 = PIXEL_MONO | PIXEL_8BIT | 0x000B,
+
+    BayerGr10 = PIXEL_MONO | PIXEL_16BIT | 0x000C,
+    BayerRg10 = PIXEL_MONO | PIXEL_16BIT | 0x000D,
+    BayerGb10 = PIXEL_MONO | PIXEL_16BIT | 0x000E,
+    BayerBg10 = PIXEL_MONO | PIXEL_16BIT | 0x000F,
+
     BayerGr12 = PIXEL_MONO | PIXEL_16BIT | 0x0010,
     BayerRg12 = PIXEL_MONO | PIXEL_16BIT | 0x0011,
     BayerGb12 = PIXEL_MONO | PIXEL_16BIT | 0x0012,
     BayerBg12 = PIXEL_MONO | PIXEL_16BIT | 0x0013,
+
     BayerGr16 = PIXEL_MONO | PIXEL_16BIT | 0x002E,
     BayerRg16 = PIXEL_MONO | PIXEL_16BIT | 0x002F,
     BayerGb16 = PIXEL_MONO | PIXEL_16BIT | 0x0030,
     BayerBg16 = PIXEL_MONO | PIXEL_16BIT | 0x0031,
 
+    Rgb8Planar = PIXEL_COLOR | PIXEL_24BIT | 0x0021,
+    Rgb10Planar = PIXEL_COLOR | PIXEL_48BIT | 0x0022,
+    Rgb12Planar = PIXEL_COLOR | PIXEL_48BIT | 0x0023,
+    Rgb16Planar = PIXEL_COLOR | PIXEL_48BIT | 0x0024,
+
     Rgb8 = PIXEL_COLOR | PIXEL_24BIT | 0x0014,
     Rgb10 = PIXEL_COLOR | PIXEL_48BIT | 0x0018,
     Rgb12 = PIXEL_COLOR | PIXEL_48BIT | 0x001A,
@@ -99,9 +119,387 @@ pub enum PixelFormatEntry {
 
     Mono10Packed = PIXEL_MONO | PIXEL_12BIT | 0x0004, // GigE Vision specific format
     Mono12Packed = PIXEL_MONO | PIXEL_12BIT | 0x0006, // GigE Vision specific format
+}
 
-    // ... Include all other formats similarly.
-    Rgb8Planar = PIXEL_COLOR | PIXEL_24BIT | 0x0021,
-    Rgb10Planar = PIXEL_COLOR | PIXEL_48BIT | 0x0022,
-    // ... More formats as needed.
+impl PixelFormatEntry {
+    /// Every format, in GxPixelFormat.h order.
+    pub const ALL: [PixelFormatEntry; 56] = [
+        PixelFormatEntry::Undefined,
+        PixelFormatEntry::Mono8,
+        PixelFormatEntry::Mono8Signed,
+        PixelFormatEntry::Mono10,
+        PixelFormatEntry::Mono12,
+        PixelFormatEntry::Mono14,
+        PixelFormatEntry::Mono16,
+        PixelFormatEntry::BayerGr8,
+        PixelFormatEntry::BayerRg8,
+        PixelFormatEntry::BayerGb8,
+        PixelFormatEntry::BayerBg8,
+        PixelFormatEntry::BayerGr10,
+        PixelFormatEntry::BayerRg10,
+        PixelFormatEntry::BayerGb10,
+        PixelFormatEntry::BayerBg10,
+        PixelFormatEntry::BayerGr12,
+        PixelFormatEntry::BayerRg12,
+        PixelFormatEntry::BayerGb12,
+        PixelFormatEntry::BayerBg12,
+        PixelFormatEntry::BayerGr16,
+        PixelFormatEntry::BayerRg16,
+        PixelFormatEntry::BayerGb16,
+        PixelFormatEntry::BayerBg16,
+        PixelFormatEntry::Rgb8Planar,
+        PixelFormatEntry::Rgb10Planar,
+        PixelFormatEntry::Rgb12Planar,
+        PixelFormatEntry::Rgb16Planar,
+        PixelFormatEntry::Rgb8,
+        PixelFormatEntry::Rgb10,
+        PixelFormatEntry::Rgb12,
+        PixelFormatEntry::Rgb14,
+        PixelFormatEntry::Rgb16,
+        PixelFormatEntry::Bgr8,
+        PixelFormatEntry::Bgr10,
+        PixelFormatEntry::Bgr12,
+        PixelFormatEntry::Bgr14,
+        PixelFormatEntry::Bgr16,
+        PixelFormatEntry::Rgba8,
+        PixelFormatEntry::Bgra8,
+        PixelFormatEntry::Argb8,
+        PixelFormatEntry::Abgr8,
+        PixelFormatEntry::Yuv444_8,
+        PixelFormatEntry::Yuv422_8,
+        PixelFormatEntry::Yuv411_8,
+        PixelFormatEntry::Yuv420_8Planar,
+        PixelFormatEntry::Ycbcr444_8,
+        PixelFormatEntry::Ycbcr422_8,
+        PixelFormatEntry::Ycbcr411_8,
+        PixelFormatEntry::Ycbcr601_444_8,
+        PixelFormatEntry::Ycbcr601_422_8,
+        PixelFormatEntry::Ycbcr601_411_8,
+        PixelFormatEntry::Ycbcr709_444_8,
+        PixelFormatEntry::Ycbcr709_422_8,
+        PixelFormatEntry::Ycbcr709_411_8,
+        PixelFormatEntry::Mono10Packed,
+        PixelFormatEntry::Mono12Packed,
+    ];
+
+    /// GenICam PFNC name, or the SDK's own name for formats PFNC does not define.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PixelFormatEntry::Undefined => "Undefined",
+            PixelFormatEntry::Mono8 => "Mono8",
+            PixelFormatEntry::Mono8Signed => "Mono8s",
+            PixelFormatEntry::Mono10 => "Mono10",
+            PixelFormatEntry::Mono12 => "Mono12",
+            PixelFormatEntry::Mono14 => "Mono14",
+            PixelFormatEntry::Mono16 => "Mono16",
+            PixelFormatEntry::BayerGr8 => "BayerGR8",
+            PixelFormatEntry::BayerRg8 => "BayerRG8",
+            PixelFormatEntry::BayerGb8 => "BayerGB8",
+            PixelFormatEntry::BayerBg8 => "BayerBG8",
+            PixelFormatEntry::BayerGr10 => "BayerGR10",
+            PixelFormatEntry::BayerRg10 => "BayerRG10",
+            PixelFormatEntry::BayerGb10 => "BayerGB10",
+            PixelFormatEntry::BayerBg10 => "BayerBG10",
+            PixelFormatEntry::BayerGr12 => "BayerGR12",
+            PixelFormatEntry::BayerRg12 => "BayerRG12",
+            PixelFormatEntry::BayerGb12 => "BayerGB12",
+            PixelFormatEntry::BayerBg12 => "BayerBG12",
+            PixelFormatEntry::BayerGr16 => "BayerGR16",
+            PixelFormatEntry::BayerRg16 => "BayerRG16",
+            PixelFormatEntry::BayerGb16 => "BayerGB16",
+            PixelFormatEntry::BayerBg16 => "BayerBG16",
+            PixelFormatEntry::Rgb8Planar => "RGB8_Planar",
+            PixelFormatEntry::Rgb10Planar => "RGB10_Planar",
+            PixelFormatEntry::Rgb12Planar => "RGB12_Planar",
+            PixelFormatEntry::Rgb16Planar => "RGB16_Planar",
+            PixelFormatEntry::Rgb8 => "RGB8",
+            PixelFormatEntry::Rgb10 => "RGB10",
+            PixelFormatEntry::Rgb12 => "RGB12",
+            PixelFormatEntry::Rgb14 => "RGB14",
+            PixelFormatEntry::Rgb16 => "RGB16",
+            PixelFormatEntry::Bgr8 => "BGR8",
+            PixelFormatEntry::Bgr10 => "BGR10",
+            PixelFormatEntry::Bgr12 => "BGR12",
+            PixelFormatEntry::Bgr14 => "BGR14",
+            PixelFormatEntry::Bgr16 => "BGR16",
+            PixelFormatEntry::Rgba8 => "RGBa8",
+            PixelFormatEntry::Bgra8 => "BGRa8",
+            PixelFormatEntry::Argb8 => "ARGB8",
+            PixelFormatEntry::Abgr8 => "ABGR8",
+            PixelFormatEntry::Yuv444_8 => "YUV8_UYV",
+            PixelFormatEntry::Yuv422_8 => "YUV422_8",
+            PixelFormatEntry::Yuv411_8 => "YUV411_8_UYYVYY",
+            PixelFormatEntry::Yuv420_8Planar => "YUV420_8_Planar",
+            PixelFormatEntry::Ycbcr444_8 => "YCbCr8",
+            PixelFormatEntry::Ycbcr422_8 => "YCbCr422_8",
+            PixelFormatEntry::Ycbcr411_8 => "YCbCr411_8",
+            PixelFormatEntry::Ycbcr601_444_8 => "YCbCr601_8_CbYCr",
+            PixelFormatEntry::Ycbcr601_422_8 => "YCbCr601_422_8",
+            PixelFormatEntry::Ycbcr601_411_8 => "YCbCr601_411_8_CbYYCrYY",
+            PixelFormatEntry::Ycbcr709_444_8 => "YCbCr709_8_CbYCr",
+            PixelFormatEntry::Ycbcr709_422_8 => "YCbCr709_422_8",
+            PixelFormatEntry::Ycbcr709_411_8 => "YCbCr709_411_8_CbYYCrYY",
+            PixelFormatEntry::Mono10Packed => "Mono10Packed",
+            PixelFormatEntry::Mono12Packed => "Mono12Packed",
+        }
+    }
+
+    /// Bits one pixel occupies in the buffer, the GX_PIXEL_xBIT field of the value.
+    pub fn bits_per_pixel(&self) -> u32 {
+        (*self as u32 >> 16) & 0xff
+    }
+
+    /// Significant bits per channel, e.g. 10 for Mono10 which occupies 16.
+    pub fn bit_depth(&self) -> u32 {
+        use PixelFormatEntry::*;
+        match self {
+            Undefined => 0,
+            Mono10 | BayerGr10 | BayerRg10 | BayerGb10 | BayerBg10 | Rgb10 | Bgr10
+            | Rgb10Planar | Mono10Packed => 10,
+            Mono12 | BayerGr12 | BayerRg12 | BayerGb12 | BayerBg12 | Rgb12 | Bgr12
+            | Rgb12Planar | Mono12Packed => 12,
+            Mono14 | Rgb14 | Bgr14 => 14,
+            Mono16 | BayerGr16 | BayerRg16 | BayerGb16 | BayerBg16 | Rgb16 | Bgr16
+            | Rgb16Planar => 16,
+            _ => 8,
+        }
+    }
+
+    /// Colour channels of a decoded pixel: 3 for the RGB, YUV and YCbCr formats, 4 with alpha
+    /// and 1 for mono and for the Bayer formats until demosaiced.
+    ///
+    /// This is not the sample count in the buffer, the subsampled YUV and YCbCr formats carry
+    /// fewer samples per pixel there, see `bits_per_pixel`.
+    pub fn channels(&self) -> u32 {
+        use PixelFormatEntry::*;
+        match self {
+            Undefined => 0,
+            Rgba8 | Bgra8 | Argb8 | Abgr8 => 4,
+            _ if self.is_color() => 3,
+            _ => 1,
+        }
+    }
+
+    /// The Bayer pattern of the raw formats, `None` for the others.
+    pub fn bayer_pattern(&self) -> Option<PixelColorFilterEntry> {
+        use PixelFormatEntry::*;
+        match self {
+            BayerRg8 | BayerRg10 | BayerRg12 | BayerRg16 => Some(PixelColorFilterEntry::BayerRG),
+            BayerGb8 | BayerGb10 | BayerGb12 | BayerGb16 => Some(PixelColorFilterEntry::BayerGB),
+            BayerGr8 | BayerGr10 | BayerGr12 | BayerGr16 => Some(PixelColorFilterEntry::BayerGR),
+            BayerBg8 | BayerBg10 | BayerBg12 | BayerBg16 => Some(PixelColorFilterEntry::BayerBG),
+            _ => None,
+        }
+    }
+
+    pub fn is_bayer(&self) -> bool {
+        self.bayer_pattern().is_some()
+    }
+
+    /// Single channel formats, Bayer formats carry the GX_PIXEL_MONO flag too.
+    pub fn is_mono(&self) -> bool {
+        *self as u32 & PIXEL_MONO != 0
+    }
+
+    pub fn is_color(&self) -> bool {
+        *self as u32 & PIXEL_COLOR != 0
+    }
+
+    /// Pixels share bytes, e.g. Mono12Packed stores 2 pixels in 3 bytes.
+    pub fn is_packed(&self) -> bool {
+        matches!(
+            self,
+            PixelFormatEntry::Mono10Packed | PixelFormatEntry::Mono12Packed
+        )
+    }
+
+    /// Each channel in its own plane instead of interleaved.
+    pub fn is_planar(&self) -> bool {
+        use PixelFormatEntry::*;
+        matches!(
+            self,
+            Rgb8Planar | Rgb10Planar | Rgb12Planar | Rgb16Planar | Yuv420_8Planar
+        )
+    }
+
+    /// Bytes of a `width` x `height` image in this format.
+    pub fn image_size(&self, width: usize, height: usize) -> usize {
+        (width * height * self.bits_per_pixel() as usize).div_ceil(8)
+    }
+}
+
+impl TryFrom<u32> for PixelFormatEntry {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        PixelFormatEntry::ALL
+            .into_iter()
+            .find(|format| *format as u32 == value)
+            .ok_or_else(|| {
+                Error::new(ErrorKind::InvalidParameter(format!(
+                    "unknown pixel format 0x{:08X}",
+                    value
+                )))
+            })
+    }
+}
+
+// 帧数据里的nPixelFormat是i32，按位转成u32，不会丢信息
+impl TryFrom<i32> for PixelFormatEntry {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        PixelFormatEntry::try_from(value as u32)
+    }
+}
+
+impl TryFrom<i64> for PixelFormatEntry {
+    type Error = Error;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        let value = u32::try_from(value).map_err(|_| {
+            Error::new(ErrorKind::InvalidParameter(format!(
+                "unknown pixel format {}",
+                value
+            )))
+        })?;
+        PixelFormatEntry::try_from(value)
+    }
+}
+
+impl From<PixelFormatEntry> for u32 {
+    fn from(format: PixelFormatEntry) -> Self {
+        format as u32
+    }
+}
+
+impl From<PixelFormatEntry> for i64 {
+    fn from(format: PixelFormatEntry) -> Self {
+        format as u32 as i64
+    }
+}
+
+impl fmt::Display for PixelFormatEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for PixelFormatEntry {
+    type Err = Error;
+
+    /// Parse a PFNC name, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PixelFormatEntry::ALL
+            .into_iter()
+            .find(|format| format.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                Error::new(ErrorKind::InvalidParameter(format!(
+                    "unknown pixel format name {:?}",
+                    s
+                )))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    #[test]
+    fn every_format_round_trips_its_value_and_name() {
+        let mut names = HashSet::new();
+        for format in PixelFormatEntry::ALL {
+            let value = format as u32;
+            assert_eq!(PixelFormatEntry::try_from(value).unwrap(), format);
+            assert_eq!(PixelFormatEntry::try_from(value as i32).unwrap(), format);
+            assert_eq!(
+                PixelFormatEntry::try_from(i64::from(format)).unwrap(),
+                format
+            );
+
+            let name = format.to_string();
+            assert_eq!(name, format.name());
+            assert_eq!(name.parse::<PixelFormatEntry>().unwrap(), format);
+            assert_eq!(
+                name.to_ascii_lowercase()
+                    .parse::<PixelFormatEntry>()
+                    .unwrap(),
+                format
+            );
+            assert!(names.insert(name), "{} is named twice", format.name());
+        }
+        assert_eq!(names.len(), PixelFormatEntry::ALL.len());
+    }
+
+    #[test]
+    fn unknown_values_and_names_are_errors() {
+        assert!(PixelFormatEntry::try_from(0x0108_1234u32).is_err());
+        assert!(PixelFormatEntry::try_from(-1i64).is_err());
+        assert!(PixelFormatEntry::try_from(i64::from(u32::MAX) + 1).is_err());
+        assert!("Mono9".parse::<PixelFormatEntry>().is_err());
+    }
+
+    #[test]
+    fn bits_per_pixel_covers_the_bit_depth() {
+        for format in PixelFormatEntry::ALL {
+            assert!(format.bit_depth() <= 16, "{}", format);
+            if format != PixelFormatEntry::Undefined && !format.is_packed() {
+                assert!(format.bits_per_pixel() >= format.bit_depth(), "{}", format);
+            }
+        }
+    }
+
+    #[test]
+    fn packed_formats_share_bytes_between_pixels() {
+        use PixelFormatEntry::*;
+
+        for format in [Mono10Packed, Mono12Packed] {
+            assert!(format.is_packed() && format.is_mono());
+            assert_eq!(format.bits_per_pixel(), 12);
+            assert_eq!(format.channels(), 1);
+            assert_eq!(format.image_size(2, 2), 6);
+            assert_eq!(format.image_size(3, 1), 5);
+        }
+        assert_eq!(Mono10Packed.bit_depth(), 10);
+        assert_eq!(Mono12Packed.bit_depth(), 12);
+
+        assert!(!Mono12.is_packed());
+        assert_eq!(Mono12.bits_per_pixel(), 16);
+        assert_eq!(Mono12.bit_depth(), 12);
+        assert_eq!(Mono12.image_size(2, 2), 8);
+    }
+
+    #[test]
+    fn planar_formats_count_every_plane() {
+        use PixelFormatEntry::*;
+
+        assert!(Rgb8Planar.is_planar() && Yuv420_8Planar.is_planar());
+        assert!(!Rgb8.is_planar());
+        assert_eq!(Rgb8Planar.image_size(4, 2), 24);
+        assert_eq!(Rgb10Planar.bits_per_pixel(), 48);
+        assert_eq!(Rgb10Planar.bit_depth(), 10);
+        assert_eq!(Rgb16Planar.image_size(4, 2), 48);
+        // 4:2:0每个像素平均1.5字节
+        assert_eq!(Yuv420_8Planar.bits_per_pixel(), 12);
+        assert_eq!(Yuv420_8Planar.image_size(4, 2), 12);
+    }
+
+    #[test]
+    fn channels_count_decoded_colour_channels() {
+        use PixelFormatEntry::*;
+
+        assert_eq!(Undefined.channels(), 0);
+        assert_eq!(Mono8.channels(), 1);
+        assert_eq!(BayerRg12.channels(), 1);
+        assert_eq!(Bgr8.channels(), 3);
+        assert_eq!(Argb8.channels(), 4);
+        for format in [Yuv444_8, Yuv422_8, Yuv411_8, Ycbcr709_422_8] {
+            assert_eq!(format.channels(), 3, "{}", format);
+        }
+        assert_eq!(Yuv422_8.bits_per_pixel(), 16);
+        assert_eq!(Yuv411_8.image_size(4, 1), 6);
+    }
 }
@@ -32,7 +32,7 @@ pub fn convert_to_frame_data(facade: &GXFrameDataFacade) -> GX_FRAME_DATA {
 }
 
 pub fn fetch_frame_data(
-    gx: &GXInstance,
+    gx: &dyn GXInterface,
     device_handle: GX_DEV_HANDLE,
 ) -> Result<(GXFrameDataFacade, Vec<u8>)> {
     let mut width_value: i64 = 0;
@@ -8,7 +8,12 @@ use crate::raw::gx_enum::*;
 pub fn match_feature_type(feature_id: GX_FEATURE_ID) -> GX_FEATURE_TYPE {
     let feature_id_str = format!("{:?}", feature_id);
     println!("{:?}", feature_id_str);
-    let feature_type_str = feature_id_str.split("_").collect::<Vec<&str>>()[1];
+    let feature_id_parts = feature_id_str.split("_").collect::<Vec<&str>>();
+    // 设备层和数据流层的feature会多一个DEV/DS前缀，例如GX_DS_INT_LOST_FRAME_COUNT，类型在它后面
+    let feature_type_str = match feature_id_parts[1] {
+        "DEV" | "DS" => feature_id_parts[2],
+        feature_type_str => feature_type_str,
+    };
     println!("{:?}", feature_type_str);
     match feature_type_str {
         "INT" => GX_FEATURE_TYPE::GX_FEATURE_INT,